@@ -0,0 +1,422 @@
+//! The Challonge REST API.
+//!
+//! Tournaments are identified by their URL, like `my_weekly`, or `myorg-my_weekly` for
+//! tournaments that belong to an organization. Challonge has nowhere to link a Discord
+//! account, so organizers put each participant's Discord ID in the participant's notes
+//! (`misc` in the API).
+
+use async_trait::async_trait;
+use reqwest::{RequestBuilder, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::json;
+
+use super::{BracketService, BridgeError, RemoteEntrant, RemoteSet, SetScore, SetState};
+
+pub const API_URL: &str = "https://api.challonge.com/v1";
+
+pub struct Challonge {
+    http: reqwest::Client,
+    url: String,
+    api_key: String,
+}
+
+impl Challonge {
+    /// Creates a client for the API at `url`, which is [`API_URL`] outside of tests.
+    #[must_use]
+    pub fn new(url: impl Into<String>, api_key: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: url.into(),
+            api_key: api_key.into(),
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+        event: &str,
+    ) -> Result<T, BridgeError> {
+        if self.api_key.is_empty() {
+            return Err(BridgeError::NotConfigured);
+        }
+
+        let response = request.query(&[("api_key", &self.api_key)]).send().await?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND => Err(BridgeError::NotFound(event.to_string())),
+            StatusCode::UNAUTHORIZED => {
+                Err(BridgeError::Api("the API key was rejected".to_string()))
+            }
+            StatusCode::UNPROCESSABLE_ENTITY => {
+                let errors: Errors = response.json().await?;
+                Err(BridgeError::Api(errors.errors.join(", ")))
+            }
+            _ => Ok(response.error_for_status()?.json().await?),
+        }
+    }
+
+    fn endpoint(&self, event: &str, path: &str) -> String {
+        format!("{}/tournaments/{}/{}.json", self.url, event, path)
+    }
+}
+
+#[async_trait]
+impl BracketService for Challonge {
+    async fn entrants(&self, event: &str) -> Result<Vec<RemoteEntrant>, BridgeError> {
+        let participants: Vec<ParticipantWrapper> = self
+            .send(self.http.get(self.endpoint(event, "participants")), event)
+            .await?;
+
+        Ok(participants
+            .into_iter()
+            .map(|p| RemoteEntrant {
+                id: p.participant.id.to_string(),
+                discord_id: p.participant.misc.as_deref().and_then(discord_id),
+                name: p.participant.name,
+            })
+            .collect())
+    }
+
+    async fn sets(&self, event: &str) -> Result<Vec<RemoteSet>, BridgeError> {
+        let matches: Vec<MatchWrapper> = self
+            .send(self.http.get(self.endpoint(event, "matches")), event)
+            .await?;
+
+        // Losers rounds are negative, and only double elimination brackets have them.
+        let double_elimination = matches.iter().any(|m| m.r#match.round < 0);
+
+        Ok(matches
+            .into_iter()
+            .map(|m| {
+                let m = m.r#match;
+                RemoteSet {
+                    id: m.id.to_string(),
+                    round: round_name(m.round, double_elimination),
+                    entrants: [
+                        m.player1_id.map(|id| id.to_string()),
+                        m.player2_id.map(|id| id.to_string()),
+                    ],
+                    winner: m.winner_id.map(|id| id.to_string()),
+                    state: match m.state.as_str() {
+                        "open" => SetState::Called,
+                        "complete" => SetState::Completed,
+                        _ => SetState::Waiting,
+                    },
+                }
+            })
+            .collect())
+    }
+
+    async fn report(&self, event: &str, set: &str, score: &SetScore) -> Result<(), BridgeError> {
+        let path = format!("matches/{set}");
+
+        // Scores are written from player one's side, so the match is fetched to find out
+        // which player that is.
+        let current: MatchWrapper = self
+            .send(self.http.get(self.endpoint(event, &path)), event)
+            .await?;
+
+        let winner: u64 = score
+            .winner
+            .parse()
+            .map_err(|_| BridgeError::Api(format!("\"{}\" isn't a participant", score.winner)))?;
+        let (won, lost) = score.games.unwrap_or((1, 0));
+        let player_one_won = current.r#match.player1_id == Some(winner);
+        let scores = if player_one_won {
+            format!("{won}-{lost}")
+        } else {
+            format!("{lost}-{won}")
+        };
+
+        let _: MatchWrapper = self
+            .send(
+                self.http.put(self.endpoint(event, &path)).json(&json!({
+                    "match": { "scores_csv": scores, "winner_id": winner }
+                })),
+                event,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Turns a tournament's link into the ID that the API expects. Anything that isn't a
+/// Challonge link is assumed to be an ID already.
+#[must_use]
+pub fn event_id(event: &str) -> String {
+    let event = event.trim().trim_end_matches('/');
+    let link = event
+        .strip_prefix("https://")
+        .or_else(|| event.strip_prefix("http://"))
+        .unwrap_or(event);
+
+    let Some((host, path)) = link.split_once('/') else {
+        return event.to_string();
+    };
+
+    // Some links have a language, like `challonge.com/fr/my_weekly`.
+    let url = path.rsplit('/').next().unwrap_or(path);
+    match host.strip_suffix("challonge.com") {
+        Some("" | "www.") => url.to_string(),
+        Some(subdomain) => format!("{}-{}", subdomain.trim_end_matches('.'), url),
+        None => event.to_string(),
+    }
+}
+
+/// Reads a Discord user from a participant's notes, which can hold an ID or a mention.
+fn discord_id(misc: &str) -> Option<u64> {
+    let misc = misc.trim();
+    misc.strip_prefix("<@")
+        .and_then(|m| m.strip_suffix('>'))
+        .map_or(misc, |m| m.trim_start_matches('!'))
+        .parse()
+        .ok()
+}
+
+fn round_name(round: i32, double_elimination: bool) -> String {
+    if round < 0 {
+        format!("Losers Round {}", -round)
+    } else if double_elimination {
+        format!("Winners Round {round}")
+    } else {
+        format!("Round {round}")
+    }
+}
+
+#[derive(Deserialize)]
+struct Errors {
+    errors: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ParticipantWrapper {
+    participant: Participant,
+}
+
+#[derive(Deserialize)]
+struct Participant {
+    id: u64,
+    name: String,
+    misc: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct MatchWrapper {
+    r#match: Match,
+}
+
+#[derive(Deserialize)]
+struct Match {
+    id: u64,
+    round: i32,
+    state: String,
+    player1_id: Option<u64>,
+    player2_id: Option<u64>,
+    winner_id: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        matchers::{body_partial_json, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    const EVENT: &str = "my_weekly";
+
+    async fn server() -> (MockServer, Challonge) {
+        let server = MockServer::start().await;
+        let client = Challonge::new(server.uri(), "secret");
+        (server, client)
+    }
+
+    fn open_match() -> serde_json::Value {
+        json!({ "match": {
+            "id": 501,
+            "round": 1,
+            "state": "open",
+            "player1_id": 11,
+            "player2_id": 12,
+            "winner_id": null,
+        } })
+    }
+
+    #[test]
+    fn test_event_id() {
+        assert_eq!(event_id("my_weekly"), "my_weekly");
+        assert_eq!(event_id("https://challonge.com/my_weekly"), "my_weekly");
+        assert_eq!(event_id("challonge.com/fr/my_weekly/"), "my_weekly");
+        assert_eq!(
+            event_id("https://myorg.challonge.com/my_weekly"),
+            "myorg-my_weekly"
+        );
+    }
+
+    #[test]
+    fn test_discord_id() {
+        assert_eq!(
+            discord_id("80351110224678912"),
+            Some(80_351_110_224_678_912)
+        );
+        assert_eq!(
+            discord_id(" <@!80351110224678912> "),
+            Some(80_351_110_224_678_912)
+        );
+        assert_eq!(discord_id("ask me on twitter"), None);
+    }
+
+    #[tokio::test]
+    async fn test_entrants() {
+        let (server, client) = server().await;
+
+        Mock::given(method("GET"))
+            .and(path("/tournaments/my_weekly/participants.json"))
+            .and(query_param("api_key", "secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "participant": { "id": 11, "name": "Alice", "misc": "<@80351110224678912>" } },
+                { "participant": { "id": 12, "name": "Bob", "misc": null } },
+            ])))
+            .mount(&server)
+            .await;
+
+        let entrants = client.entrants(EVENT).await.unwrap();
+
+        assert_eq!(
+            entrants,
+            [
+                RemoteEntrant {
+                    id: "11".to_string(),
+                    name: "Alice".to_string(),
+                    discord_id: Some(80_351_110_224_678_912),
+                },
+                RemoteEntrant {
+                    id: "12".to_string(),
+                    name: "Bob".to_string(),
+                    discord_id: None,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sets() {
+        let (server, client) = server().await;
+
+        Mock::given(method("GET"))
+            .and(path("/tournaments/my_weekly/matches.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                open_match(),
+                { "match": {
+                    "id": 502,
+                    "round": -1,
+                    "state": "pending",
+                    "player1_id": null,
+                    "player2_id": null,
+                    "winner_id": null,
+                } },
+            ])))
+            .mount(&server)
+            .await;
+
+        let sets = client.sets(EVENT).await.unwrap();
+
+        assert_eq!(
+            sets,
+            [
+                RemoteSet {
+                    id: "501".to_string(),
+                    round: "Winners Round 1".to_string(),
+                    entrants: [Some("11".to_string()), Some("12".to_string())],
+                    winner: None,
+                    state: SetState::Called,
+                },
+                RemoteSet {
+                    id: "502".to_string(),
+                    round: "Losers Round 1".to_string(),
+                    entrants: [None, None],
+                    winner: None,
+                    state: SetState::Waiting,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_report_uses_player_one_order() {
+        let (server, client) = server().await;
+
+        Mock::given(method("GET"))
+            .and(path("/tournaments/my_weekly/matches/501.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(open_match()))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/tournaments/my_weekly/matches/501.json"))
+            .and(body_partial_json(json!({
+                "match": { "scores_csv": "1-2", "winner_id": 12 }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(open_match()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        client
+            .report(
+                EVENT,
+                "501",
+                &SetScore {
+                    winner: "12".to_string(),
+                    loser: "11".to_string(),
+                    games: Some((2, 1)),
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_errors() {
+        let (server, client) = server().await;
+
+        Mock::given(method("GET"))
+            .and(path("/tournaments/missing/matches.json"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/tournaments/my_weekly/matches/501.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(open_match()))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .respond_with(ResponseTemplate::new(422).set_body_json(json!({
+                "errors": ["Match is already complete"]
+            })))
+            .mount(&server)
+            .await;
+
+        assert!(matches!(
+            client.sets("missing").await,
+            Err(BridgeError::NotFound(e)) if e == "missing"
+        ));
+
+        let score = SetScore {
+            winner: "11".to_string(),
+            loser: "12".to_string(),
+            games: None,
+        };
+        assert!(matches!(
+            client.report(EVENT, "501", &score).await,
+            Err(BridgeError::Api(e)) if e == "Match is already complete"
+        ));
+
+        let unconfigured = Challonge::new(server.uri(), "");
+        assert!(matches!(
+            unconfigured.sets(EVENT).await,
+            Err(BridgeError::NotConfigured)
+        ));
+    }
+}
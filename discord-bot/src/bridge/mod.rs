@@ -0,0 +1,93 @@
+//! Bridges to external bracket services.
+//!
+//! Tournaments that are run on another site are mirrored into the bot: entrants and sets
+//! are imported from the service, and results that players confirm in Discord are
+//! reported back to it. Every service is accessed through [`BracketService`], so the
+//! Discord side doesn't care which one a tournament uses.
+
+use std::fmt::Display;
+
+use async_trait::async_trait;
+
+pub mod challonge;
+pub mod startgg;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoteEntrant {
+    /// The service's ID for the entrant.
+    pub id: String,
+    pub name: String,
+    /// The Discord account the entrant linked on the service, if any.
+    pub discord_id: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SetState {
+    /// One or both players are still unknown, or the set hasn't been called yet.
+    Waiting,
+    /// The set has been called, or is being played.
+    Called,
+    Completed,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoteSet {
+    pub id: String,
+    /// A display name for the round, like "Winners Semi-Final".
+    pub round: String,
+    /// The IDs of the entrants in the set, once they're known.
+    pub entrants: [Option<String>; 2],
+    pub winner: Option<String>,
+    pub state: SetState,
+}
+
+/// A result to report to the service.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetScore {
+    pub winner: String,
+    pub loser: String,
+    /// The game count for the winner and the loser, if the players gave one.
+    pub games: Option<(u32, u32)>,
+}
+
+#[derive(Debug)]
+pub enum BridgeError {
+    /// The service couldn't be reached, or sent something that couldn't be read.
+    Http(reqwest::Error),
+    /// The service rejected the request.
+    Api(String),
+    /// The event doesn't exist, or the credentials can't see it.
+    NotFound(String),
+    /// The bridge isn't configured, usually because there's no token.
+    NotConfigured,
+}
+
+impl std::error::Error for BridgeError {}
+
+impl Display for BridgeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BridgeError::Http(e) => write!(f, "the bracket service couldn't be reached: {e}"),
+            BridgeError::Api(e) => write!(f, "the bracket service returned an error: {e}"),
+            BridgeError::NotFound(event) => write!(f, "event \"{event}\" couldn't be found"),
+            BridgeError::NotConfigured => write!(f, "this bracket service isn't set up"),
+        }
+    }
+}
+
+impl From<reqwest::Error> for BridgeError {
+    fn from(e: reqwest::Error) -> Self {
+        BridgeError::Http(e)
+    }
+}
+
+#[async_trait]
+pub trait BracketService: Send + Sync {
+    /// Every entrant of the event.
+    async fn entrants(&self, event: &str) -> Result<Vec<RemoteEntrant>, BridgeError>;
+
+    /// Every set of the event, including the ones that are still waiting for players.
+    async fn sets(&self, event: &str) -> Result<Vec<RemoteSet>, BridgeError>;
+
+    async fn report(&self, event: &str, set: &str, score: &SetScore) -> Result<(), BridgeError>;
+}
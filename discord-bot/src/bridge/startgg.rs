@@ -0,0 +1,496 @@
+//! The start.gg GraphQL API.
+//!
+//! Events are identified by their slug, like `tournament/genesis-9/event/melee-singles`.
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+
+use super::{BracketService, BridgeError, RemoteEntrant, RemoteSet, SetScore, SetState};
+
+pub const API_URL: &str = "https://api.start.gg/gql/alpha";
+
+/// start.gg limits how many objects a single query can return, so lists are paginated.
+const PAGE_SIZE: u32 = 50;
+
+const ENTRANTS_QUERY: &str = "query EventEntrants($slug: String!, $page: Int!, $perPage: Int!) {
+  event(slug: $slug) {
+    entrants(query: { page: $page, perPage: $perPage }) {
+      pageInfo { totalPages }
+      nodes {
+        id
+        name
+        participants { user { authorizations(types: [DISCORD]) { externalId } } }
+      }
+    }
+  }
+}";
+
+const SETS_QUERY: &str = "query EventSets($slug: String!, $page: Int!, $perPage: Int!) {
+  event(slug: $slug) {
+    sets(page: $page, perPage: $perPage, sortType: STANDARD) {
+      pageInfo { totalPages }
+      nodes {
+        id
+        state
+        fullRoundText
+        winnerId
+        slots { entrant { id } }
+      }
+    }
+  }
+}";
+
+const REPORT_MUTATION: &str =
+    "mutation ReportSet($setId: ID!, $winnerId: ID!, $gameData: [BracketSetGameDataInput]) {
+  reportBracketSet(setId: $setId, winnerId: $winnerId, gameData: $gameData) { id }
+}";
+
+pub struct StartGg {
+    http: reqwest::Client,
+    url: String,
+    token: String,
+}
+
+impl StartGg {
+    /// Creates a client for the API at `url`, which is [`API_URL`] outside of tests.
+    #[must_use]
+    pub fn new(url: impl Into<String>, token: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: url.into(),
+            token: token.into(),
+        }
+    }
+
+    async fn query<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Value,
+    ) -> Result<T, BridgeError> {
+        if self.token.is_empty() {
+            return Err(BridgeError::NotConfigured);
+        }
+
+        let response: Response<T> = self
+            .http
+            .post(&self.url)
+            .bearer_auth(&self.token)
+            .json(&json!({ "query": query, "variables": variables }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if !response.errors.is_empty() {
+            return Err(BridgeError::Api(
+                response
+                    .errors
+                    .into_iter()
+                    .map(|e| e.message)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ));
+        }
+
+        response
+            .data
+            .ok_or_else(|| BridgeError::Api("the response was empty".to_string()))
+    }
+
+    /// Fetches every page of a list on an event.
+    async fn pages<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        event: &str,
+    ) -> Result<Vec<T>, BridgeError> {
+        let mut nodes = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let data: EventData<Connection<T>> = self
+                .query(
+                    query,
+                    json!({ "slug": event, "page": page, "perPage": PAGE_SIZE }),
+                )
+                .await?;
+
+            let connection = data
+                .event
+                .ok_or_else(|| BridgeError::NotFound(event.to_string()))?
+                .list;
+            nodes.extend(connection.nodes);
+
+            if page >= connection.page_info.total_pages {
+                return Ok(nodes);
+            }
+            page += 1;
+        }
+    }
+}
+
+#[async_trait]
+impl BracketService for StartGg {
+    async fn entrants(&self, event: &str) -> Result<Vec<RemoteEntrant>, BridgeError> {
+        let nodes: Vec<EntrantNode> = self.pages(ENTRANTS_QUERY, event).await?;
+
+        Ok(nodes
+            .into_iter()
+            .map(|node| {
+                // Teams have more than one participant, and the first linked account is used.
+                let discord_id = node
+                    .participants
+                    .iter()
+                    .filter_map(|p| p.user.as_ref()?.authorizations.as_ref())
+                    .flatten()
+                    .find_map(|a| a.external_id.as_ref()?.parse().ok());
+
+                RemoteEntrant {
+                    id: node.id.into(),
+                    name: node.name,
+                    discord_id,
+                }
+            })
+            .collect())
+    }
+
+    async fn sets(&self, event: &str) -> Result<Vec<RemoteSet>, BridgeError> {
+        let nodes: Vec<SetNode> = self.pages(SETS_QUERY, event).await?;
+
+        Ok(nodes
+            .into_iter()
+            .map(|node| {
+                let mut slots = node
+                    .slots
+                    .into_iter()
+                    .map(|s| s.entrant.map(|e| String::from(e.id)));
+
+                RemoteSet {
+                    id: node.id.into(),
+                    round: node.full_round_text.unwrap_or_default(),
+                    entrants: [slots.next().flatten(), slots.next().flatten()],
+                    winner: node.winner_id.map(String::from),
+                    // See `ActivityState` in the start.gg schema.
+                    state: match node.state {
+                        Some(2 | 6) => SetState::Called,
+                        Some(3) => SetState::Completed,
+                        _ => SetState::Waiting,
+                    },
+                }
+            })
+            .collect())
+    }
+
+    async fn report(&self, _event: &str, set: &str, score: &SetScore) -> Result<(), BridgeError> {
+        // start.gg only needs to know who won each game, not the order they were played in.
+        let game_data = score.games.map(|(won, lost)| {
+            std::iter::repeat_n(&score.winner, won as usize)
+                .chain(std::iter::repeat_n(&score.loser, lost as usize))
+                .enumerate()
+                .map(|(i, winner)| json!({ "gameNum": i + 1, "winnerId": winner }))
+                .collect::<Vec<_>>()
+        });
+
+        let _: Value = self
+            .query(
+                REPORT_MUTATION,
+                json!({ "setId": set, "winnerId": score.winner, "gameData": game_data }),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct Response<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<ResponseError>,
+}
+
+#[derive(Deserialize)]
+struct ResponseError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct EventData<T> {
+    event: Option<EventList<T>>,
+}
+
+/// The event, with whichever list was queried.
+#[derive(Deserialize)]
+struct EventList<T> {
+    #[serde(alias = "entrants", alias = "sets")]
+    list: T,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Connection<T> {
+    page_info: PageInfo,
+    nodes: Vec<T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    total_pages: u32,
+}
+
+/// start.gg returns most IDs as numbers, but sets that haven't started yet can have
+/// string IDs like `preview_123_1`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Id {
+    Number(u64),
+    Text(String),
+}
+
+impl From<Id> for String {
+    fn from(id: Id) -> Self {
+        match id {
+            Id::Number(id) => id.to_string(),
+            Id::Text(id) => id,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct EntrantNode {
+    id: Id,
+    name: String,
+    #[serde(default)]
+    participants: Vec<Participant>,
+}
+
+#[derive(Deserialize)]
+struct Participant {
+    user: Option<User>,
+}
+
+#[derive(Deserialize)]
+struct User {
+    authorizations: Option<Vec<Authorization>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Authorization {
+    external_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SetNode {
+    id: Id,
+    state: Option<u8>,
+    full_round_text: Option<String>,
+    winner_id: Option<Id>,
+    #[serde(default)]
+    slots: Vec<Slot>,
+}
+
+#[derive(Deserialize)]
+struct Slot {
+    entrant: Option<SlotEntrant>,
+}
+
+#[derive(Deserialize)]
+struct SlotEntrant {
+    id: Id,
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        matchers::{body_partial_json, body_string_contains, header, method},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    const EVENT: &str = "tournament/weekly-1/event/singles";
+
+    async fn server() -> (MockServer, StartGg) {
+        let server = MockServer::start().await;
+        let client = StartGg::new(server.uri(), "secret");
+        (server, client)
+    }
+
+    #[tokio::test]
+    async fn test_entrants_are_paginated() {
+        let (server, client) = server().await;
+
+        let page = |page: u32, nodes: Value| {
+            Mock::given(method("POST"))
+                .and(header("authorization", "Bearer secret"))
+                .and(body_string_contains("EventEntrants"))
+                .and(body_partial_json(json!({ "variables": { "page": page } })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "data": { "event": { "entrants": {
+                        "pageInfo": { "totalPages": 2 },
+                        "nodes": nodes,
+                    } } }
+                })))
+        };
+
+        page(
+            1,
+            json!([{
+                "id": 101,
+                "name": "Alice",
+                "participants": [{ "user": { "authorizations": [{ "externalId": "80351110224678912" }] } }],
+            }]),
+        )
+        .mount(&server)
+        .await;
+        page(
+            2,
+            json!([{ "id": 102, "name": "Bob", "participants": [{ "user": null }] }]),
+        )
+        .mount(&server)
+        .await;
+
+        let entrants = client.entrants(EVENT).await.unwrap();
+
+        assert_eq!(
+            entrants,
+            [
+                RemoteEntrant {
+                    id: "101".to_string(),
+                    name: "Alice".to_string(),
+                    discord_id: Some(80_351_110_224_678_912),
+                },
+                RemoteEntrant {
+                    id: "102".to_string(),
+                    name: "Bob".to_string(),
+                    discord_id: None,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sets() {
+        let (server, client) = server().await;
+
+        Mock::given(method("POST"))
+            .and(body_string_contains("EventSets"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "event": { "sets": {
+                    "pageInfo": { "totalPages": 1 },
+                    "nodes": [
+                        {
+                            "id": 9001,
+                            "state": 6,
+                            "fullRoundText": "Winners Round 1",
+                            "winnerId": null,
+                            "slots": [{ "entrant": { "id": 101 } }, { "entrant": { "id": 102 } }],
+                        },
+                        {
+                            "id": "preview_9_2",
+                            "state": 1,
+                            "fullRoundText": "Winners Final",
+                            "winnerId": null,
+                            "slots": [{ "entrant": null }, { "entrant": null }],
+                        },
+                    ],
+                } } }
+            })))
+            .mount(&server)
+            .await;
+
+        let sets = client.sets(EVENT).await.unwrap();
+
+        assert_eq!(sets[0].id, "9001");
+        assert_eq!(sets[0].state, SetState::Called);
+        assert_eq!(
+            sets[0].entrants,
+            [Some("101".to_string()), Some("102".to_string())]
+        );
+        assert_eq!(sets[1].id, "preview_9_2");
+        assert_eq!(sets[1].state, SetState::Waiting);
+        assert_eq!(sets[1].entrants, [None, None]);
+    }
+
+    #[tokio::test]
+    async fn test_report() {
+        let (server, client) = server().await;
+
+        Mock::given(method("POST"))
+            .and(body_string_contains("reportBracketSet"))
+            .and(body_partial_json(json!({ "variables": {
+                "setId": "9001",
+                "winnerId": "101",
+                "gameData": [
+                    { "gameNum": 1, "winnerId": "101" },
+                    { "gameNum": 2, "winnerId": "101" },
+                    { "gameNum": 3, "winnerId": "102" },
+                ],
+            } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "reportBracketSet": [{ "id": 9001 }] }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        client
+            .report(
+                EVENT,
+                "9001",
+                &SetScore {
+                    winner: "101".to_string(),
+                    loser: "102".to_string(),
+                    games: Some((2, 1)),
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_errors() {
+        let (server, client) = server().await;
+
+        Mock::given(method("POST"))
+            .and(body_string_contains("EventSets"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({ "data": { "event": null } })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("reportBracketSet"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": null,
+                "errors": [{ "message": "Set is already completed" }],
+            })))
+            .mount(&server)
+            .await;
+
+        assert!(matches!(
+            client.sets(EVENT).await,
+            Err(BridgeError::NotFound(e)) if e == EVENT
+        ));
+
+        let score = SetScore {
+            winner: "101".to_string(),
+            loser: "102".to_string(),
+            games: None,
+        };
+        assert!(matches!(
+            client.report(EVENT, "9001", &score).await,
+            Err(BridgeError::Api(e)) if e == "Set is already completed"
+        ));
+
+        let unconfigured = StartGg::new(server.uri(), "");
+        assert!(matches!(
+            unconfigured.sets(EVENT).await,
+            Err(BridgeError::NotConfigured)
+        ));
+    }
+}
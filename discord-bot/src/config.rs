@@ -13,6 +13,8 @@ pub struct Config {
     pub log_as_json: bool,
     pub log_level: LogLevel,
     pub db: DatabaseSettings,
+    pub startgg: StartGgSettings,
+    pub challonge: ChallongeSettings,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -42,6 +44,38 @@ impl Default for DatabaseSettings {
     }
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StartGgSettings {
+    /// A personal access token from the start.gg developer settings. Tournaments can't be
+    /// imported from start.gg without one.
+    pub token: Option<String>,
+    pub api_url: String,
+}
+
+impl Default for StartGgSettings {
+    fn default() -> Self {
+        Self {
+            token: None,
+            api_url: bot::bridge::startgg::API_URL.to_owned(),
+        }
+    }
+}
+
+/// Challonge API keys belong to a server, so they're set with `/tournament credentials`
+/// rather than here.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChallongeSettings {
+    pub api_url: String,
+}
+
+impl Default for ChallongeSettings {
+    fn default() -> Self {
+        Self {
+            api_url: bot::bridge::challonge::API_URL.to_owned(),
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -49,6 +83,8 @@ impl Default for Config {
             debug_guild_id: Default::default(),
             log_level: Default::default(),
             db: Default::default(),
+            startgg: StartGgSettings::default(),
+            challonge: ChallongeSettings::default(),
             log_as_json: false,
         }
     }
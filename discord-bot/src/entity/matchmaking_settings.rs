@@ -3,7 +3,12 @@
 pub use crate::entity::prelude::*;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
-use twilight_model::id::marker::{ChannelMarker, GuildMarker, RoleMarker};
+use std::collections::HashMap;
+
+use twilight_model::id::{
+    marker::{ChannelMarker, GuildMarker, MessageMarker, RoleMarker},
+    Id,
+};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "matchmaking_settings")]
@@ -17,9 +22,51 @@ pub struct Model {
     pub admin_role: Option<IdWrapper<RoleMarker>>,
     #[sea_orm(default_value = false)]
     pub threads_are_private: bool,
+    /// The looking-for-game board in the matchmaking channel.
+    pub lfg_board_message: Option<IdWrapper<MessageMarker>>,
+    /// The games whose queue is shared with the other guilds that opted in, as a list of
+    /// game IDs.
+    pub cross_server_games: Json,
+    /// Guilds whose players are never matched with this guild's, as a list of guild IDs.
+    pub blocked_guilds: Json,
+    /// Audit log entries are also posted here.
+    pub audit_channel: Option<IdWrapper<ChannelMarker>>,
+    /// Misconduct reports are posted here for staff to handle.
+    pub report_channel: Option<IdWrapper<ChannelMarker>>,
+    /// The role that's pinged to alert a game's subscribers, as a map of game IDs to role IDs.
+    pub alert_roles: Json,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {}
 
+impl Model {
+    /// The games this guild shares a queue with other guilds for.
+    #[must_use]
+    pub fn cross_server_games(&self) -> Vec<Uuid> {
+        serde_json::from_value(self.cross_server_games.clone()).unwrap_or_default()
+    }
+
+    /// The guilds this guild refuses to be matched with.
+    #[must_use]
+    pub fn blocked_guilds(&self) -> Vec<u64> {
+        serde_json::from_value(self.blocked_guilds.clone()).unwrap_or_default()
+    }
+
+    /// The roles pinged for the games' subscriber alerts, by game.
+    #[must_use]
+    pub fn alert_roles(&self) -> HashMap<Uuid, u64> {
+        serde_json::from_value(self.alert_roles.clone()).unwrap_or_default()
+    }
+
+    /// The role pinged to alert the game's subscribers, if the guild set one.
+    #[must_use]
+    pub fn alert_role(&self, game: Uuid) -> Option<Id<RoleMarker>> {
+        self.alert_roles()
+            .get(&game)
+            .copied()
+            .and_then(Id::new_checked)
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -9,14 +9,40 @@ use twilight_model::id::Id;
 
 pub mod prelude;
 
+pub mod audit_log;
+pub mod availability_window;
+pub mod bracket_credentials;
+pub mod cross_server_match;
 pub mod game;
 pub mod game_character;
+pub mod game_subscription;
+pub mod ladder_challenge;
+pub mod ladder_position;
+pub mod league;
+pub mod league_lineup;
+pub mod league_match;
+pub mod league_participant;
+pub mod league_team;
+pub mod league_team_match;
+pub mod league_team_member;
+pub mod lfg_session;
+pub mod matchmaking_ban;
 pub mod matchmaking_invitation;
 pub mod matchmaking_lobbies;
 pub mod matchmaking_player_lobby;
+pub mod matchmaking_queue;
+pub mod matchmaking_result;
 pub mod matchmaking_settings;
+pub mod player_rating;
+pub mod report;
 pub mod sea_orm_active_enums;
 pub mod state;
+pub mod tournament;
+pub mod tournament_entrant;
+pub mod tournament_match;
+pub mod tournament_pool_match;
+pub mod tournament_remote_set;
+pub mod tournament_swiss_match;
 pub mod users;
 
 pub use sea_orm;
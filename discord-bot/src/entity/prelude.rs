@@ -1,15 +1,41 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.6.0
 
 pub use super::IdWrapper;
+pub use super::{audit_log, audit_log::Entity as AuditLog};
+pub use super::{availability_window, availability_window::Entity as AvailabilityWindow};
+pub use super::{bracket_credentials, bracket_credentials::Entity as BracketCredentials};
+pub use super::{cross_server_match, cross_server_match::Entity as CrossServerMatch};
 pub use super::{game, game::Entity as Game};
 pub use super::{game_character, game_character::Entity as GameCharacter};
+pub use super::{game_subscription, game_subscription::Entity as GameSubscription};
+pub use super::{ladder_challenge, ladder_challenge::Entity as LadderChallenge};
+pub use super::{ladder_position, ladder_position::Entity as LadderPosition};
+pub use super::{league, league::Entity as League};
+pub use super::{league_lineup, league_lineup::Entity as LeagueLineup};
+pub use super::{league_match, league_match::Entity as LeagueMatch};
+pub use super::{league_participant, league_participant::Entity as LeagueParticipant};
+pub use super::{league_team, league_team::Entity as LeagueTeam};
+pub use super::{league_team_match, league_team_match::Entity as LeagueTeamMatch};
+pub use super::{league_team_member, league_team_member::Entity as LeagueTeamMember};
+pub use super::{lfg_session, lfg_session::Entity as LfgSession};
+pub use super::{matchmaking_ban, matchmaking_ban::Entity as MatchmakingBan};
 pub use super::{matchmaking_invitation, matchmaking_invitation::Entity as MatchmakingInvitation};
 pub use super::{matchmaking_lobbies, matchmaking_lobbies::Entity as MatchmakingLobbies};
 pub use super::{
     matchmaking_player_lobby, matchmaking_player_lobby::Entity as MatchmakingPlayerLobby,
 };
+pub use super::{matchmaking_queue, matchmaking_queue::Entity as MatchmakingQueue};
+pub use super::{matchmaking_result, matchmaking_result::Entity as MatchmakingResult};
 pub use super::{matchmaking_settings, matchmaking_settings::Entity as MatchmakingSettings};
+pub use super::{player_rating, player_rating::Entity as PlayerRating};
+pub use super::{report, report::Entity as Report};
 pub use super::{sea_orm_active_enums, sea_orm_active_enums::*};
 pub use super::{state, state::Entity as State};
+pub use super::{tournament, tournament::Entity as Tournament};
+pub use super::{tournament_entrant, tournament_entrant::Entity as TournamentEntrant};
+pub use super::{tournament_match, tournament_match::Entity as TournamentMatch};
+pub use super::{tournament_pool_match, tournament_pool_match::Entity as TournamentPoolMatch};
+pub use super::{tournament_remote_set, tournament_remote_set::Entity as TournamentRemoteSet};
+pub use super::{tournament_swiss_match, tournament_swiss_match::Entity as TournamentSwissMatch};
 pub use super::{users, users::Entity as Users};
 pub use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, Set};
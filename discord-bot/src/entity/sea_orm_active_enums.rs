@@ -24,3 +24,328 @@ pub enum LobbyPrivacy {
     #[sea_orm(string_value = "open")]
     Open,
 }
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    Iden,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "league_role")]
+pub enum LeagueRole {
+    #[sea_orm(string_value = "player")]
+    Player,
+    #[sea_orm(string_value = "organizer")]
+    Organizer,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    Iden,
+)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "league_match_status"
+)]
+pub enum LeagueMatchStatus {
+    #[sea_orm(string_value = "scheduled")]
+    Scheduled,
+    #[sea_orm(string_value = "completed")]
+    Completed,
+    /// The match wasn't played before its deadline.
+    #[sea_orm(string_value = "forfeited")]
+    Forfeited,
+    /// The match wasn't played before its deadline, and an organizer needs to decide the result.
+    #[sea_orm(string_value = "admin_review")]
+    AdminReview,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    Iden,
+)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "ladder_challenge_status"
+)]
+pub enum LadderChallengeStatus {
+    /// Waiting for the defender to accept.
+    #[sea_orm(string_value = "pending")]
+    Pending,
+    /// The defender accepted, and the match is being played.
+    #[sea_orm(string_value = "accepted")]
+    Accepted,
+    #[sea_orm(string_value = "completed")]
+    Completed,
+    /// The defender declined or didn't accept in time, so the challenger won.
+    #[sea_orm(string_value = "forfeited")]
+    Forfeited,
+    /// The challenger withdrew the challenge.
+    #[sea_orm(string_value = "cancelled")]
+    Cancelled,
+    /// The players reported different winners, so an organizer has to decide.
+    #[sea_orm(string_value = "disputed")]
+    Disputed,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    Iden,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "tournament_format")]
+pub enum TournamentFormat {
+    #[sea_orm(string_value = "single_elimination")]
+    SingleElimination,
+    #[sea_orm(string_value = "double_elimination")]
+    DoubleElimination,
+    /// Swiss rounds, optionally followed by a single elimination top cut.
+    #[sea_orm(string_value = "swiss")]
+    Swiss,
+    /// Round robin pools, followed by a single elimination bracket.
+    #[sea_orm(string_value = "pools")]
+    Pools,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    Iden,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "tournament_state")]
+pub enum TournamentState {
+    /// Entrants can still join, and the bracket hasn't been generated.
+    #[sea_orm(string_value = "registration")]
+    Registration,
+    #[sea_orm(string_value = "in_progress")]
+    InProgress,
+    #[sea_orm(string_value = "completed")]
+    Completed,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    Iden,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "bracket_side")]
+pub enum BracketSide {
+    #[sea_orm(string_value = "winners")]
+    Winners,
+    #[sea_orm(string_value = "losers")]
+    Losers,
+    #[sea_orm(string_value = "grand_finals")]
+    GrandFinals,
+    #[sea_orm(string_value = "grand_finals_reset")]
+    GrandFinalsReset,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    Iden,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "bracket_source")]
+pub enum BracketSource {
+    #[sea_orm(string_value = "start_gg")]
+    StartGg,
+    #[sea_orm(string_value = "challonge")]
+    Challonge,
+}
+
+/// How a player hears about someone looking to play a game they subscribed to.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    Iden,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "subscription_alert")]
+pub enum SubscriptionAlert {
+    #[sea_orm(string_value = "dm")]
+    Dm,
+    /// A mention in the matchmaking channel, shared with the other subscribers.
+    #[sea_orm(string_value = "ping")]
+    Ping,
+}
+
+/// What an admin did, as recorded in the audit log.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    Iden,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "audit_action")]
+pub enum AuditAction {
+    #[sea_orm(string_value = "matchmaking_channel")]
+    MatchmakingChannel,
+    #[sea_orm(string_value = "admin_role")]
+    AdminRole,
+    #[sea_orm(string_value = "lobby_threads")]
+    LobbyThreads,
+    #[sea_orm(string_value = "cross_server_games")]
+    CrossServerGames,
+    #[sea_orm(string_value = "blocked_servers")]
+    BlockedServers,
+    #[sea_orm(string_value = "audit_channel")]
+    AuditChannel,
+    #[sea_orm(string_value = "matchmaking_ban")]
+    MatchmakingBan,
+    #[sea_orm(string_value = "matchmaking_unban")]
+    MatchmakingUnban,
+    #[sea_orm(string_value = "report_channel")]
+    ReportChannel,
+    #[sea_orm(string_value = "lobby_closed")]
+    LobbyClosed,
+    #[sea_orm(string_value = "lobby_extended")]
+    LobbyExtended,
+    #[sea_orm(string_value = "alert_roles")]
+    AlertRoles,
+    #[sea_orm(string_value = "report_claimed")]
+    ReportClaimed,
+    #[sea_orm(string_value = "report_resolved")]
+    ReportResolved,
+    #[sea_orm(string_value = "report_dismissed")]
+    ReportDismissed,
+}
+
+/// What a player was reported for.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    Iden,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "report_category")]
+pub enum ReportCategory {
+    #[sea_orm(string_value = "cheating")]
+    Cheating,
+    #[sea_orm(string_value = "harassment")]
+    Harassment,
+    #[sea_orm(string_value = "unsportsmanlike")]
+    Unsportsmanlike,
+    /// The player didn't show up to their match, or left it early.
+    #[sea_orm(string_value = "no_show")]
+    NoShow,
+    #[sea_orm(string_value = "other")]
+    Other,
+}
+
+/// Where a misconduct report is in the staff queue.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    Iden,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "report_status")]
+pub enum ReportStatus {
+    #[sea_orm(string_value = "open")]
+    Open,
+    /// A staff member is looking into it.
+    #[sea_orm(string_value = "claimed")]
+    Claimed,
+    #[sea_orm(string_value = "resolved")]
+    Resolved,
+    #[sea_orm(string_value = "dismissed")]
+    Dismissed,
+}
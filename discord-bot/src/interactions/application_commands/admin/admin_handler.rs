@@ -3,19 +3,32 @@ use std::sync::Arc;
 use twilight_model::application::command::{CommandOption, CommandOptionType, CommandType};
 use twilight_model::channel::ChannelType;
 use twilight_model::guild::Permissions;
-use twilight_util::builder::command::{CommandBuilder, SubCommandBuilder, SubCommandGroupBuilder};
+use twilight_util::builder::command::{
+    BooleanBuilder, ChannelBuilder, CommandBuilder, IntegerBuilder, RoleBuilder, StringBuilder,
+    SubCommandBuilder, SubCommandGroupBuilder, UserBuilder,
+};
 
 use crate::interactions::application_commands::{
     ApplicationCommandData, CommandGroupDescriptor, InteractionHandler, MessageComponentData,
+    ModalSubmitData,
 };
 
-use crate::interactions::application_commands::CommonUtilities;
+use crate::interactions::application_commands::{
+    lfg::LfgCommandHandler, matchmaking::BackgroundLoop, CommonUtilities,
+};
 
+use super::audit_handler::{action_name, AuditLogHandler, AUDIT_ACTIONS};
+use super::lobbies_handler::LobbiesHandler;
+use super::mm_ban_handler::MatchmakingBanHandler;
 use super::mm_settings_handler::MatchmakingSettingsHandler;
 
+#[allow(clippy::struct_field_names)]
 pub struct AdminCommandHandler {
     // utils: Arc<ApplicationCommandUtilities>,
     matchmaking_settings_handler: MatchmakingSettingsHandler,
+    audit_log_handler: AuditLogHandler,
+    matchmaking_ban_handler: MatchmakingBanHandler,
+    lobbies_handler: LobbiesHandler,
     // matchmaking_panels_handler: MatchmakingPanelsHandler,
 }
 
@@ -39,6 +52,7 @@ impl InteractionHandler for AdminCommandHandler {
                 "Shows the matchmaking settings panel",
             )
             .subcommands([
+                SubCommandBuilder::new("panel", "Show every setting, with controls to change them"),
                 SubCommandBuilder::new("admin-role", "Set which users can act as admins").option(
                     CommandOption {
                         name: "role".to_string(),
@@ -77,7 +91,111 @@ impl InteractionHandler for AdminCommandHandler {
                     min_value: None,
                     options: None,
                 }),
+                SubCommandBuilder::new(
+                    "cross-server",
+                    "Share a game's queue with the other servers that opted in",
+                )
+                .option(StringBuilder::new("game", "The game to share").required(true))
+                .option(
+                    BooleanBuilder::new("shared", "Whether players can be matched across servers")
+                        .required(true),
+                ),
+                SubCommandBuilder::new(
+                    "block-server",
+                    "Never match this server's players with another server's",
+                )
+                .option(StringBuilder::new("server", "The other server's ID").required(true))
+                .option(
+                    BooleanBuilder::new("blocked", "Whether the server is blocked").required(true),
+                ),
+                SubCommandBuilder::new("audit-channel", "Also post the audit log in a channel")
+                    .option(
+                        ChannelBuilder::new(
+                            "channel",
+                            "The audit log channel (to disable, set to empty)",
+                        )
+                        .channel_types([ChannelType::GuildText]),
+                    ),
+                SubCommandBuilder::new(
+                    "report-channel",
+                    "Set the channel that misconduct reports are sent to",
+                )
+                .option(
+                    ChannelBuilder::new(
+                        "channel",
+                        "The report channel (to stop taking reports, set to empty)",
+                    )
+                    .channel_types([ChannelType::GuildText]),
+                ),
+                SubCommandBuilder::new(
+                    "alert-role",
+                    "Set the role that's pinged when someone is looking to play a game",
+                )
+                .option(StringBuilder::new("game", "The game").required(true))
+                .option(RoleBuilder::new(
+                    "role",
+                    "The alert role (to ping subscribers one by one, set to empty)",
+                )),
             ]),
+        )
+        .option(
+            SubCommandBuilder::new("audit", "Show what admins did in this server")
+                .option(UserBuilder::new(
+                    "user",
+                    "Only show what this user did, or what was done to them",
+                ))
+                .option(
+                    StringBuilder::new("action", "Only show this kind of action").choices(
+                        AUDIT_ACTIONS
+                            .iter()
+                            .map(|(name, action)| (*name, action_name(*action))),
+                    ),
+                ),
+        )
+        .option(
+            SubCommandBuilder::new(
+                "mm-ban",
+                "Stop a player from playing matches in this server",
+            )
+            .option(UserBuilder::new("user", "The player to ban").required(true))
+            .option(
+                IntegerBuilder::new("duration", "How long the ban lasts").choices([
+                    ("1 hour", 60),
+                    ("1 day", 60 * 24),
+                    ("3 days", 60 * 24 * 3),
+                    ("1 week", 60 * 24 * 7),
+                    ("30 days", 60 * 24 * 30),
+                    ("Until lifted (default)", -1),
+                ]),
+            )
+            .option(
+                StringBuilder::new("reason", "Shown to the player when they try to play")
+                    .max_length(200),
+            ),
+        )
+        .option(
+            SubCommandBuilder::new("mm-unban", "Let a banned player play matches again")
+                .option(UserBuilder::new("user", "The player to unban").required(true)),
+        )
+        .option(
+            SubCommandGroupBuilder::new("lobbies", "Look after the running matchmaking lobbies")
+                .subcommands([
+                    SubCommandBuilder::new("list", "Show the lobbies that are open right now"),
+                    SubCommandBuilder::new("close", "Close a lobby").option(
+                        ChannelBuilder::new("lobby", "The lobby's thread")
+                            .required(true)
+                            .channel_types([ChannelType::PublicThread, ChannelType::PrivateThread]),
+                    ),
+                    SubCommandBuilder::new("extend", "Keep a lobby open for another 30 minutes")
+                        .option(
+                            ChannelBuilder::new("lobby", "The lobby's thread")
+                                .required(true)
+                                .channel_types([
+                                    ChannelType::PublicThread,
+                                    ChannelType::PrivateThread,
+                                ]),
+                        ),
+                ]),
         );
 
         let command = builder.build();
@@ -108,6 +226,15 @@ impl InteractionHandler for AdminCommandHandler {
                     .process_command(data)
                     .await?;
             }
+            "audit" => {
+                self.audit_log_handler.process_command(data).await?;
+            }
+            "mm-ban" | "mm-unban" => {
+                self.matchmaking_ban_handler.process_command(data).await?;
+            }
+            "lobbies" => {
+                self.lobbies_handler.process_command(data).await?;
+            }
             "matchmaking-panels" => {
                 // self.matchmaking_panels_handler
                 //     .process_command(data)
@@ -127,7 +254,7 @@ impl InteractionHandler for AdminCommandHandler {
         unreachable!()
     }
 
-    async fn process_modal(&self, _data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
+    async fn process_modal(&self, _data: Box<ModalSubmitData>) -> anyhow::Result<()> {
         todo!("Admin handler does not currently process modals")
     }
 
@@ -143,6 +270,11 @@ impl InteractionHandler for AdminCommandHandler {
                         .await?;
                     return Ok(());
                 }
+                "audit" => {
+                    data.action = field.to_string();
+                    self.audit_log_handler.process_component(data).await?;
+                    return Ok(());
+                }
                 "panels" => {
                     // data.action = field.to_string();
                     // self.matchmaking_panels_handler
@@ -162,9 +294,16 @@ impl InteractionHandler for AdminCommandHandler {
 }
 
 impl AdminCommandHandler {
-    pub fn new(utils: Arc<CommonUtilities>) -> Self {
+    pub fn new(
+        utils: Arc<CommonUtilities>,
+        background: Arc<BackgroundLoop>,
+        lfg: LfgCommandHandler,
+    ) -> Self {
         Self {
-            matchmaking_settings_handler: MatchmakingSettingsHandler::new(utils),
+            matchmaking_settings_handler: MatchmakingSettingsHandler::new(utils.clone()),
+            audit_log_handler: AuditLogHandler::new(utils.clone()),
+            matchmaking_ban_handler: MatchmakingBanHandler::new(utils.clone(), lfg),
+            lobbies_handler: LobbiesHandler::new(utils, background),
             // matchmaking_panels_handler: MatchmakingPanelsHandler::new(utils.clone()),
             // utils,
         }
@@ -0,0 +1,258 @@
+use std::sync::Arc;
+
+use bot::entity::prelude::*;
+
+use sea_orm::{prelude::*, Condition, PaginatorTrait, QueryOrder};
+use twilight_model::{
+    application::interaction::application_command::CommandOptionValue,
+    channel::message::{
+        component::{ActionRow, Button, ButtonStyle},
+        Component, Embed, MessageFlags,
+    },
+    http::interaction::{InteractionResponse, InteractionResponseType},
+    id::{
+        marker::{GuildMarker, UserMarker},
+        Id,
+    },
+};
+use twilight_util::builder::{
+    embed::{EmbedBuilder, EmbedFooterBuilder},
+    InteractionResponseDataBuilder,
+};
+
+use crate::interactions::application_commands::{
+    utils::{string_option, user_option},
+    ApplicationCommandData, CommandGroupDescriptor, CommonUtilities, InteractionHandler,
+    MessageComponentData, ModalSubmitData,
+};
+
+/// How many entries are shown on one page of the audit log.
+const PAGE_SIZE: u64 = 10;
+
+/// The actions that `/admin audit` can filter by, with the names shown to admins.
+pub const AUDIT_ACTIONS: [(&str, AuditAction); 15] = [
+    ("Matchmaking channel", AuditAction::MatchmakingChannel),
+    ("Admin role", AuditAction::AdminRole),
+    ("Lobby threads", AuditAction::LobbyThreads),
+    ("Cross-server games", AuditAction::CrossServerGames),
+    ("Blocked servers", AuditAction::BlockedServers),
+    ("Audit channel", AuditAction::AuditChannel),
+    ("Matchmaking bans", AuditAction::MatchmakingBan),
+    ("Matchmaking unbans", AuditAction::MatchmakingUnban),
+    ("Report channel", AuditAction::ReportChannel),
+    ("Closed lobbies", AuditAction::LobbyClosed),
+    ("Extended lobbies", AuditAction::LobbyExtended),
+    ("Alert roles", AuditAction::AlertRoles),
+    ("Claimed reports", AuditAction::ReportClaimed),
+    ("Resolved reports", AuditAction::ReportResolved),
+    ("Dismissed reports", AuditAction::ReportDismissed),
+];
+
+pub struct AuditLogHandler {
+    utils: Arc<CommonUtilities>,
+}
+
+#[async_trait]
+impl InteractionHandler for AuditLogHandler {
+    fn describe(&self) -> CommandGroupDescriptor {
+        // This is not a top-level command handler.
+        // This function should never be registered into the InteractionProcessor/
+        CommandGroupDescriptor {
+            name: "audit",
+            description: "Shows what admins did in the guild",
+            commands: Box::new([]),
+        }
+    }
+
+    async fn process_command(&self, data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
+        let options = data
+            .command
+            .options
+            .first()
+            .and_then(|o| match &o.value {
+                CommandOptionValue::SubCommand(options) => Some(options.as_slice()),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        let filter = AuditFilter {
+            user: user_option(options, "user"),
+            action: string_option(options, "action").and_then(parse_action),
+        };
+
+        let (embed, components) = self.audit_page(data.guild_id, &filter, 0).await?;
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .embeds(&[embed])?
+            .components(&components)?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn process_autocomplete(&self, _data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
+        unreachable!()
+    }
+
+    async fn process_modal(&self, _data: Box<ModalSubmitData>) -> anyhow::Result<()> {
+        unreachable!()
+    }
+
+    async fn process_component(&self, data: Box<MessageComponentData>) -> anyhow::Result<()> {
+        let guild_id = data
+            .interaction
+            .guild_id
+            .ok_or_else(|| anyhow!("you must run this command in a guild"))?;
+        let member = data
+            .interaction
+            .member
+            .as_ref()
+            .ok_or_else(|| anyhow!("cannot get member data"))?;
+
+        if !self.utils.is_guild_admin(guild_id, member).await? {
+            return Err(anyhow!("only admins can read the audit log"));
+        }
+
+        // The page buttons carry the filters, as "<page>:<user>:<action>".
+        let mut parts = data.action.splitn(3, ':');
+        let page = parts
+            .next()
+            .ok_or_else(|| anyhow!("no page given"))?
+            .parse::<u64>()?;
+        let filter = AuditFilter {
+            user: parts
+                .next()
+                .and_then(|u| u.parse::<u64>().ok())
+                .and_then(Id::new_checked),
+            action: parts.next().and_then(parse_action),
+        };
+
+        let (embed, components) = self.audit_page(guild_id, &filter, page).await?;
+
+        self.utils
+            .send_message(
+                &data.interaction,
+                &InteractionResponse {
+                    kind: InteractionResponseType::UpdateMessage,
+                    data: Some(
+                        InteractionResponseDataBuilder::new()
+                            .embeds([embed])
+                            .components(components)
+                            .build(),
+                    ),
+                },
+            )
+            .await
+    }
+}
+
+struct AuditFilter {
+    /// Entries where the user is either the admin or the target.
+    user: Option<Id<UserMarker>>,
+    action: Option<AuditAction>,
+}
+
+impl AuditLogHandler {
+    pub fn new(utils: Arc<CommonUtilities>) -> Self {
+        Self { utils }
+    }
+
+    /// Shows one page of the guild's audit log, newest entries first.
+    async fn audit_page(
+        &self,
+        guild: Id<GuildMarker>,
+        filter: &AuditFilter,
+        page: u64,
+    ) -> anyhow::Result<(Embed, Vec<Component>)> {
+        let mut query = AuditLog::find()
+            .filter(audit_log::Column::GuildId.eq(IdWrapper::from(guild)))
+            .order_by_desc(audit_log::Column::CreatedAt);
+
+        if let Some(user) = filter.user {
+            let user = self.utils.find_or_create_user(user).await?.user_id;
+            query = query.filter(
+                Condition::any()
+                    .add(audit_log::Column::Actor.eq(user))
+                    .add(audit_log::Column::Target.eq(user)),
+            );
+        }
+        if let Some(action) = filter.action {
+            query = query.filter(audit_log::Column::Action.eq(action));
+        }
+
+        let paginator = query.paginate(self.utils.db_ref(), PAGE_SIZE);
+        let pages = paginator.num_pages().await?.max(1);
+        let page = page.min(pages - 1);
+        let entries = paginator.fetch_page(page).await?;
+
+        let mut lines = Vec::new();
+        for entry in &entries {
+            let actor = self.utils.discord_user_id(entry.actor).await?;
+            lines.push(format!(
+                "<t:{}:f> <@{actor}> {}",
+                entry.created_at.timestamp(),
+                entry.details
+            ));
+        }
+
+        let description = if lines.is_empty() {
+            "Nothing was recorded yet.".to_string()
+        } else {
+            lines.join("\n")
+        };
+
+        let embed = EmbedBuilder::new()
+            .title("Audit log")
+            .description(description)
+            .footer(EmbedFooterBuilder::new(format!(
+                "Page {} of {pages}",
+                page + 1
+            )))
+            .validate()?
+            .build();
+
+        let target = |page: u64| {
+            format!(
+                "admin:audit:{page}:{}:{}",
+                filter.user.map(|u| u.to_string()).unwrap_or_default(),
+                filter.action.map(action_name).unwrap_or_default()
+            )
+        };
+        let button = |custom_id: String, label: &str, disabled: bool| {
+            Component::Button(Button {
+                custom_id: Some(custom_id),
+                disabled,
+                emoji: None,
+                label: Some(label.to_string()),
+                style: ButtonStyle::Secondary,
+                url: None,
+            })
+        };
+
+        let components = vec![Component::ActionRow(ActionRow {
+            components: vec![
+                button(target(page.saturating_sub(1)), "Previous", page == 0),
+                button(target(page + 1), "Next", page + 1 >= pages),
+            ],
+        })];
+
+        Ok((embed, components))
+    }
+}
+
+/// The value that an action is stored as, which is also how it's picked in `/admin audit`.
+#[must_use]
+pub fn action_name(action: AuditAction) -> String {
+    action.to_value()
+}
+
+fn parse_action(name: &str) -> Option<AuditAction> {
+    AUDIT_ACTIONS
+        .iter()
+        .map(|(_, action)| *action)
+        .find(|action| action_name(*action) == name)
+}
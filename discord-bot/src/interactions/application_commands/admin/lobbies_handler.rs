@@ -0,0 +1,294 @@
+use std::{collections::HashMap, sync::Arc};
+
+use bot::entity::prelude::*;
+
+use sea_orm::QueryOrder;
+use twilight_model::{
+    application::interaction::application_command::CommandDataOption,
+    channel::message::{Embed, MessageFlags},
+    id::{
+        marker::{ChannelMarker, GuildMarker, UserMarker},
+        Id,
+    },
+};
+use twilight_util::{
+    builder::embed::{EmbedBuilder, EmbedFooterBuilder},
+    snowflake::Snowflake,
+};
+
+use crate::interactions::application_commands::{
+    matchmaking::BackgroundLoop,
+    utils::{channel_option, subcommand_path},
+    ApplicationCommandData, CommandGroupDescriptor, CommonUtilities, InteractionHandler,
+    MessageComponentData, ModalSubmitData,
+};
+
+/// How many lobbies `/admin lobbies list` shows, so that the list fits in one embed.
+const MAX_LISTED_LOBBIES: usize = 15;
+
+pub struct LobbiesHandler {
+    utils: Arc<CommonUtilities>,
+    background: Arc<BackgroundLoop>,
+}
+
+#[async_trait]
+impl InteractionHandler for LobbiesHandler {
+    fn describe(&self) -> CommandGroupDescriptor {
+        // This is not a top-level command handler.
+        // This function should never be registered into the InteractionProcessor/
+        CommandGroupDescriptor {
+            name: "lobbies",
+            description: "Looks after the running matchmaking lobbies",
+            commands: Box::new([]),
+        }
+    }
+
+    async fn process_command(&self, data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
+        let actor = data
+            .interaction
+            .member
+            .as_ref()
+            .and_then(|m| m.user.as_ref())
+            .map(|u| u.id)
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+
+        let (path, options) = subcommand_path(&data.command.options);
+
+        let interaction = self
+            .utils
+            .http_client
+            .interaction(self.utils.application_id);
+        let followup = interaction
+            .create_followup(data.interaction.token.as_str())
+            .flags(MessageFlags::EPHEMERAL);
+
+        match path.as_slice() {
+            ["lobbies", "list"] => {
+                let embed = self.list_lobbies(data.guild_id).await?;
+                followup.embeds(&[embed])?.await?;
+            }
+            ["lobbies", "close"] => {
+                let content = self.close_lobby(data.guild_id, actor, options).await?;
+                followup.content(&content)?.await?;
+            }
+            ["lobbies", "extend"] => {
+                let content = self.extend_lobby(data.guild_id, actor, options).await?;
+                followup.content(&content)?.await?;
+            }
+            _ => return Err(anyhow!("unmatched command option found: {path:?}")),
+        }
+
+        Ok(())
+    }
+
+    async fn process_autocomplete(&self, _data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
+        unreachable!()
+    }
+
+    async fn process_modal(&self, _data: Box<ModalSubmitData>) -> anyhow::Result<()> {
+        unreachable!()
+    }
+
+    async fn process_component(&self, _data: Box<MessageComponentData>) -> anyhow::Result<()> {
+        unreachable!()
+    }
+}
+
+impl LobbiesHandler {
+    pub fn new(utils: Arc<CommonUtilities>, background: Arc<BackgroundLoop>) -> Self {
+        Self { utils, background }
+    }
+
+    /// Shows the guild's open lobbies, oldest first, with a link to each lobby's thread.
+    async fn list_lobbies(&self, guild: Id<GuildMarker>) -> anyhow::Result<Embed> {
+        // Lobbies don't know their guild, so they're found through the guild's threads.
+        let threads = self
+            .utils
+            .http_client
+            .active_threads(guild)
+            .await?
+            .model()
+            .await?
+            .threads;
+        let last_messages = threads
+            .iter()
+            .map(|t| (t.id, t.last_message_id))
+            .collect::<HashMap<_, _>>();
+
+        let lobbies = MatchmakingLobbies::find()
+            .filter(matchmaking_lobbies::Column::EndedAt.is_null())
+            .filter(
+                matchmaking_lobbies::Column::ChannelId
+                    .is_in(threads.iter().map(|t| IdWrapper::from(t.id))),
+            )
+            .order_by_asc(matchmaking_lobbies::Column::StartedAt)
+            .all(self.utils.db_ref())
+            .await?;
+
+        let players = MatchmakingPlayerLobby::find()
+            .filter(matchmaking_player_lobby::Column::Lobby.is_in(lobbies.iter().map(|l| l.id)))
+            .all(self.utils.db_ref())
+            .await?;
+        let games = Game::find()
+            .filter(game::Column::Id.is_in(lobbies.iter().filter_map(|l| l.game)))
+            .all(self.utils.db_ref())
+            .await?
+            .into_iter()
+            .map(|g| (g.id, g.name))
+            .collect::<HashMap<_, _>>();
+
+        let mut entries = Vec::new();
+        for lobby in lobbies.iter().take(MAX_LISTED_LOBBIES) {
+            let thread = lobby.channel_id.into_id();
+
+            let mut participants = Vec::new();
+            for player in players.iter().filter(|p| p.lobby == lobby.id) {
+                let player = self.utils.discord_user_id(player.player).await?;
+                participants.push(format!("<@{player}>"));
+            }
+
+            let game = lobby
+                .game
+                .and_then(|g| games.get(&g).cloned())
+                .or_else(|| lobby.game_other.clone())
+                .unwrap_or_else(|| "Any game".to_string());
+
+            // A thread's latest message is the last time anyone spoke in the lobby.
+            let last_activity = last_messages
+                .get(&thread)
+                .copied()
+                .flatten()
+                .map_or(lobby.started_at.timestamp(), |m| m.timestamp() / 1000);
+
+            entries.push(format!(
+                "<#{thread}> · {game}\n{}\nOpened <t:{}:R> · last activity <t:{last_activity}:R> · \
+                closes <t:{}:R>",
+                if participants.is_empty() {
+                    "No players".to_string()
+                } else {
+                    participants.join(", ")
+                },
+                lobby.started_at.timestamp(),
+                lobby.timeout_after.timestamp(),
+            ));
+        }
+
+        let description = if entries.is_empty() {
+            "There are no open lobbies.".to_string()
+        } else {
+            entries.join("\n\n")
+        };
+        let footer = if lobbies.len() > MAX_LISTED_LOBBIES {
+            format!(
+                "Showing the {MAX_LISTED_LOBBIES} oldest of {} lobbies",
+                lobbies.len()
+            )
+        } else {
+            format!("{} open lobbies", lobbies.len())
+        };
+
+        Ok(EmbedBuilder::new()
+            .title("Open lobbies")
+            .description(description)
+            .footer(EmbedFooterBuilder::new(footer))
+            .validate()?
+            .build())
+    }
+
+    /// Finds the open lobby in the thread that an admin picked.
+    async fn picked_lobby(
+        &self,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<(Id<ChannelMarker>, Option<matchmaking_lobbies::Model>)> {
+        let thread = channel_option(options, "lobby").ok_or_else(|| anyhow!("no lobby given"))?;
+
+        let lobby = MatchmakingLobbies::find()
+            .filter(matchmaking_lobbies::Column::ChannelId.eq(IdWrapper::from(thread)))
+            .filter(matchmaking_lobbies::Column::EndedAt.is_null())
+            .one(self.utils.db_ref())
+            .await?;
+
+        Ok((thread, lobby))
+    }
+
+    /// Closes the lobby and archives its thread, along with the other side of a
+    /// cross-server match.
+    async fn close_lobby(
+        &self,
+        guild: Id<GuildMarker>,
+        actor: Id<UserMarker>,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<String> {
+        let (thread, lobby) = self.picked_lobby(options).await?;
+        let Some(lobby) = lobby else {
+            return Ok(format!("<#{thread}> is not an open lobby."));
+        };
+
+        self.background.close_lobby(&lobby).await?;
+        self.background.close_mirrored_lobby(lobby.id).await?;
+
+        self.utils
+            .http_client
+            .create_message(thread)
+            .content("An admin closed this lobby. Thanks for using runback!")?
+            .await?;
+        self.utils
+            .http_client
+            .update_thread(thread)
+            .archived(true)
+            .locked(true)
+            .await?;
+
+        self.utils
+            .audit(
+                guild,
+                actor,
+                AuditAction::LobbyClosed,
+                None,
+                format!("closed the lobby <#{thread}>"),
+            )
+            .await?;
+
+        Ok(format!("Closed <#{thread}>."))
+    }
+
+    async fn extend_lobby(
+        &self,
+        guild: Id<GuildMarker>,
+        actor: Id<UserMarker>,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<String> {
+        let (thread, lobby) = self.picked_lobby(options).await?;
+        let Some(lobby) = lobby else {
+            return Ok(format!("<#{thread}> is not an open lobby."));
+        };
+
+        self.background.extend_lobby(&lobby).await?;
+
+        let lobby = MatchmakingLobbies::find_by_id(lobby.id)
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("could not find the lobby"))?;
+        let until = lobby.timeout_after.timestamp();
+
+        self.utils
+            .http_client
+            .create_message(thread)
+            .content(&format!(
+                "An admin kept this lobby open until <t:{until}:t>."
+            ))?
+            .await?;
+
+        self.utils
+            .audit(
+                guild,
+                actor,
+                AuditAction::LobbyExtended,
+                None,
+                format!("extended the lobby <#{thread}>"),
+            )
+            .await?;
+
+        Ok(format!("<#{thread}> now stays open until <t:{until}:f>."))
+    }
+}
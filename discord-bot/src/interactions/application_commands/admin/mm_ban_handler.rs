@@ -0,0 +1,203 @@
+use std::sync::Arc;
+
+use bot::entity::prelude::*;
+
+use chrono::{Duration, Utc};
+use sea_orm::{prelude::*, IntoActiveModel};
+use twilight_model::{
+    application::interaction::application_command::{CommandDataOption, CommandOptionValue},
+    channel::message::MessageFlags,
+    id::{
+        marker::{GuildMarker, UserMarker},
+        Id,
+    },
+};
+
+use crate::interactions::application_commands::{
+    lfg::LfgCommandHandler,
+    utils::{integer_option, string_option, user_option},
+    ApplicationCommandData, CommandGroupDescriptor, CommonUtilities, InteractionHandler,
+    MessageComponentData, ModalSubmitData,
+};
+
+pub struct MatchmakingBanHandler {
+    utils: Arc<CommonUtilities>,
+    lfg: LfgCommandHandler,
+}
+
+#[async_trait]
+impl InteractionHandler for MatchmakingBanHandler {
+    fn describe(&self) -> CommandGroupDescriptor {
+        // This is not a top-level command handler.
+        // This function should never be registered into the InteractionProcessor/
+        CommandGroupDescriptor {
+            name: "mm-ban",
+            description: "Bans players from matchmaking",
+            commands: Box::new([]),
+        }
+    }
+
+    async fn process_command(&self, data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
+        let actor = data
+            .interaction
+            .member
+            .as_ref()
+            .and_then(|m| m.user.as_ref())
+            .map(|u| u.id)
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+
+        let subcommand = data
+            .command
+            .options
+            .first()
+            .ok_or_else(|| anyhow!("could not get any command options"))?;
+        let options = match &subcommand.value {
+            CommandOptionValue::SubCommand(options) => options.as_slice(),
+            _ => &[],
+        };
+
+        let content = match subcommand.name.as_str() {
+            "mm-ban" => self.ban(data.guild_id, actor, options).await?,
+            "mm-unban" => self.unban(data.guild_id, actor, options).await?,
+            name => return Err(anyhow!("unmatched command option found: {name}")),
+        };
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .content(&content)?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn process_autocomplete(&self, _data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
+        unreachable!()
+    }
+
+    async fn process_modal(&self, _data: Box<ModalSubmitData>) -> anyhow::Result<()> {
+        unreachable!()
+    }
+
+    async fn process_component(&self, _data: Box<MessageComponentData>) -> anyhow::Result<()> {
+        unreachable!()
+    }
+}
+
+impl MatchmakingBanHandler {
+    pub fn new(utils: Arc<CommonUtilities>, lfg: LfgCommandHandler) -> Self {
+        Self { utils, lfg }
+    }
+
+    /// Bans the player, replacing any ban they already have, and takes them out of the
+    /// guild's queue and off its LFG board.
+    async fn ban(
+        &self,
+        guild: Id<GuildMarker>,
+        actor: Id<UserMarker>,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<String> {
+        let user = user_option(options, "user").ok_or_else(|| anyhow!("no user provided"))?;
+        if user == actor {
+            return Err(anyhow!("you cannot ban yourself"));
+        }
+
+        let player = self.utils.find_or_create_user(user).await?.user_id;
+        let banned_by = self.utils.find_or_create_user(actor).await?.user_id;
+
+        let created_at = Utc::now();
+        let expires_at = integer_option(options, "duration")
+            .filter(|minutes| *minutes > 0)
+            .map(|minutes| created_at + Duration::minutes(minutes));
+        let reason = string_option(options, "reason").map(str::to_string);
+
+        MatchmakingBan::delete_many()
+            .filter(matchmaking_ban::Column::GuildId.eq(IdWrapper::from(guild)))
+            .filter(matchmaking_ban::Column::Player.eq(player))
+            .exec(self.utils.db_ref())
+            .await?;
+
+        let ban = matchmaking_ban::Model {
+            id: Uuid::new_v4(),
+            guild_id: guild.into(),
+            player,
+            banned_by,
+            reason: reason.clone(),
+            created_at,
+            expires_at,
+        };
+
+        MatchmakingBan::insert(ban.into_active_model())
+            .exec(self.utils.db_ref())
+            .await?;
+
+        MatchmakingQueue::delete_many()
+            .filter(matchmaking_queue::Column::GuildId.eq(IdWrapper::from(guild)))
+            .filter(matchmaking_queue::Column::Player.eq(player))
+            .exec(self.utils.db_ref())
+            .await?;
+
+        let res = LfgSession::delete_many()
+            .filter(lfg_session::Column::GuildId.eq(IdWrapper::from(guild)))
+            .filter(lfg_session::Column::Player.eq(player))
+            .exec(self.utils.db_ref())
+            .await?;
+        if res.rows_affected > 0 {
+            self.lfg.refresh_board(guild).await?;
+        }
+
+        let until = expires_at.map_or_else(
+            || "until further notice".to_string(),
+            |t| format!("until <t:{}:f>", t.timestamp()),
+        );
+        let details = match &reason {
+            Some(reason) => format!("banned <@{user}> from matchmaking {until}: {reason}"),
+            None => format!("banned <@{user}> from matchmaking {until}"),
+        };
+        self.utils
+            .audit(
+                guild,
+                actor,
+                AuditAction::MatchmakingBan,
+                Some(user),
+                details,
+            )
+            .await?;
+
+        Ok(format!("<@{user}> is banned from matchmaking {until}."))
+    }
+
+    async fn unban(
+        &self,
+        guild: Id<GuildMarker>,
+        actor: Id<UserMarker>,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<String> {
+        let user = user_option(options, "user").ok_or_else(|| anyhow!("no user provided"))?;
+        let player = self.utils.find_or_create_user(user).await?.user_id;
+
+        if self.utils.active_ban(guild, player).await?.is_none() {
+            return Ok(format!("<@{user}> is not banned from matchmaking."));
+        }
+
+        MatchmakingBan::delete_many()
+            .filter(matchmaking_ban::Column::GuildId.eq(IdWrapper::from(guild)))
+            .filter(matchmaking_ban::Column::Player.eq(player))
+            .exec(self.utils.db_ref())
+            .await?;
+
+        self.utils
+            .audit(
+                guild,
+                actor,
+                AuditAction::MatchmakingUnban,
+                Some(user),
+                format!("lifted the matchmaking ban of <@{user}>"),
+            )
+            .await?;
+
+        Ok(format!("<@{user}> can play matches again."))
+    }
+}
@@ -13,7 +13,7 @@ use twilight_model::{
 use crate::interactions::{
     application_commands::{
         ApplicationCommandData, ApplicationCommandUtilities, CommandGroupDescriptor,
-        InteractionHandler, MessageComponentData,
+        InteractionHandler, MessageComponentData, ModalSubmitData,
     },
 };
 
@@ -98,7 +98,7 @@ impl InteractionHandler for MatchmakingPanelsHandler {
         todo!()
     }
 
-    async fn process_modal(&self, _data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
+    async fn process_modal(&self, _data: Box<ModalSubmitData>) -> anyhow::Result<()> {
         todo!()
     }
 
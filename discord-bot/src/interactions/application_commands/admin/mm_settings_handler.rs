@@ -3,22 +3,34 @@ use std::sync::Arc;
 use bot::entity::prelude::*;
 
 use chrono::Utc;
-use sea_orm::{ActiveModelTrait, EntityTrait, IntoActiveModel, Set};
+use sea_orm::{
+    prelude::Uuid, ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
 use twilight_model::{
-    application::interaction::application_command::CommandOptionValue,
-    channel::message::MessageFlags,
-    guild::{PartialMember, Permissions},
+    application::interaction::application_command::{CommandDataOption, CommandOptionValue},
+    channel::{
+        message::{
+            component::{ActionRow, Button, ButtonStyle, SelectMenu, SelectMenuOption},
+            Component, Embed, MessageFlags,
+        },
+        ChannelType,
+    },
     http::interaction::{InteractionResponse, InteractionResponseType},
     id::{
-        marker::{ChannelMarker, GuildMarker, RoleMarker},
+        marker::{ChannelMarker, GuildMarker, RoleMarker, UserMarker},
         Id,
     },
 };
-use twilight_util::builder::InteractionResponseDataBuilder;
+use twilight_util::builder::{
+    embed::{EmbedBuilder, EmbedFieldBuilder},
+    InteractionResponseDataBuilder,
+};
 
 use crate::interactions::application_commands::{
+    utils::{boolean_option, channel_option, role_option, string_option},
     ApplicationCommandData, CommandGroupDescriptor, CommonUtilities, InteractionHandler,
-    MessageComponentData,
+    MessageComponentData, ModalSubmitData,
 };
 
 pub struct MatchmakingSettingsHandler {
@@ -42,6 +54,14 @@ impl InteractionHandler for MatchmakingSettingsHandler {
         // VERIFY: Is it possible that we can send the information of other guilds here?
         debug!(data = ?format!("{:?}", data.command));
 
+        let actor = data
+            .interaction
+            .member
+            .as_ref()
+            .and_then(|m| m.user.as_ref())
+            .map(|u| u.id)
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+
         let group = data
             .command
             .options
@@ -92,6 +112,7 @@ impl InteractionHandler for MatchmakingSettingsHandler {
                 };
 
                 let message;
+                let details;
 
                 if let Some(channel) = subcommand_options
                     .iter()
@@ -111,16 +132,28 @@ impl InteractionHandler for MatchmakingSettingsHandler {
                         "Successfully set the matchmaking channel to <#{}>.",
                         channel
                     );
+                    details = format!("set the matchmaking channel to <#{channel}>");
                 } else {
                     // There's no channel, disable the matchmaking channel
                     model.channel_id = Set(None);
                     message = "Successfully disabled matchmaking channel.".to_string();
+                    details = "disabled the matchmaking channel".to_string();
                 }
 
                 MatchmakingSettings::update(model)
                     .exec(self.utils.db_ref())
                     .await?;
 
+                self.utils
+                    .audit(
+                        data.guild_id,
+                        actor,
+                        AuditAction::MatchmakingChannel,
+                        None,
+                        details,
+                    )
+                    .await?;
+
                 self.utils
                     .http_client
                     .interaction(self.utils.application_id)
@@ -141,6 +174,7 @@ impl InteractionHandler for MatchmakingSettingsHandler {
                 };
 
                 let message;
+                let details;
 
                 if let Some(role) = subcommand_options
                     .iter()
@@ -155,16 +189,22 @@ impl InteractionHandler for MatchmakingSettingsHandler {
                 {
                     model.admin_role = Set(Some(role.into()));
                     message = format!("Successfully set the admin role to <@&{}>.", role);
+                    details = format!("set the admin role to <@&{role}>");
                 } else {
                     // Disable the admin role
                     model.admin_role = Set(None);
                     message = "Successfully removed the admin role.".to_string();
+                    details = "removed the admin role".to_string();
                 }
 
                 MatchmakingSettings::update(model)
                     .exec(self.utils.db_ref())
                     .await?;
 
+                self.utils
+                    .audit(data.guild_id, actor, AuditAction::AdminRole, None, details)
+                    .await?;
+
                 self.utils
                     .http_client
                     .interaction(self.utils.application_id)
@@ -174,6 +214,50 @@ impl InteractionHandler for MatchmakingSettingsHandler {
                     .exec()
                     .await?;
             }
+            "panel" => {
+                let (embed, components) = self.settings_panel(data.guild_id).await?;
+
+                self.utils
+                    .http_client
+                    .interaction(self.utils.application_id)
+                    .create_followup(data.interaction.token.as_str())
+                    .embeds(&[embed])?
+                    .components(&components)?
+                    .flags(MessageFlags::EPHEMERAL)
+                    .await?;
+            }
+            "cross-server" | "block-server" | "audit-channel" | "report-channel" | "alert-role" => {
+                let message = match subcommand.name.as_str() {
+                    "cross-server" => {
+                        self.share_game(data.guild_id, actor, &subcommand_options)
+                            .await?
+                    }
+                    "block-server" => {
+                        self.block_server(data.guild_id, actor, &subcommand_options)
+                            .await?
+                    }
+                    "audit-channel" => {
+                        self.set_audit_channel(data.guild_id, actor, &subcommand_options)
+                            .await?
+                    }
+                    "alert-role" => {
+                        self.set_alert_role(data.guild_id, actor, &subcommand_options)
+                            .await?
+                    }
+                    _ => {
+                        self.set_report_channel(data.guild_id, actor, &subcommand_options)
+                            .await?
+                    }
+                };
+
+                self.utils
+                    .http_client
+                    .interaction(self.utils.application_id)
+                    .create_followup(data.interaction.token.as_str())
+                    .content(message.as_str())?
+                    .flags(MessageFlags::EPHEMERAL)
+                    .await?;
+            }
             _ => {
                 return Err(anyhow!(
                     "unmatched command option found: {}",
@@ -189,36 +273,40 @@ impl InteractionHandler for MatchmakingSettingsHandler {
         todo!()
     }
 
-    async fn process_modal(&self, _data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
+    async fn process_modal(&self, _data: Box<ModalSubmitData>) -> anyhow::Result<()> {
         todo!()
     }
 
     async fn process_component(&self, data: Box<MessageComponentData>) -> anyhow::Result<()> {
-        let msg =
-            data.interaction.message.as_ref().ok_or_else(|| {
-                anyhow!("no message attached to this message component interaction")
-            })?;
-
-        let guild_id = msg
+        let guild_id = data
+            .interaction
             .guild_id
             .ok_or_else(|| anyhow!("you must run this command in a guild"))?;
 
-        let member = msg
+        let member = data
+            .interaction
             .member
             .as_ref()
             .ok_or_else(|| anyhow!("cannot get member data"))?;
 
-        let guild = self.utils.get_guild_settings(guild_id).await?;
-
         // validate that the user has the proper permissions
-        if !self.is_authorized_admin(member, guild.admin_role) {
+        if !self.utils.is_guild_admin(guild_id, member).await? {
             return Err(anyhow!("you are not authorized to use this panel"));
         }
 
-        match data.action.as_str() {
+        let actor = member
+            .user
+            .as_ref()
+            .map(|u| u.id)
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+
+        let (action, details) = match data.action.as_str() {
             "channel" => {
-                self.set_matchmaking_channel(&data).await?;
-                return Ok(());
+                let channel = self.set_matchmaking_channel(&data).await?;
+                (
+                    AuditAction::MatchmakingChannel,
+                    format!("set the matchmaking channel to <#{channel}>"),
+                )
             }
             "role" => {
                 // set the admin role for this guild
@@ -232,8 +320,96 @@ impl InteractionHandler for MatchmakingSettingsHandler {
                 .ok_or_else(|| anyhow!("could not convert role into an ID"))?;
 
                 self.set_admin_role(guild_id, role).await?;
+                (
+                    AuditAction::AdminRole,
+                    format!("set the admin role to <@&{role}>"),
+                )
+            }
+            "clear-channel" | "clear-role" => {
+                let mut model = matchmaking_settings::ActiveModel {
+                    guild_id: Set(guild_id.into()),
+                    last_updated: Set(Utc::now()),
+                    ..Default::default()
+                };
+                let audit = if data.action == "clear-channel" {
+                    model.channel_id = Set(None);
+                    (
+                        AuditAction::MatchmakingChannel,
+                        "disabled the matchmaking channel",
+                    )
+                } else {
+                    model.admin_role = Set(None);
+                    (AuditAction::AdminRole, "removed the admin role")
+                };
+
+                MatchmakingSettings::update(model)
+                    .exec(self.utils.db_ref())
+                    .await?;
+
+                (audit.0, audit.1.to_string())
+            }
+            "private-threads" => {
+                let settings = self.utils.get_guild_settings(guild_id).await?;
 
-                return Ok(());
+                MatchmakingSettings::update(matchmaking_settings::ActiveModel {
+                    guild_id: Set(settings.guild_id),
+                    last_updated: Set(Utc::now()),
+                    threads_are_private: Set(!settings.threads_are_private),
+                    ..Default::default()
+                })
+                .exec(self.utils.db_ref())
+                .await?;
+
+                (
+                    AuditAction::LobbyThreads,
+                    if settings.threads_are_private {
+                        "made lobby threads public"
+                    } else {
+                        "made lobby threads private"
+                    }
+                    .to_string(),
+                )
+            }
+            "cross-server" => {
+                let settings = self.utils.get_guild_settings(guild_id).await?;
+                let listed = self.panel_games().await?;
+
+                // Shared games that aren't in the menu stay shared.
+                let mut games = settings.cross_server_games();
+                games.retain(|g| !listed.iter().any(|game| game.id == *g));
+                for value in &data.message.values {
+                    games.push(value.parse::<Uuid>()?);
+                }
+
+                MatchmakingSettings::update(matchmaking_settings::ActiveModel {
+                    guild_id: Set(settings.guild_id),
+                    last_updated: Set(Utc::now()),
+                    cross_server_games: Set(serde_json::to_value(&games)?),
+                    ..Default::default()
+                })
+                .exec(self.utils.db_ref())
+                .await?;
+
+                let names = Game::find()
+                    .filter(game::Column::Id.is_in(games))
+                    .order_by_asc(game::Column::Name)
+                    .all(self.utils.db_ref())
+                    .await?
+                    .into_iter()
+                    .map(|g| format!("**{}**", g.name))
+                    .collect::<Vec<_>>();
+
+                (
+                    AuditAction::CrossServerGames,
+                    if names.is_empty() {
+                        "stopped sharing every queue with other servers".to_string()
+                    } else {
+                        format!(
+                            "set the queues shared with other servers to {}",
+                            names.join(", ")
+                        )
+                    },
+                )
             }
             _ => {
                 return Err(anyhow!(
@@ -241,7 +417,28 @@ impl InteractionHandler for MatchmakingSettingsHandler {
                     &data.action
                 ))
             }
-        }
+        };
+
+        self.utils
+            .audit(guild_id, actor, action, None, details)
+            .await?;
+
+        // Redraw the panel with the new values.
+        let (embed, components) = self.settings_panel(guild_id).await?;
+        self.utils
+            .send_message(
+                &data.interaction,
+                &InteractionResponse {
+                    kind: InteractionResponseType::UpdateMessage,
+                    data: Some(
+                        InteractionResponseDataBuilder::new()
+                            .embeds([embed])
+                            .components(components)
+                            .build(),
+                    ),
+                },
+            )
+            .await
     }
 }
 
@@ -250,7 +447,10 @@ impl MatchmakingSettingsHandler {
         Self { utils }
     }
 
-    async fn set_matchmaking_channel(&self, data: &MessageComponentData) -> anyhow::Result<()> {
+    async fn set_matchmaking_channel(
+        &self,
+        data: &MessageComponentData,
+    ) -> anyhow::Result<Id<ChannelMarker>> {
         let channel_id: Id<ChannelMarker> = Id::new(
             data.message
                 .values
@@ -281,6 +481,12 @@ impl MatchmakingSettingsHandler {
                 has_accepted_eula: None,
                 threads_are_private: false,
                 admin_role: None,
+                lfg_board_message: None,
+                cross_server_games: serde_json::json!([]),
+                blocked_guilds: serde_json::json!([]),
+                audit_channel: None,
+                report_channel: None,
+                alert_roles: serde_json::json!({}),
             }
             .into_active_model();
             setting
@@ -290,25 +496,257 @@ impl MatchmakingSettingsHandler {
         };
 
         // TODO: Produce a Kafka message, saying that this guild's settings have been updated
-        let _message = InteractionResponse { kind: InteractionResponseType::UpdateMessage, data: Some(
-            InteractionResponseDataBuilder::new()
-                .flags(MessageFlags::EPHEMERAL)
-                .content("Successfully set the matchmaking channel. Please wait a few moments for changes to take effect.")
-                .build()
-        )};
-
-        let _res =
-            self.utils
-            .http_client
-            .interaction(self.utils.application_id)
-            .update_response(data.interaction.token.as_str())
-            .content(Some("Successfully set the matchmaking channel. Please wait a few moments for changes to take effect"))?
-            // .map_err(|e| RunbackError { message: "Could not set content for response message during set_matchmaking_channel()".to_owned(), inner: Some(Box::new(e)) })?
-            // .(component.id, component.token.as_str(), &message)
-            .exec()
+
+        Ok(channel_id)
+    }
+
+    /// Adds or removes a game from the queues this guild shares with other guilds.
+    async fn share_game(
+        &self,
+        guild: Id<GuildMarker>,
+        actor: Id<UserMarker>,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<String> {
+        let name = string_option(options, "game").ok_or_else(|| anyhow!("no game provided"))?;
+        let shared = boolean_option(options, "shared").unwrap_or(false);
+
+        let game = Game::find()
+            .filter(game::Column::Name.eq(name))
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("\"{name}\" is not a known game"))?;
+
+        let settings = self.utils.get_guild_settings(guild).await?;
+        let mut games = settings.cross_server_games();
+        games.retain(|g| *g != game.id);
+        if shared {
+            games.push(game.id);
+        }
+
+        MatchmakingSettings::update(matchmaking_settings::ActiveModel {
+            guild_id: Set(settings.guild_id),
+            last_updated: Set(Utc::now()),
+            cross_server_games: Set(serde_json::to_value(games)?),
+            ..Default::default()
+        })
+        .exec(self.utils.db_ref())
+        .await?;
+
+        let details = if shared {
+            format!("shared the queue for **{}** with other servers", game.name)
+        } else {
+            format!("stopped sharing the queue for **{}**", game.name)
+        };
+        self.utils
+            .audit(guild, actor, AuditAction::CrossServerGames, None, details)
             .await?;
 
-        Ok(())
+        Ok(if shared {
+            format!(
+                "Players queueing for **{}** can now be matched with players from other servers.",
+                game.name
+            )
+        } else {
+            format!(
+                "Players queueing for **{}** are only matched within this server.",
+                game.name
+            )
+        })
+    }
+
+    /// Adds or removes a guild from the guilds this guild's players are never matched with.
+    async fn block_server(
+        &self,
+        guild: Id<GuildMarker>,
+        actor: Id<UserMarker>,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<String> {
+        let server = string_option(options, "server")
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .filter(|id| *id != 0)
+            .ok_or_else(|| anyhow!("that is not a valid server ID"))?;
+        let blocked = boolean_option(options, "blocked").unwrap_or(false);
+
+        let settings = self.utils.get_guild_settings(guild).await?;
+        let mut guilds = settings.blocked_guilds();
+        guilds.retain(|g| *g != server);
+        if blocked {
+            guilds.push(server);
+        }
+
+        MatchmakingSettings::update(matchmaking_settings::ActiveModel {
+            guild_id: Set(settings.guild_id),
+            last_updated: Set(Utc::now()),
+            blocked_guilds: Set(serde_json::to_value(guilds)?),
+            ..Default::default()
+        })
+        .exec(self.utils.db_ref())
+        .await?;
+
+        let details = if blocked {
+            format!("blocked server {server}")
+        } else {
+            format!("unblocked server {server}")
+        };
+        self.utils
+            .audit(guild, actor, AuditAction::BlockedServers, None, details)
+            .await?;
+
+        Ok(if blocked {
+            format!("Players from server {server} will no longer be matched with this server's.")
+        } else {
+            format!("Players from server {server} can be matched with this server's again.")
+        })
+    }
+
+    /// Sets or clears the channel that audit log entries are posted in.
+    async fn set_audit_channel(
+        &self,
+        guild: Id<GuildMarker>,
+        actor: Id<UserMarker>,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<String> {
+        let channel = channel_option(options, "channel");
+
+        MatchmakingSettings::update(matchmaking_settings::ActiveModel {
+            guild_id: Set(self.utils.get_guild_settings(guild).await?.guild_id),
+            last_updated: Set(Utc::now()),
+            audit_channel: Set(channel.map(Into::into)),
+            ..Default::default()
+        })
+        .exec(self.utils.db_ref())
+        .await?;
+
+        // Logged after the change, so that a new channel starts with its own entry.
+        let (details, message) = match channel {
+            Some(channel) => (
+                format!("set the audit channel to <#{channel}>"),
+                format!("The audit log is now also posted in <#{channel}>."),
+            ),
+            None => (
+                "stopped posting the audit log in a channel".to_string(),
+                "The audit log is no longer posted in a channel.".to_string(),
+            ),
+        };
+        self.utils
+            .audit(guild, actor, AuditAction::AuditChannel, None, details)
+            .await?;
+
+        Ok(message)
+    }
+
+    /// Sets or clears the channel that misconduct reports are sent to. Without one, players
+    /// can't send reports.
+    async fn set_report_channel(
+        &self,
+        guild: Id<GuildMarker>,
+        actor: Id<UserMarker>,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<String> {
+        let channel = channel_option(options, "channel");
+
+        MatchmakingSettings::update(matchmaking_settings::ActiveModel {
+            guild_id: Set(self.utils.get_guild_settings(guild).await?.guild_id),
+            last_updated: Set(Utc::now()),
+            report_channel: Set(channel.map(Into::into)),
+            ..Default::default()
+        })
+        .exec(self.utils.db_ref())
+        .await?;
+
+        let (details, message) = match channel {
+            Some(channel) => (
+                format!("set the report channel to <#{channel}>"),
+                format!("Misconduct reports are now sent to <#{channel}>."),
+            ),
+            None => (
+                "stopped taking misconduct reports".to_string(),
+                "Players can no longer send misconduct reports.".to_string(),
+            ),
+        };
+        self.utils
+            .audit(guild, actor, AuditAction::ReportChannel, None, details)
+            .await?;
+
+        Ok(message)
+    }
+
+    /// Sets or clears the role that's pinged to alert a game's subscribers. The players who
+    /// subscribed to pings for the game are given the role.
+    async fn set_alert_role(
+        &self,
+        guild: Id<GuildMarker>,
+        actor: Id<UserMarker>,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<String> {
+        let name = string_option(options, "game").ok_or_else(|| anyhow!("no game provided"))?;
+        let role = role_option(options, "role");
+
+        let game = Game::find()
+            .filter(game::Column::Name.eq(name))
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("\"{name}\" is not a known game"))?;
+
+        let settings = self.utils.get_guild_settings(guild).await?;
+        let mut roles = settings.alert_roles();
+        roles.remove(&game.id);
+        if let Some(role) = role {
+            roles.insert(game.id, role.get());
+        }
+
+        MatchmakingSettings::update(matchmaking_settings::ActiveModel {
+            guild_id: Set(settings.guild_id),
+            last_updated: Set(Utc::now()),
+            alert_roles: Set(serde_json::to_value(roles)?),
+            ..Default::default()
+        })
+        .exec(self.utils.db_ref())
+        .await?;
+
+        if let Some(role) = role {
+            let subscriptions = GameSubscription::find()
+                .filter(game_subscription::Column::GuildId.eq(IdWrapper::from(guild)))
+                .filter(game_subscription::Column::Game.eq(game.id))
+                .filter(game_subscription::Column::Alert.eq(SubscriptionAlert::Ping))
+                .all(self.utils.db_ref())
+                .await?;
+
+            for subscription in subscriptions {
+                let user = self.utils.discord_user_id(subscription.player).await?;
+                // Members who left the server can't be given the role.
+                if let Err(e) = self
+                    .utils
+                    .http_client
+                    .add_guild_member_role(guild, user, role)
+                    .await
+                {
+                    warn!(error = ?e, user = ?user, "could not give a subscriber the alert role");
+                }
+            }
+        }
+
+        let (details, message) = match role {
+            Some(role) => (
+                format!("set the alert role for **{}** to <@&{role}>", game.name),
+                format!(
+                    "Subscribers to **{}** are now alerted with a <@&{role}> ping.",
+                    game.name
+                ),
+            ),
+            None => (
+                format!("removed the alert role for **{}**", game.name),
+                format!(
+                    "Subscribers to **{}** are now pinged one by one.",
+                    game.name
+                ),
+            ),
+        };
+        self.utils
+            .audit(guild, actor, AuditAction::AlertRoles, None, details)
+            .await?;
+
+        Ok(message)
     }
 
     async fn set_admin_role(
@@ -327,23 +765,241 @@ impl MatchmakingSettingsHandler {
         )
     }
 
-    fn is_authorized_admin(
+    /// The games that are listed in the panel's cross-server menu. A menu can't hold more
+    /// than 25 options.
+    async fn panel_games(&self) -> anyhow::Result<Vec<game::Model>> {
+        Ok(Game::find()
+            .order_by_asc(game::Column::Name)
+            .limit(25)
+            .all(self.utils.db_ref())
+            .await?)
+    }
+
+    /// Shows every matchmaking setting of the guild, with menus and buttons to change them.
+    async fn settings_panel(
         &self,
-        member: &PartialMember,
-        role: Option<bot::entity::IdWrapper<RoleMarker>>,
-    ) -> bool {
-        if let Some(perms) = member.permissions {
-            if perms.contains(Permissions::ADMINISTRATOR) {
-                return true;
+        guild: Id<GuildMarker>,
+    ) -> anyhow::Result<(Embed, Vec<Component>)> {
+        let settings = self.utils.get_guild_settings(guild).await?;
+
+        let mut channels = self
+            .utils
+            .http_client
+            .guild_channels(guild)
+            .await?
+            .models()
+            .await?;
+        channels.retain(|c| c.kind == ChannelType::GuildText);
+        channels.sort_by_key(|c| c.position);
+
+        // Neither @everyone nor the roles of integrations can be handed out.
+        let mut roles = self.utils.http_client.roles(guild).await?.models().await?;
+        roles.retain(|r| r.id.get() != guild.get() && !r.managed);
+        roles.sort_by_key(|r| std::cmp::Reverse(r.position));
+
+        let games = self.panel_games().await?;
+        let shared = settings.cross_server_games();
+        let shared_names = Game::find()
+            .filter(game::Column::Id.is_in(shared.clone()))
+            .order_by_asc(game::Column::Name)
+            .all(self.utils.db_ref())
+            .await?
+            .into_iter()
+            .map(|g| g.name)
+            .collect::<Vec<_>>();
+        let blocked = settings.blocked_guilds();
+
+        let or_none = |values: Vec<String>| {
+            if values.is_empty() {
+                "None".to_string()
+            } else {
+                values.join(", ")
             }
+        };
 
-            debug!(permissions = ?perms, "user does not have permissions to call admin commands");
-        }
+        let embed = EmbedBuilder::new()
+            .title("Matchmaking settings")
+            .field(
+                EmbedFieldBuilder::new(
+                    "Matchmaking channel",
+                    settings
+                        .channel_id
+                        .as_ref()
+                        .map_or_else(|| "Not set".to_string(), |c| format!("<#{c}>")),
+                )
+                .inline(),
+            )
+            .field(
+                EmbedFieldBuilder::new(
+                    "Admin role",
+                    settings
+                        .admin_role
+                        .as_ref()
+                        .map_or_else(|| "Not set".to_string(), |r| format!("<@&{r}>")),
+                )
+                .inline(),
+            )
+            .field(
+                EmbedFieldBuilder::new(
+                    "Lobby threads",
+                    if settings.threads_are_private {
+                        "Private"
+                    } else {
+                        "Public"
+                    },
+                )
+                .inline(),
+            )
+            .field(EmbedFieldBuilder::new(
+                "LFG board",
+                match (&settings.channel_id, &settings.lfg_board_message) {
+                    (Some(channel), Some(message)) => {
+                        format!("https://discord.com/channels/{guild}/{channel}/{message}")
+                    }
+                    _ => "Not posted".to_string(),
+                },
+            ))
+            .field(EmbedFieldBuilder::new(
+                "Cross-server games",
+                or_none(shared_names),
+            ))
+            .field(EmbedFieldBuilder::new(
+                "Audit channel",
+                settings
+                    .audit_channel
+                    .as_ref()
+                    .map_or_else(|| "Not set".to_string(), |c| format!("<#{c}>")),
+            ))
+            .field(EmbedFieldBuilder::new(
+                "Report channel",
+                settings
+                    .report_channel
+                    .as_ref()
+                    .map_or_else(|| "Not set".to_string(), |c| format!("<#{c}>")),
+            ))
+            .field(EmbedFieldBuilder::new(
+                "Blocked servers",
+                or_none(blocked.iter().map(ToString::to_string).collect()),
+            ))
+            .field(
+                EmbedFieldBuilder::new(
+                    "EULA accepted",
+                    settings
+                        .has_accepted_eula
+                        .map_or_else(|| "No".to_string(), |t| format!("<t:{}:f>", t.timestamp())),
+                )
+                .inline(),
+            )
+            .field(
+                EmbedFieldBuilder::new(
+                    "Last updated",
+                    format!("<t:{}:f>", settings.last_updated.timestamp()),
+                )
+                .inline(),
+            )
+            .validate()?
+            .build();
+
+        let select =
+            |custom_id: &str, placeholder: &str, options: Vec<SelectMenuOption>, multiple: bool| {
+                Component::ActionRow(ActionRow {
+                    components: vec![Component::SelectMenu(SelectMenu {
+                        custom_id: format!("admin:settings:{custom_id}"),
+                        disabled: false,
+                        max_values: Some(if multiple {
+                            u8::try_from(options.len()).unwrap_or(25)
+                        } else {
+                            1
+                        }),
+                        min_values: Some(u8::from(!multiple)),
+                        options,
+                        placeholder: Some(placeholder.to_string()),
+                    })],
+                })
+            };
+        let button = |custom_id: &str, label: &str, disabled: bool| {
+            Component::Button(Button {
+                custom_id: Some(format!("admin:settings:{custom_id}")),
+                disabled,
+                emoji: None,
+                label: Some(label.to_string()),
+                style: ButtonStyle::Secondary,
+                url: None,
+            })
+        };
 
-        if role.map(|r| member.roles.contains(&r.into())).is_some() {
-            return true;
+        // Select menus need at least one option.
+        let mut components = Vec::new();
+        if !channels.is_empty() {
+            let options = channels
+                .iter()
+                .take(25)
+                .map(|c| SelectMenuOption {
+                    default: settings.channel_id.as_ref().map(IdWrapper::into_id) == Some(c.id),
+                    description: None,
+                    emoji: None,
+                    label: format!("#{}", c.name.as_deref().unwrap_or("channel")),
+                    value: c.id.to_string(),
+                })
+                .collect();
+            components.push(select("channel", "Matchmaking channel", options, false));
         }
-
-        false
+        if !roles.is_empty() {
+            let options = roles
+                .iter()
+                .take(25)
+                .map(|r| SelectMenuOption {
+                    default: settings.admin_role.as_ref().map(IdWrapper::into_id) == Some(r.id),
+                    description: None,
+                    emoji: None,
+                    label: r.name.clone(),
+                    value: r.id.to_string(),
+                })
+                .collect();
+            components.push(select("role", "Admin role", options, false));
+        }
+        if !games.is_empty() {
+            let options = games
+                .iter()
+                .map(|g| SelectMenuOption {
+                    default: shared.contains(&g.id),
+                    description: None,
+                    emoji: None,
+                    label: g.name.clone(),
+                    value: g.id.to_string(),
+                })
+                .collect();
+            components.push(select(
+                "cross-server",
+                "Games shared with other servers",
+                options,
+                true,
+            ));
+        }
+        components.push(Component::ActionRow(ActionRow {
+            components: vec![
+                button(
+                    "private-threads",
+                    if settings.threads_are_private {
+                        "Make lobby threads public"
+                    } else {
+                        "Make lobby threads private"
+                    },
+                    false,
+                ),
+                button(
+                    "clear-channel",
+                    "Clear channel",
+                    settings.channel_id.is_none(),
+                ),
+                button(
+                    "clear-role",
+                    "Clear admin role",
+                    settings.admin_role.is_none(),
+                ),
+            ],
+        }));
+
+        Ok((embed, components))
     }
 }
@@ -1,4 +1,7 @@
 pub mod admin_handler;
+pub mod audit_handler;
+pub mod lobbies_handler;
+pub mod mm_ban_handler;
 // TODO: Revisit this
 // pub mod mm_panels_handler;
 pub mod mm_settings_handler;
@@ -15,7 +15,7 @@ use twilight_util::builder::InteractionResponseDataBuilder as CallbackDataBuilde
 
 use super::{
     ApplicationCommandData, CommandGroupDescriptor, CommonUtilities, InteractionHandler,
-    MessageComponentData,
+    MessageComponentData, ModalSubmitData,
 };
 
 // TODO: Make a distinct EULA for the bot itself
@@ -173,7 +173,7 @@ impl InteractionHandler for EulaCommandHandler {
         unreachable!()
     }
 
-    async fn process_modal(&self, _data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
+    async fn process_modal(&self, _data: Box<ModalSubmitData>) -> anyhow::Result<()> {
         unreachable!()
     }
 
@@ -0,0 +1,2861 @@
+use std::{collections::HashMap, sync::Arc};
+
+use bot::{
+    entity::prelude::*,
+    league::{
+        ladder::{can_challenge, positions_after},
+        schedule::round_robin,
+        standings::{standings, Scoring, SetResult, Tiebreaker},
+    },
+};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use sea_orm::{
+    prelude::*, Condition, IntoActiveModel, QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+use twilight_model::{
+    application::{command::CommandType, interaction::application_command::CommandDataOption},
+    channel::{
+        message::{
+            allowed_mentions::AllowedMentionsBuilder,
+            component::{ActionRow, Button, ButtonStyle},
+            Component, MessageFlags,
+        },
+        ChannelType,
+    },
+    guild::PartialMember,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+    id::{
+        marker::{GuildMarker, UserMarker},
+        Id,
+    },
+};
+use twilight_util::builder::{
+    command::{
+        BooleanBuilder, ChannelBuilder, CommandBuilder, IntegerBuilder, StringBuilder,
+        SubCommandBuilder, SubCommandGroupBuilder, UserBuilder,
+    },
+    embed::{EmbedBuilder, EmbedFieldBuilder, EmbedFooterBuilder},
+    InteractionResponseDataBuilder,
+};
+
+use crate::interactions::application_commands::{
+    utils::{
+        boolean_option, channel_option, integer_option, string_option, subcommand_path, user_option,
+    },
+    ApplicationCommandData, CommandGroupDescriptor, CommonUtilities, InteractionHandler,
+    MessageComponentData, ModalSubmitData,
+};
+
+/// Lineups are limited by the number of player options on `/league team lineup`.
+const MAX_TEAM_SIZE: i64 = 5;
+
+pub struct LeagueCommandHandler {
+    utils: Arc<CommonUtilities>,
+}
+
+#[async_trait]
+impl InteractionHandler for LeagueCommandHandler {
+    fn describe(&self) -> CommandGroupDescriptor {
+        let builder = CommandBuilder::new(
+            "league",
+            "Create, manage and play in leagues",
+            CommandType::ChatInput,
+        )
+        .dm_permission(false)
+        .option(
+            SubCommandBuilder::new("create", "Create a new league")
+                .option(
+                    StringBuilder::new("name", "The name of the league")
+                        .required(true)
+                        .max_length(80),
+                )
+                .option(
+                    StringBuilder::new("start", "The first day of the league (YYYY-MM-DD)")
+                        .required(true),
+                )
+                .option(
+                    StringBuilder::new("end", "The last day of the league (YYYY-MM-DD)")
+                        .required(true),
+                )
+                .option(
+                    StringBuilder::new("game", "The game that's played in this league")
+                        .max_length(80),
+                )
+                .option(
+                    ChannelBuilder::new("matches-channel", "Where league matches are played")
+                        .channel_types([ChannelType::GuildText]),
+                )
+                .option(
+                    ChannelBuilder::new("reporting-channel", "Where results are posted")
+                        .channel_types([ChannelType::GuildText]),
+                )
+                .option(BooleanBuilder::new(
+                    "forfeit-unplayed",
+                    "Forfeit scheduled matches that aren't played in time (default: true)",
+                ))
+                .option(
+                    IntegerBuilder::new(
+                        "team-size",
+                        "Make this a team league with this many players in each lineup",
+                    )
+                    .min_value(1)
+                    .max_value(MAX_TEAM_SIZE),
+                )
+                .option(
+                    IntegerBuilder::new(
+                        "ladder-range",
+                        "Make this a ladder where players challenge up to this many spots above",
+                    )
+                    .min_value(1)
+                    .max_value(10),
+                ),
+        )
+        .option(
+            SubCommandBuilder::new("options", "View or change the settings of a league")
+                .option(
+                    StringBuilder::new("league", "The name of the league")
+                        .required(true)
+                        .max_length(80),
+                )
+                .option(StringBuilder::new("name", "Rename the league").max_length(80))
+                .option(StringBuilder::new(
+                    "start",
+                    "The first day of the league (YYYY-MM-DD)",
+                ))
+                .option(StringBuilder::new(
+                    "end",
+                    "The last day of the league (YYYY-MM-DD)",
+                ))
+                .option(
+                    ChannelBuilder::new("matches-channel", "Where league matches are played")
+                        .channel_types([ChannelType::GuildText]),
+                )
+                .option(
+                    ChannelBuilder::new("reporting-channel", "Where results are posted")
+                        .channel_types([ChannelType::GuildText]),
+                )
+                .option(BooleanBuilder::new(
+                    "forfeit-unplayed",
+                    "Forfeit scheduled matches that aren't played in time",
+                ))
+                .option(
+                    IntegerBuilder::new("team-size", "The number of players in each lineup")
+                        .min_value(1)
+                        .max_value(MAX_TEAM_SIZE),
+                )
+                .option(
+                    IntegerBuilder::new(
+                        "ladder-range",
+                        "How many spots above them ladder players can challenge",
+                    )
+                    .min_value(1)
+                    .max_value(10),
+                )
+                .option(
+                    IntegerBuilder::new(
+                        "challenge-hours",
+                        "How long ladder players have to accept a challenge (default: 48)",
+                    )
+                    .min_value(1)
+                    .max_value(168),
+                )
+                .option(IntegerBuilder::new(
+                    "points-win",
+                    "Points for winning a set",
+                ))
+                .option(IntegerBuilder::new(
+                    "points-draw",
+                    "Points for drawing a set",
+                ))
+                .option(IntegerBuilder::new(
+                    "points-loss",
+                    "Points for losing a set",
+                ))
+                .option(
+                    StringBuilder::new(
+                        "tiebreakers",
+                        "Comma separated: head-to-head, game-differential, strength-of-schedule",
+                    )
+                    .max_length(100),
+                ),
+        )
+        .option(
+            SubCommandBuilder::new("invite", "Invite a player to join a league")
+                .option(
+                    StringBuilder::new("league", "The name of the league")
+                        .required(true)
+                        .max_length(80),
+                )
+                .option(UserBuilder::new("player", "The player to invite").required(true)),
+        )
+        .option(
+            SubCommandBuilder::new(
+                "schedule",
+                "Schedule a round robin between every player or team in the league",
+            )
+            .option(
+                StringBuilder::new("league", "The name of the league")
+                    .required(true)
+                    .max_length(80),
+            )
+            .option(BooleanBuilder::new(
+                "double",
+                "Play everyone twice (default: false)",
+            ))
+            .option(
+                IntegerBuilder::new("days-per-round", "How long each round lasts (default: 7)")
+                    .min_value(1)
+                    .max_value(28),
+            ),
+        )
+        .option(
+            SubCommandBuilder::new("standings", "Show the standings of a league").option(
+                StringBuilder::new("league", "The name of the league")
+                    .required(true)
+                    .max_length(80),
+            ),
+        )
+        .option(
+            SubCommandGroupBuilder::new("ladder", "Commands related to ladder leagues")
+                .subcommands([
+                    SubCommandBuilder::new("view", "Show the positions on a ladder").option(
+                        StringBuilder::new("league", "The name of the league")
+                            .required(true)
+                            .max_length(80),
+                    ),
+                    SubCommandBuilder::new("challenge", "Challenge a player above you")
+                        .option(
+                            StringBuilder::new("league", "The name of the league")
+                                .required(true)
+                                .max_length(80),
+                        )
+                        .option(
+                            UserBuilder::new("player", "The player to challenge").required(true),
+                        ),
+                    SubCommandBuilder::new("report", "Report the result of your challenge")
+                        .option(
+                            StringBuilder::new("league", "The name of the league")
+                                .required(true)
+                                .max_length(80),
+                        )
+                        .option(
+                            StringBuilder::new("result", "Whether you won or lost")
+                                .required(true)
+                                .choices([("Won", "won"), ("Lost", "lost")]),
+                        )
+                        .option(UserBuilder::new(
+                            "player",
+                            "Report for this player instead (organizers only)",
+                        )),
+                ]),
+        )
+        .option(
+            SubCommandGroupBuilder::new("team", "Commands related to team leagues").subcommands([
+                SubCommandBuilder::new("create", "Create a team and become its captain")
+                    .option(
+                        StringBuilder::new("league", "The name of the league")
+                            .required(true)
+                            .max_length(80),
+                    )
+                    .option(
+                        StringBuilder::new("name", "The name of the team")
+                            .required(true)
+                            .max_length(80),
+                    ),
+                SubCommandBuilder::new("invite", "Invite a player to your team")
+                    .option(
+                        StringBuilder::new("league", "The name of the league")
+                            .required(true)
+                            .max_length(80),
+                    )
+                    .option(UserBuilder::new("player", "The player to invite").required(true)),
+                SubCommandBuilder::new("options", "View or change the settings of a team")
+                    .option(
+                        StringBuilder::new("league", "The name of the league")
+                            .required(true)
+                            .max_length(80),
+                    )
+                    .option(
+                        StringBuilder::new("team", "The name of the team")
+                            .required(true)
+                            .max_length(80),
+                    )
+                    .option(StringBuilder::new("name", "Rename the team").max_length(80))
+                    .option(UserBuilder::new("remove", "Remove a player from the team"))
+                    .option(UserBuilder::new(
+                        "captain",
+                        "Make another player the captain",
+                    )),
+                SubCommandBuilder::new("lineup", "Submit your team's lineup for a match")
+                    .option(
+                        StringBuilder::new("league", "The name of the league")
+                            .required(true)
+                            .max_length(80),
+                    )
+                    .option(
+                        IntegerBuilder::new("round", "The round of the match")
+                            .required(true)
+                            .min_value(1),
+                    )
+                    .option(UserBuilder::new("player-1", "The first player").required(true))
+                    .option(UserBuilder::new("player-2", "The second player"))
+                    .option(UserBuilder::new("player-3", "The third player"))
+                    .option(UserBuilder::new("player-4", "The fourth player"))
+                    .option(UserBuilder::new("player-5", "The fifth player")),
+                SubCommandBuilder::new("report", "Report the results of your lineup")
+                    .option(
+                        StringBuilder::new("league", "The name of the league")
+                            .required(true)
+                            .max_length(80),
+                    )
+                    .option(
+                        IntegerBuilder::new("round", "The round of the match")
+                            .required(true)
+                            .min_value(1),
+                    )
+                    .option(
+                        StringBuilder::new(
+                            "results",
+                            "A W or L for each player in lineup order, like \"W L W\"",
+                        )
+                        .required(true)
+                        .max_length(20),
+                    ),
+            ]),
+        )
+        .option(
+            SubCommandGroupBuilder::new("match", "Commands related to league matches").subcommands(
+                [
+                    SubCommandBuilder::new("list", "Show the scheduled matches of a league")
+                        .option(
+                            StringBuilder::new("league", "The name of the league")
+                                .required(true)
+                                .max_length(80),
+                        )
+                        .option(
+                            StringBuilder::new("show", "Whose matches to show (default: all)")
+                                .choices([
+                                    ("Mine", "mine"),
+                                    ("Team", "team"),
+                                    ("Player", "player"),
+                                    ("All", "all"),
+                                ]),
+                        )
+                        .option(UserBuilder::new(
+                            "player",
+                            "The player whose matches to show",
+                        ))
+                        .option(
+                            StringBuilder::new("team", "The team whose matches to show")
+                                .max_length(80),
+                        ),
+                    SubCommandBuilder::new("report", "Report the score of one of your matches")
+                        .option(
+                            StringBuilder::new("league", "The name of the league")
+                                .required(true)
+                                .max_length(80),
+                        )
+                        .option(
+                            IntegerBuilder::new("round", "The round of the match")
+                                .required(true)
+                                .min_value(1),
+                        )
+                        .option(
+                            IntegerBuilder::new("score", "The number of games you won")
+                                .required(true)
+                                .min_value(0),
+                        )
+                        .option(
+                            IntegerBuilder::new(
+                                "opponent-score",
+                                "The number of games your opponent won",
+                            )
+                            .required(true)
+                            .min_value(0),
+                        )
+                        .option(UserBuilder::new(
+                            "player",
+                            "Report for this player instead (organizers only)",
+                        )),
+                ],
+            ),
+        );
+
+        let command = builder.build();
+        CommandGroupDescriptor {
+            name: "league",
+            description: "Commands that are related to leagues",
+            commands: Box::new([command]),
+        }
+    }
+
+    async fn process_command(&self, data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
+        let member = data
+            .interaction
+            .member
+            .as_ref()
+            .ok_or_else(|| anyhow!("command cannot be run in a DM"))?;
+
+        let (path, options) = subcommand_path(&data.command.options);
+
+        match path.as_slice() {
+            ["create"] => self.create_league(&data, member, options).await,
+            ["options"] => self.league_options(&data, member, options).await,
+            ["invite"] => self.invite_player(&data, member, options).await,
+            ["schedule"] => self.schedule_league(&data, member, options).await,
+            ["team", "create"] => self.create_team(&data, member, options).await,
+            ["team", "invite"] => self.invite_to_team(&data, member, options).await,
+            ["team", "options"] => self.team_options(&data, member, options).await,
+            ["team", "lineup"] => self.submit_lineup(&data, member, options).await,
+            ["team", "report"] => self.report_lineup_results(&data, member, options).await,
+            ["standings"] => self.show_standings(&data, options).await,
+            ["ladder", "view"] => self.view_ladder(&data, options).await,
+            ["ladder", "challenge"] => self.challenge_player(&data, member, options).await,
+            ["ladder", "report"] => self.report_challenge(&data, member, options).await,
+            ["match", "list"] => self.list_matches(&data, member, options).await,
+            ["match", "report"] => self.report_match(&data, member, options).await,
+            _ => Err(anyhow!("command handler for \"{:?}\" not found.", path)),
+        }
+    }
+
+    async fn process_autocomplete(&self, _data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
+        unreachable!()
+    }
+
+    async fn process_modal(&self, _data: Box<ModalSubmitData>) -> anyhow::Result<()> {
+        unreachable!()
+    }
+
+    async fn process_component(&self, data: Box<MessageComponentData>) -> anyhow::Result<()> {
+        let user = data
+            .interaction
+            .member
+            .as_ref()
+            .and_then(|m| m.user.as_ref())
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+
+        let mut fields = data.action.split(':');
+        let action = fields.next().unwrap_or_default();
+
+        match action {
+            "accept" | "deny" => {
+                let league_id: Uuid = fields
+                    .next()
+                    .ok_or_else(|| anyhow!("no league found in the invitation"))?
+                    .parse()?;
+                let invited: u64 = fields
+                    .next()
+                    .ok_or_else(|| anyhow!("no player found in the invitation"))?
+                    .parse()?;
+
+                if user.id.get() != invited {
+                    self.utils
+                        .send_message(
+                            &data.interaction,
+                            &InteractionResponse {
+                                kind: InteractionResponseType::ChannelMessageWithSource,
+                                data: Some(
+                                    InteractionResponseDataBuilder::new()
+                                        .content("You were not invited to this league.")
+                                        .flags(MessageFlags::EPHEMERAL)
+                                        .build(),
+                                ),
+                            },
+                        )
+                        .await?;
+
+                    return Ok(());
+                }
+
+                let league = League::find_by_id(league_id)
+                    .one(self.utils.db_ref())
+                    .await?
+                    .ok_or_else(|| anyhow!("that league no longer exists"))?;
+
+                let content = if action == "accept" {
+                    let player = self.utils.find_or_create_user(user.id).await?;
+                    self.add_participant(&league, player.user_id, LeagueRole::Player)
+                        .await?;
+
+                    format!("<@{}> joined **{}**.", user.id, league.name)
+                } else {
+                    format!("<@{}> declined to join **{}**.", user.id, league.name)
+                };
+
+                self.utils
+                    .send_message(
+                        &data.interaction,
+                        &InteractionResponse {
+                            kind: InteractionResponseType::UpdateMessage,
+                            data: Some(
+                                InteractionResponseDataBuilder::new()
+                                    .content(content)
+                                    .components([])
+                                    .build(),
+                            ),
+                        },
+                    )
+                    .await?;
+
+                Ok(())
+            }
+            "team-accept" | "team-deny" => {
+                let team_id: Uuid = fields
+                    .next()
+                    .ok_or_else(|| anyhow!("no team found in the invitation"))?
+                    .parse()?;
+                let invited: u64 = fields
+                    .next()
+                    .ok_or_else(|| anyhow!("no player found in the invitation"))?
+                    .parse()?;
+
+                if user.id.get() != invited {
+                    self.utils
+                        .send_message(
+                            &data.interaction,
+                            &InteractionResponse {
+                                kind: InteractionResponseType::ChannelMessageWithSource,
+                                data: Some(
+                                    InteractionResponseDataBuilder::new()
+                                        .content("You were not invited to this team.")
+                                        .flags(MessageFlags::EPHEMERAL)
+                                        .build(),
+                                ),
+                            },
+                        )
+                        .await?;
+
+                    return Ok(());
+                }
+
+                let team = LeagueTeam::find_by_id(team_id)
+                    .one(self.utils.db_ref())
+                    .await?
+                    .ok_or_else(|| anyhow!("that team no longer exists"))?;
+
+                let content = if action == "team-accept" {
+                    let player = self.utils.find_or_create_user(user.id).await?;
+                    self.add_team_member(&team, player.user_id).await?;
+
+                    format!("<@{}> joined **{}**.", user.id, team.name)
+                } else {
+                    format!("<@{}> declined to join **{}**.", user.id, team.name)
+                };
+
+                self.utils
+                    .send_message(
+                        &data.interaction,
+                        &InteractionResponse {
+                            kind: InteractionResponseType::UpdateMessage,
+                            data: Some(
+                                InteractionResponseDataBuilder::new()
+                                    .content(content)
+                                    .components([])
+                                    .build(),
+                            ),
+                        },
+                    )
+                    .await?;
+
+                Ok(())
+            }
+            _ => Err(anyhow!("no handler for action: {}", data.action)),
+        }
+    }
+}
+
+impl LeagueCommandHandler {
+    pub fn new(utils: Arc<CommonUtilities>) -> Self {
+        Self { utils }
+    }
+
+    async fn create_league(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        if !self.utils.is_guild_admin(data.guild_id, member).await? {
+            return Err(anyhow!("only admins can create leagues"));
+        }
+
+        let name = string_option(options, "name").ok_or_else(|| anyhow!("no name provided"))?;
+        let start_date = parse_date(
+            string_option(options, "start").ok_or_else(|| anyhow!("no start date provided"))?,
+        )?;
+        let end_date = parse_date(
+            string_option(options, "end").ok_or_else(|| anyhow!("no end date provided"))?,
+        )?;
+
+        if end_date <= start_date {
+            return Err(anyhow!("the league must end after it starts"));
+        }
+
+        let game = if let Some(name) = string_option(options, "game") {
+            Some(
+                Game::find()
+                    .filter(game::Column::Name.eq(name))
+                    .one(self.utils.db_ref())
+                    .await?
+                    .ok_or_else(|| anyhow!("\"{}\" is not a known game", name))?
+                    .id,
+            )
+        } else {
+            None
+        };
+
+        let existing = League::find()
+            .filter(league::Column::GuildId.eq(IdWrapper::from(data.guild_id)))
+            .filter(league::Column::Name.eq(name))
+            .one(self.utils.db_ref())
+            .await?;
+
+        if existing.is_some() {
+            return Err(anyhow!("a league named \"{}\" already exists", name));
+        }
+
+        let user = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+        let organizer = self.utils.find_or_create_user(user.id).await?;
+
+        let league = league::Model {
+            id: Uuid::new_v4(),
+            guild_id: data.guild_id.into(),
+            name: name.to_string(),
+            game,
+            start_date,
+            end_date,
+            matches_channel: channel_option(options, "matches-channel").map(Into::into),
+            reporting_channel: channel_option(options, "reporting-channel").map(Into::into),
+            forfeit_unplayed: boolean_option(options, "forfeit-unplayed").unwrap_or(true),
+            team_size: integer_option(options, "team-size")
+                .map(i32::try_from)
+                .transpose()?,
+            points_win: Scoring::default().win,
+            points_draw: Scoring::default().draw,
+            points_loss: Scoring::default().loss,
+            tiebreakers: Tiebreaker::DEFAULT_ORDER
+                .iter()
+                .map(|t| t.name())
+                .collect::<Vec<_>>()
+                .join(","),
+            standings_posted: false,
+            ladder_range: integer_option(options, "ladder-range")
+                .map(i32::try_from)
+                .transpose()?,
+            challenge_hours: 48,
+            created_by: organizer.user_id,
+            created_at: Utc::now(),
+        };
+
+        let league = League::insert(league.into_active_model())
+            .exec_with_returning(self.utils.db_ref())
+            .await?;
+
+        self.add_participant(&league, organizer.user_id, LeagueRole::Organizer)
+            .await?;
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .embeds(&[describe_league(&league).validate()?.build()])?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn league_options(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let league = self
+            .find_league(
+                data.guild_id,
+                string_option(options, "league").ok_or_else(|| anyhow!("no league provided"))?,
+            )
+            .await?;
+
+        let has_changes = options.iter().any(|o| o.name != "league");
+
+        let league = if has_changes {
+            if !self.is_organizer(&league, data.guild_id, member).await? {
+                return Err(anyhow!("only league organizers can change league settings"));
+            }
+
+            // Teams, schedules and ladder positions are built around the team size and the
+            // ladder range, so those can't change once the league is under way.
+            let fixed = ["team-size", "ladder-range"]
+                .into_iter()
+                .find(|name| options.iter().any(|o| o.name == *name));
+            if let Some(name) = fixed {
+                let scheduled = LeagueMatch::find()
+                    .filter(league_match::Column::League.eq(league.id))
+                    .count(self.utils.db_ref())
+                    .await?
+                    + LeagueTeamMatch::find()
+                        .filter(league_team_match::Column::League.eq(league.id))
+                        .count(self.utils.db_ref())
+                        .await?;
+
+                if league.start_date <= Utc::now() || scheduled > 0 {
+                    return Err(anyhow!(
+                        "`{}` can't be changed once the league has started or has scheduled matches",
+                        name
+                    ));
+                }
+            }
+            if options.iter().any(|o| o.name == "team-size") {
+                let teams = LeagueTeam::find()
+                    .filter(league_team::Column::League.eq(league.id))
+                    .count(self.utils.db_ref())
+                    .await?;
+
+                if teams > 0 {
+                    return Err(anyhow!(
+                        "`team-size` can't be changed once teams have been created"
+                    ));
+                }
+            }
+
+            let mut model = league.clone().into_active_model();
+
+            // Either date can change on its own, so the pair is checked against the other's
+            // current value.
+            let start_date = string_option(options, "start")
+                .map(parse_date)
+                .transpose()?
+                .unwrap_or(league.start_date);
+            let end_date = string_option(options, "end")
+                .map(parse_date)
+                .transpose()?
+                .unwrap_or(league.end_date);
+            if end_date <= start_date {
+                return Err(anyhow!("the league must end after it starts"));
+            }
+
+            if let Some(name) = string_option(options, "name") {
+                model.name = Set(name.to_string());
+            }
+            model.start_date = Set(start_date);
+            model.end_date = Set(end_date);
+            if let Some(channel) = channel_option(options, "matches-channel") {
+                model.matches_channel = Set(Some(channel.into()));
+            }
+            if let Some(channel) = channel_option(options, "reporting-channel") {
+                model.reporting_channel = Set(Some(channel.into()));
+            }
+            if let Some(forfeit) = boolean_option(options, "forfeit-unplayed") {
+                model.forfeit_unplayed = Set(forfeit);
+            }
+            if let Some(size) = integer_option(options, "team-size") {
+                model.team_size = Set(Some(i32::try_from(size)?));
+            }
+            if let Some(range) = integer_option(options, "ladder-range") {
+                model.ladder_range = Set(Some(i32::try_from(range)?));
+            }
+            if let Some(hours) = integer_option(options, "challenge-hours") {
+                model.challenge_hours = Set(i32::try_from(hours)?);
+            }
+            if let Some(points) = integer_option(options, "points-win") {
+                model.points_win = Set(i32::try_from(points)?);
+            }
+            if let Some(points) = integer_option(options, "points-draw") {
+                model.points_draw = Set(i32::try_from(points)?);
+            }
+            if let Some(points) = integer_option(options, "points-loss") {
+                model.points_loss = Set(i32::try_from(points)?);
+            }
+            if let Some(tiebreakers) = string_option(options, "tiebreakers") {
+                let tiebreakers = Tiebreaker::parse_list(tiebreakers)
+                    .map_err(|t| anyhow!("\"{}\" is not a tiebreaker", t))?;
+
+                model.tiebreakers = Set(tiebreakers
+                    .iter()
+                    .map(|t| t.name())
+                    .collect::<Vec<_>>()
+                    .join(","));
+            }
+
+            let league = model.update(self.utils.db_ref()).await?;
+
+            // Players who joined before the league became a ladder start at the bottom.
+            if league.ladder_range.is_some() {
+                let players = LeagueParticipant::find()
+                    .filter(league_participant::Column::League.eq(league.id))
+                    .filter(league_participant::Column::Role.eq(LeagueRole::Player))
+                    .order_by_asc(league_participant::Column::JoinedAt)
+                    .all(self.utils.db_ref())
+                    .await?;
+
+                for player in players {
+                    self.add_ladder_position(&league, player.player).await?;
+                }
+            }
+
+            league
+        } else {
+            league
+        };
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .embeds(&[describe_league(&league).validate()?.build()])?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn invite_player(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let league = self
+            .find_league(
+                data.guild_id,
+                string_option(options, "league").ok_or_else(|| anyhow!("no league provided"))?,
+            )
+            .await?;
+
+        if !self.is_organizer(&league, data.guild_id, member).await? {
+            return Err(anyhow!("only league organizers can invite players"));
+        }
+
+        let player = user_option(options, "player").ok_or_else(|| anyhow!("no player provided"))?;
+        let organizer = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .content(
+                format!(
+                    "<@{}>, <@{}> has invited you to join **{}**.",
+                    player, organizer.id, league.name
+                )
+                .as_str(),
+            )?
+            .components(&[Component::ActionRow(ActionRow {
+                components: vec![
+                    Component::Button(Button {
+                        custom_id: Some(format!("league:accept:{}:{}", league.id, player)),
+                        disabled: false,
+                        emoji: None,
+                        label: Some("Accept".to_string()),
+                        style: ButtonStyle::Primary,
+                        url: None,
+                    }),
+                    Component::Button(Button {
+                        custom_id: Some(format!("league:deny:{}:{}", league.id, player)),
+                        disabled: false,
+                        emoji: None,
+                        label: Some("Deny".to_string()),
+                        style: ButtonStyle::Danger,
+                        url: None,
+                    }),
+                ],
+            })])?
+            .allowed_mentions(Some(
+                &AllowedMentionsBuilder::new().user_ids([player]).build(),
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn schedule_league(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let league = self
+            .find_league(
+                data.guild_id,
+                string_option(options, "league").ok_or_else(|| anyhow!("no league provided"))?,
+            )
+            .await?;
+
+        if !self.is_organizer(&league, data.guild_id, member).await? {
+            return Err(anyhow!("only league organizers can schedule matches"));
+        }
+
+        let scheduled = LeagueMatch::find()
+            .filter(league_match::Column::League.eq(league.id))
+            .count(self.utils.db_ref())
+            .await?
+            + LeagueTeamMatch::find()
+                .filter(league_team_match::Column::League.eq(league.id))
+                .count(self.utils.db_ref())
+                .await?;
+
+        if scheduled > 0 {
+            return Err(anyhow!("this league has already been scheduled"));
+        }
+
+        if league.ladder_range.is_some() {
+            return Err(anyhow!(
+                "ladder leagues are played through challenges instead of a schedule"
+            ));
+        }
+
+        // Team leagues are scheduled between teams instead of individual players.
+        let entrants = if league.team_size.is_some() {
+            LeagueTeam::find()
+                .filter(league_team::Column::League.eq(league.id))
+                .order_by_asc(league_team::Column::CreatedAt)
+                .all(self.utils.db_ref())
+                .await?
+                .into_iter()
+                .map(|t| t.id)
+                .collect::<Vec<_>>()
+        } else {
+            LeagueParticipant::find()
+                .filter(league_participant::Column::League.eq(league.id))
+                .filter(league_participant::Column::Role.eq(LeagueRole::Player))
+                .order_by_asc(league_participant::Column::JoinedAt)
+                .all(self.utils.db_ref())
+                .await?
+                .into_iter()
+                .map(|p| p.player)
+                .collect::<Vec<_>>()
+        };
+
+        if entrants.len() < 2 {
+            return Err(anyhow!(
+                "a league needs at least two players or teams to be scheduled"
+            ));
+        }
+
+        let cycles = if boolean_option(options, "double").unwrap_or(false) {
+            2
+        } else {
+            1
+        };
+        let days_per_round = integer_option(options, "days-per-round").unwrap_or(7);
+
+        let rounds = round_robin(&entrants, cycles);
+        let round_count = rounds.len();
+        let mut matches = Vec::new();
+        let mut team_matches = Vec::new();
+        let mut last_deadline = league.start_date;
+
+        for (round, pairings) in (1..).zip(rounds) {
+            last_deadline = league.start_date + Duration::days(days_per_round * i64::from(round));
+
+            for (one, two) in pairings {
+                if league.team_size.is_some() {
+                    team_matches.push(
+                        league_team_match::Model {
+                            id: Uuid::new_v4(),
+                            league: league.id,
+                            round,
+                            team_one: one,
+                            team_two: two,
+                            deadline: last_deadline,
+                            status: LeagueMatchStatus::Scheduled,
+                            reminder_sent: false,
+                        }
+                        .into_active_model(),
+                    );
+                } else {
+                    matches.push(
+                        league_match::Model {
+                            id: Uuid::new_v4(),
+                            league: league.id,
+                            round,
+                            player_one: one,
+                            player_two: two,
+                            deadline: last_deadline,
+                            status: LeagueMatchStatus::Scheduled,
+                            player_one_score: None,
+                            player_two_score: None,
+                            reminder_sent: false,
+                            reported_by: None,
+                        }
+                        .into_active_model(),
+                    );
+                }
+            }
+        }
+
+        let match_count = matches.len() + team_matches.len();
+        if !matches.is_empty() {
+            LeagueMatch::insert_many(matches)
+                .exec(self.utils.db_ref())
+                .await?;
+        }
+        if !team_matches.is_empty() {
+            LeagueTeamMatch::insert_many(team_matches)
+                .exec(self.utils.db_ref())
+                .await?;
+        }
+
+        let mut message = format!(
+            "Scheduled {} matches over {} rounds. The last round ends <t:{}:D>.",
+            match_count,
+            round_count,
+            last_deadline.timestamp()
+        );
+
+        if last_deadline > league.end_date {
+            message.push_str(" This is after the league ends, so consider changing the end date.");
+        }
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .content(message.as_str())?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn list_matches(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let league = self
+            .find_league(
+                data.guild_id,
+                string_option(options, "league").ok_or_else(|| anyhow!("no league provided"))?,
+            )
+            .await?;
+
+        if league.team_size.is_some() {
+            return self.list_team_matches(data, member, options, &league).await;
+        }
+
+        let player = match string_option(options, "show").unwrap_or("all") {
+            "mine" => Some(
+                member
+                    .user
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("could not get user data for caller"))?
+                    .id,
+            ),
+            "player" => Some(
+                user_option(options, "player")
+                    .ok_or_else(|| anyhow!("choose a player to show their matches"))?,
+            ),
+            "team" => return Err(anyhow!("\"{}\" is not a team league", league.name)),
+            _ => None,
+        };
+
+        let mut query = LeagueMatch::find()
+            .filter(league_match::Column::League.eq(league.id))
+            .order_by_asc(league_match::Column::Round)
+            .order_by_asc(league_match::Column::Deadline);
+
+        if let Some(player) = player {
+            let player = self.utils.find_or_create_user(player).await?;
+            query = query.filter(
+                Condition::any()
+                    .add(league_match::Column::PlayerOne.eq(player.user_id))
+                    .add(league_match::Column::PlayerTwo.eq(player.user_id)),
+            );
+        }
+
+        let matches = query.all(self.utils.db_ref()).await?;
+
+        let players = self
+            .discord_ids(
+                matches
+                    .iter()
+                    .flat_map(|m| [m.player_one, m.player_two])
+                    .collect(),
+            )
+            .await?;
+
+        let mention = |user: &Uuid| {
+            players
+                .get(user)
+                .map_or_else(|| "Unknown player".to_string(), |id| format!("<@{id}>"))
+        };
+
+        let lines = matches
+            .iter()
+            .map(|m| {
+                (
+                    m.round,
+                    format!(
+                        "{} vs {} ({})\n",
+                        mention(&m.player_one),
+                        mention(&m.player_two),
+                        describe_status(m.status, m.deadline)
+                    ),
+                )
+            })
+            .collect();
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .embeds(&[schedule_embed(format!("{} matches", league.name), lines)
+                .validate()?
+                .build()])?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn report_match(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let league = self
+            .find_league(
+                data.guild_id,
+                string_option(options, "league").ok_or_else(|| anyhow!("no league provided"))?,
+            )
+            .await?;
+
+        if league.team_size.is_some() {
+            return Err(anyhow!(
+                "team captains report results with `/league team report`"
+            ));
+        }
+
+        let user = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+
+        // Organizers can report on behalf of a player, which settles the match right away.
+        let on_behalf_of = user_option(options, "player");
+        if on_behalf_of.is_some() && !self.is_organizer(&league, data.guild_id, member).await? {
+            return Err(anyhow!(
+                "only organizers can report matches for other players"
+            ));
+        }
+        let reporter = on_behalf_of.unwrap_or(user.id);
+        let player = self.utils.find_or_create_user(reporter).await?;
+
+        let round = integer_option(options, "round").ok_or_else(|| anyhow!("no round provided"))?;
+        let score = i32::try_from(
+            integer_option(options, "score").ok_or_else(|| anyhow!("no score provided"))?,
+        )?;
+        let opponent_score = i32::try_from(
+            integer_option(options, "opponent-score")
+                .ok_or_else(|| anyhow!("no opponent score provided"))?,
+        )?;
+
+        let league_match = LeagueMatch::find()
+            .filter(league_match::Column::League.eq(league.id))
+            .filter(league_match::Column::Round.eq(round))
+            .filter(
+                Condition::any()
+                    .add(league_match::Column::PlayerOne.eq(player.user_id))
+                    .add(league_match::Column::PlayerTwo.eq(player.user_id)),
+            )
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("<@{}> doesn't play in round {}", reporter, round))?;
+
+        let decidable = league_match.status == LeagueMatchStatus::Scheduled
+            || (on_behalf_of.is_some() && league_match.status == LeagueMatchStatus::AdminReview);
+        if !decidable {
+            return Err(anyhow!("this match has already been decided"));
+        }
+
+        let (player_one_score, player_two_score, opponent) =
+            if league_match.player_one == player.user_id {
+                (score, opponent_score, league_match.player_two)
+            } else {
+                (opponent_score, score, league_match.player_one)
+            };
+        let opponent = self.utils.discord_user_id(opponent).await?;
+
+        let confirms = league_match
+            .reported_by
+            .is_some_and(|reported_by| reported_by != player.user_id);
+        let agrees = league_match.player_one_score == Some(player_one_score)
+            && league_match.player_two_score == Some(player_two_score);
+
+        let (status, content, announcement) = if on_behalf_of.is_some() || (confirms && agrees) {
+            let result = format!(
+                "Round {} of **{}**: <@{}> {} - {} <@{}>",
+                round, league.name, reporter, score, opponent_score, opponent
+            );
+            (LeagueMatchStatus::Completed, result.clone(), result)
+        } else if confirms {
+            (
+                LeagueMatchStatus::AdminReview,
+                "Your score doesn't match your opponent's, so an organizer will review the match."
+                    .to_string(),
+                format!(
+                    "<@{}> and <@{}> reported different scores for their round {} match in **{}**, \
+                    so it needs to be reviewed by an organizer.",
+                    reporter, opponent, round, league.name
+                ),
+            )
+        } else {
+            (
+                LeagueMatchStatus::Scheduled,
+                format!(
+                    "Reported {score} - {opponent_score} against <@{opponent}>. The match counts \
+                    once they confirm the score with `/league match report`."
+                ),
+                format!(
+                    "<@{}> reported {} - {} in their round {} match in **{}**. <@{}>, confirm the \
+                    score with `/league match report`.",
+                    reporter, score, opponent_score, round, league.name, opponent
+                ),
+            )
+        };
+
+        // The status is checked again in the update, so that a concurrent report can't
+        // settle the match twice.
+        let res = LeagueMatch::update_many()
+            .set(league_match::ActiveModel {
+                status: Set(status),
+                player_one_score: Set(Some(player_one_score)),
+                player_two_score: Set(Some(player_two_score)),
+                reported_by: Set(Some(player.user_id)),
+                ..Default::default()
+            })
+            .filter(league_match::Column::Id.eq(league_match.id))
+            .filter(league_match::Column::Status.eq(league_match.status))
+            .exec(self.utils.db_ref())
+            .await?;
+
+        if res.rows_affected == 0 {
+            return Err(anyhow!("this match has already been decided"));
+        }
+
+        if let Some(channel) = league
+            .reporting_channel
+            .as_ref()
+            .or(league.matches_channel.as_ref())
+        {
+            // Only a pending report pings the opponent, who still has to confirm it.
+            let mentions = if status == LeagueMatchStatus::Scheduled {
+                AllowedMentionsBuilder::new().user_ids([opponent]).build()
+            } else {
+                AllowedMentionsBuilder::new().build()
+            };
+
+            self.utils
+                .http_client
+                .create_message(channel.into_id())
+                .content(announcement.as_str())?
+                .allowed_mentions(Some(&mentions))
+                .await?;
+        }
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .content(content.as_str())?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn view_ladder(
+        &self,
+        data: &ApplicationCommandData,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let league = self
+            .find_league(
+                data.guild_id,
+                string_option(options, "league").ok_or_else(|| anyhow!("no league provided"))?,
+            )
+            .await?;
+
+        if league.ladder_range.is_none() {
+            return Err(anyhow!("\"{}\" is not a ladder league", league.name));
+        }
+
+        let positions = LadderPosition::find()
+            .filter(ladder_position::Column::League.eq(league.id))
+            .order_by_asc(ladder_position::Column::Position)
+            .all(self.utils.db_ref())
+            .await?;
+
+        let ids = self
+            .discord_ids(positions.iter().map(|p| p.player).collect())
+            .await?;
+
+        let mut description = String::new();
+        for position in &positions {
+            let line = format!(
+                "`#{:<2}` {}\n",
+                position.position,
+                ids.get(&position.player)
+                    .map_or_else(|| "Unknown player".to_string(), |id| format!("<@{id}>"))
+            );
+
+            // Embed descriptions are limited to 4096 characters.
+            if description.len() + line.len() > 4096 {
+                break;
+            }
+            description.push_str(&line);
+        }
+
+        if description.is_empty() {
+            description.push_str("Nobody has joined this ladder yet.");
+        }
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .embeds(&[EmbedBuilder::new()
+                .title(format!("{} ladder", league.name))
+                .description(description)
+                .validate()?
+                .build()])?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn challenge_player(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let league = self
+            .find_league(
+                data.guild_id,
+                string_option(options, "league").ok_or_else(|| anyhow!("no league provided"))?,
+            )
+            .await?;
+
+        let range = league
+            .ladder_range
+            .ok_or_else(|| anyhow!("\"{}\" is not a ladder league", league.name))?;
+
+        let user = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+        let defender_id =
+            user_option(options, "player").ok_or_else(|| anyhow!("no player provided"))?;
+
+        let challenger = self.utils.find_or_create_user(user.id).await?;
+        let defender = self.utils.find_or_create_user(defender_id).await?;
+
+        let challenger_position = LadderPosition::find_by_id((league.id, challenger.user_id))
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("you aren't on this ladder"))?;
+        let defender_position = LadderPosition::find_by_id((league.id, defender.user_id))
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("that player isn't on this ladder"))?;
+
+        if !can_challenge(
+            challenger_position.position,
+            defender_position.position,
+            range,
+        ) {
+            return Err(anyhow!(
+                "you can only challenge players up to {} spots above you",
+                range
+            ));
+        }
+
+        if self
+            .open_challenge(&league, challenger.user_id)
+            .await?
+            .is_some()
+        {
+            return Err(anyhow!("you already have an open challenge"));
+        }
+        if self
+            .open_challenge(&league, defender.user_id)
+            .await?
+            .is_some()
+        {
+            return Err(anyhow!("that player already has an open challenge"));
+        }
+
+        let channel = match &league.matches_channel {
+            Some(channel) => channel.into_id(),
+            None => data
+                .interaction
+                .channel_id
+                .ok_or_else(|| anyhow!("command was not run in a channel"))?,
+        };
+        let accept_by = Utc::now() + Duration::hours(i64::from(league.challenge_hours));
+
+        let msg = self
+            .utils
+            .http_client
+            .create_message(channel)
+            .embeds(&[EmbedBuilder::new()
+                .title("New ladder challenge")
+                .description(format!(
+                    "<@{}> (#{}) has challenged you to a match in **{}**, <@{}> (#{}). Accept <t:{}:R> or forfeit your position.",
+                    user.id,
+                    challenger_position.position,
+                    league.name,
+                    defender_id,
+                    defender_position.position,
+                    accept_by.timestamp()
+                ))
+                .validate()?
+                .build()])?
+            .components(&[Component::ActionRow(ActionRow {
+                components: vec![
+                    Component::Button(Button {
+                        custom_id: Some("matchmaking:accept".to_string()),
+                        disabled: false,
+                        emoji: None,
+                        label: Some("Accept".to_string()),
+                        style: ButtonStyle::Primary,
+                        url: None,
+                    }),
+                    Component::Button(Button {
+                        custom_id: Some("matchmaking:deny".to_string()),
+                        disabled: false,
+                        emoji: None,
+                        label: Some("Deny".to_string()),
+                        style: ButtonStyle::Danger,
+                        url: None,
+                    }),
+                ],
+            })])?
+            .allowed_mentions(Some(
+                &AllowedMentionsBuilder::new()
+                    .user_ids([user.id, defender_id])
+                    .build(),
+            ))
+            .await?
+            .model()
+            .await?;
+
+        // Challenges are matchmaking invitations, so accepting one opens a lobby thread.
+        let invitation = matchmaking_invitation::Model {
+            id: Uuid::new_v4(),
+            lobby: None,
+            extended_to: defender.user_id,
+            invited_by: challenger.user_id,
+            game: league.game,
+            description: Some(format!("Ladder challenge in {}", league.name)),
+            channel_id: channel.into(),
+            message_id: Some(msg.id.into()),
+            expires_at: accept_by,
+        };
+
+        let invitation = MatchmakingInvitation::insert(invitation.into_active_model())
+            .exec_with_returning(self.utils.db_ref())
+            .await?;
+
+        let challenge = ladder_challenge::Model {
+            id: Uuid::new_v4(),
+            league: league.id,
+            challenger: challenger.user_id,
+            defender: defender.user_id,
+            invitation: invitation.id,
+            status: LadderChallengeStatus::Pending,
+            created_at: Utc::now(),
+            accept_by,
+            winner: None,
+            play_by: None,
+            reported_by: None,
+        };
+
+        LadderChallenge::insert(challenge.into_active_model())
+            .exec(self.utils.db_ref())
+            .await?;
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .content(format!("Sent a challenge in <#{channel}>").as_str())?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn report_challenge(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let league = self
+            .find_league(
+                data.guild_id,
+                string_option(options, "league").ok_or_else(|| anyhow!("no league provided"))?,
+            )
+            .await?;
+
+        let user = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+
+        // Organizers can report on behalf of a player, which also settles disputes.
+        let on_behalf_of = user_option(options, "player");
+        if on_behalf_of.is_some() && !self.is_organizer(&league, data.guild_id, member).await? {
+            return Err(anyhow!(
+                "only organizers can report challenges for other players"
+            ));
+        }
+        let reporter = on_behalf_of.unwrap_or(user.id);
+        let player = self.utils.find_or_create_user(reporter).await?;
+
+        let challenge = self
+            .open_challenge(&league, player.user_id)
+            .await?
+            .filter(|c| {
+                c.status == LadderChallengeStatus::Accepted
+                    || (on_behalf_of.is_some() && c.status == LadderChallengeStatus::Disputed)
+            })
+            .ok_or_else(|| {
+                anyhow!(
+                    "<@{}> doesn't have an accepted challenge in this league",
+                    reporter
+                )
+            })?;
+
+        let opponent = if challenge.challenger == player.user_id {
+            challenge.defender
+        } else {
+            challenge.challenger
+        };
+        let winner = if string_option(options, "result") == Some("won") {
+            player.user_id
+        } else {
+            opponent
+        };
+
+        let confirms = challenge
+            .reported_by
+            .is_some_and(|reported_by| reported_by != player.user_id);
+
+        if on_behalf_of.is_some() || (confirms && challenge.winner == Some(winner)) {
+            let content = resolve_ladder_challenge(
+                &self.utils,
+                &challenge,
+                winner == challenge.challenger,
+                LadderChallengeStatus::Completed,
+            )
+            .await?;
+
+            self.utils
+                .http_client
+                .interaction(self.utils.application_id)
+                .create_followup(data.interaction.token.as_str())
+                .content(content.as_str())?
+                .flags(MessageFlags::EPHEMERAL)
+                .await?;
+
+            return Ok(());
+        }
+
+        let opponent = self.utils.discord_user_id(opponent).await?;
+        let (status, content, announcement) = if confirms {
+            (
+                LadderChallengeStatus::Disputed,
+                "Your result doesn't match your opponent's, so an organizer will decide the challenge."
+                    .to_string(),
+                format!(
+                    "<@{}> and <@{}> reported different winners for their challenge in **{}**, \
+                    so an organizer has to decide it.",
+                    reporter, opponent, league.name
+                ),
+            )
+        } else {
+            (
+                LadderChallengeStatus::Accepted,
+                format!("Reported the result. It counts once <@{opponent}> confirms it."),
+                format!(
+                    "<@{}> reported that <@{}> won their challenge in **{}**. <@{}>, confirm the \
+                    result with `/league ladder report`.",
+                    reporter,
+                    self.utils.discord_user_id(winner).await?,
+                    league.name,
+                    opponent
+                ),
+            )
+        };
+
+        let res = LadderChallenge::update_many()
+            .set(ladder_challenge::ActiveModel {
+                status: Set(status),
+                winner: Set(Some(winner)),
+                reported_by: Set(Some(player.user_id)),
+                ..Default::default()
+            })
+            .filter(ladder_challenge::Column::Id.eq(challenge.id))
+            .filter(ladder_challenge::Column::Status.eq(challenge.status))
+            .exec(self.utils.db_ref())
+            .await?;
+
+        if res.rows_affected == 0 {
+            return Err(anyhow!("this challenge has already been decided"));
+        }
+
+        if let Some(channel) = league
+            .reporting_channel
+            .as_ref()
+            .or(league.matches_channel.as_ref())
+        {
+            // Only a pending report pings the opponent, who still has to confirm it.
+            let mentions = if status == LadderChallengeStatus::Accepted {
+                AllowedMentionsBuilder::new().user_ids([opponent]).build()
+            } else {
+                AllowedMentionsBuilder::new().build()
+            };
+
+            self.utils
+                .http_client
+                .create_message(channel.into_id())
+                .content(announcement.as_str())?
+                .allowed_mentions(Some(&mentions))
+                .await?;
+        }
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .content(content.as_str())?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn show_standings(
+        &self,
+        data: &ApplicationCommandData,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let league = self
+            .find_league(
+                data.guild_id,
+                string_option(options, "league").ok_or_else(|| anyhow!("no league provided"))?,
+            )
+            .await?;
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .embeds(&[standings_embed(&self.utils, &league)
+                .await?
+                .validate()?
+                .build()])?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn list_team_matches(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+        league: &league::Model,
+    ) -> anyhow::Result<()> {
+        let team = match string_option(options, "show").unwrap_or("all") {
+            "mine" => Some(self.caller_team(league, member).await?),
+            "player" => {
+                let player = user_option(options, "player")
+                    .ok_or_else(|| anyhow!("choose a player to show their matches"))?;
+                let player = self.utils.find_or_create_user(player).await?;
+
+                Some(
+                    self.team_of(league, player.user_id)
+                        .await?
+                        .ok_or_else(|| anyhow!("that player isn't on a team in this league"))?,
+                )
+            }
+            "team" => Some(
+                self.find_team(
+                    league,
+                    string_option(options, "team")
+                        .ok_or_else(|| anyhow!("choose a team to show their matches"))?,
+                )
+                .await?,
+            ),
+            _ => None,
+        };
+
+        let mut query = LeagueTeamMatch::find()
+            .filter(league_team_match::Column::League.eq(league.id))
+            .order_by_asc(league_team_match::Column::Round)
+            .order_by_asc(league_team_match::Column::Deadline);
+
+        if let Some(team) = team {
+            query = query.filter(
+                Condition::any()
+                    .add(league_team_match::Column::TeamOne.eq(team.id))
+                    .add(league_team_match::Column::TeamTwo.eq(team.id)),
+            );
+        }
+
+        let matches = query.all(self.utils.db_ref()).await?;
+
+        let teams = LeagueTeam::find()
+            .filter(league_team::Column::League.eq(league.id))
+            .all(self.utils.db_ref())
+            .await?
+            .into_iter()
+            .map(|t| (t.id, t.name))
+            .collect::<HashMap<_, _>>();
+
+        let name = |team: &Uuid| teams.get(team).map_or("Unknown team", String::as_str);
+
+        let lines = matches
+            .iter()
+            .map(|m| {
+                (
+                    m.round,
+                    format!(
+                        "**{}** vs **{}** ({})\n",
+                        name(&m.team_one),
+                        name(&m.team_two),
+                        describe_status(m.status, m.deadline)
+                    ),
+                )
+            })
+            .collect();
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .embeds(&[schedule_embed(format!("{} matches", league.name), lines)
+                .validate()?
+                .build()])?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn create_team(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let league = self
+            .find_league(
+                data.guild_id,
+                string_option(options, "league").ok_or_else(|| anyhow!("no league provided"))?,
+            )
+            .await?;
+
+        if league.team_size.is_none() {
+            return Err(anyhow!("\"{}\" is not a team league", league.name));
+        }
+
+        let name = string_option(options, "name").ok_or_else(|| anyhow!("no name provided"))?;
+
+        let existing = LeagueTeam::find()
+            .filter(league_team::Column::League.eq(league.id))
+            .filter(league_team::Column::Name.eq(name))
+            .one(self.utils.db_ref())
+            .await?;
+
+        if existing.is_some() {
+            return Err(anyhow!("a team named \"{}\" already exists", name));
+        }
+
+        let user = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+        let captain = self.utils.find_or_create_user(user.id).await?;
+
+        if self.team_of(&league, captain.user_id).await?.is_some() {
+            return Err(anyhow!("you are already on a team in this league"));
+        }
+
+        let team = league_team::Model {
+            id: Uuid::new_v4(),
+            league: league.id,
+            name: name.to_string(),
+            captain: captain.user_id,
+            created_at: Utc::now(),
+        };
+
+        let team = LeagueTeam::insert(team.into_active_model())
+            .exec_with_returning(self.utils.db_ref())
+            .await?;
+
+        self.add_team_member(&team, captain.user_id).await?;
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .embeds(&[self.describe_team(&team).await?.validate()?.build()])?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn invite_to_team(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let league = self
+            .find_league(
+                data.guild_id,
+                string_option(options, "league").ok_or_else(|| anyhow!("no league provided"))?,
+            )
+            .await?;
+
+        let team = self.caller_team(&league, member).await?;
+        let captain = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+
+        if self.utils.discord_user_id(team.captain).await? != captain.id {
+            return Err(anyhow!("only the team captain can invite players"));
+        }
+
+        let player = user_option(options, "player").ok_or_else(|| anyhow!("no player provided"))?;
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .content(
+                format!(
+                    "<@{}>, <@{}> has invited you to join **{}** in **{}**.",
+                    player, captain.id, team.name, league.name
+                )
+                .as_str(),
+            )?
+            .components(&[Component::ActionRow(ActionRow {
+                components: vec![
+                    Component::Button(Button {
+                        custom_id: Some(format!("league:team-accept:{}:{}", team.id, player)),
+                        disabled: false,
+                        emoji: None,
+                        label: Some("Accept".to_string()),
+                        style: ButtonStyle::Primary,
+                        url: None,
+                    }),
+                    Component::Button(Button {
+                        custom_id: Some(format!("league:team-deny:{}:{}", team.id, player)),
+                        disabled: false,
+                        emoji: None,
+                        label: Some("Deny".to_string()),
+                        style: ButtonStyle::Danger,
+                        url: None,
+                    }),
+                ],
+            })])?
+            .allowed_mentions(Some(
+                &AllowedMentionsBuilder::new().user_ids([player]).build(),
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn team_options(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let league = self
+            .find_league(
+                data.guild_id,
+                string_option(options, "league").ok_or_else(|| anyhow!("no league provided"))?,
+            )
+            .await?;
+        let team = self
+            .find_team(
+                &league,
+                string_option(options, "team").ok_or_else(|| anyhow!("no team provided"))?,
+            )
+            .await?;
+
+        let has_changes = options
+            .iter()
+            .any(|o| o.name != "league" && o.name != "team");
+
+        let team = if has_changes {
+            let user = member
+                .user
+                .as_ref()
+                .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+            let caller = self.utils.find_or_create_user(user.id).await?;
+
+            if team.captain != caller.user_id
+                && !self.is_organizer(&league, data.guild_id, member).await?
+            {
+                return Err(anyhow!(
+                    "only the team captain or league organizers can change team settings"
+                ));
+            }
+
+            let mut model = team.clone().into_active_model();
+            let mut captain = team.captain;
+
+            if let Some(name) = string_option(options, "name") {
+                let existing = LeagueTeam::find()
+                    .filter(league_team::Column::League.eq(league.id))
+                    .filter(league_team::Column::Name.eq(name))
+                    .one(self.utils.db_ref())
+                    .await?;
+
+                if existing.is_some_and(|t| t.id != team.id) {
+                    return Err(anyhow!("a team named \"{}\" already exists", name));
+                }
+
+                model.name = Set(name.to_string());
+            }
+
+            if let Some(new_captain) = user_option(options, "captain") {
+                let new_captain = self.utils.find_or_create_user(new_captain).await?;
+                let membership = LeagueTeamMember::find_by_id((team.id, new_captain.user_id))
+                    .one(self.utils.db_ref())
+                    .await?;
+
+                if membership.is_none() {
+                    return Err(anyhow!("the new captain has to be on the team"));
+                }
+
+                model.captain = Set(new_captain.user_id);
+                captain = new_captain.user_id;
+            }
+
+            // Every option is checked before anything is saved, so a rejected change doesn't
+            // leave the others applied.
+            let removed = match user_option(options, "remove") {
+                Some(removed) => {
+                    let removed = self.utils.find_or_create_user(removed).await?;
+
+                    if removed.user_id == captain {
+                        return Err(anyhow!(
+                            "the captain can't be removed, choose a new captain first"
+                        ));
+                    }
+
+                    Some(removed)
+                }
+                None => None,
+            };
+
+            let updated = model.update(self.utils.db_ref()).await?;
+
+            if let Some(removed) = removed {
+                LeagueTeamMember::delete_by_id((team.id, removed.user_id))
+                    .exec(self.utils.db_ref())
+                    .await?;
+            }
+
+            updated
+        } else {
+            team
+        };
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .embeds(&[self.describe_team(&team).await?.validate()?.build()])?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn submit_lineup(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let league = self
+            .find_league(
+                data.guild_id,
+                string_option(options, "league").ok_or_else(|| anyhow!("no league provided"))?,
+            )
+            .await?;
+        let team_size = league
+            .team_size
+            .ok_or_else(|| anyhow!("\"{}\" is not a team league", league.name))?;
+
+        let (team, team_match) = self.captain_match(&league, member, options).await?;
+
+        let mut players = Vec::new();
+        for slot in 1..=MAX_TEAM_SIZE {
+            if let Some(player) = user_option(options, format!("player-{slot}").as_str()) {
+                players.push(self.utils.find_or_create_user(player).await?.user_id);
+            }
+        }
+
+        if players.len() != usize::try_from(team_size)? {
+            return Err(anyhow!("a lineup needs exactly {} players", team_size));
+        }
+
+        let members = LeagueTeamMember::find()
+            .filter(league_team_member::Column::Team.eq(team.id))
+            .all(self.utils.db_ref())
+            .await?;
+
+        for (i, player) in players.iter().enumerate() {
+            if !members.iter().any(|m| m.player == *player) {
+                return Err(anyhow!("player {} isn't on your team", i + 1));
+            }
+            if players[..i].contains(player) {
+                return Err(anyhow!("player {} is already in the lineup", i + 1));
+            }
+        }
+
+        let lineup = LeagueLineup::find()
+            .filter(league_lineup::Column::TeamMatch.eq(team_match.id))
+            .filter(league_lineup::Column::Team.eq(team.id))
+            .all(self.utils.db_ref())
+            .await?;
+
+        if lineup.iter().any(|l| l.won.is_some()) {
+            return Err(anyhow!(
+                "results have already been reported for this lineup"
+            ));
+        }
+
+        LeagueLineup::delete_many()
+            .filter(league_lineup::Column::TeamMatch.eq(team_match.id))
+            .filter(league_lineup::Column::Team.eq(team.id))
+            .exec(self.utils.db_ref())
+            .await?;
+
+        LeagueLineup::insert_many((1..).zip(players).map(|(slot, player)| {
+            league_lineup::Model {
+                team_match: team_match.id,
+                team: team.id,
+                slot,
+                player,
+                won: None,
+            }
+            .into_active_model()
+        }))
+        .exec(self.utils.db_ref())
+        .await?;
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .content(
+                format!(
+                    "Submitted the lineup of **{}** for round {}.",
+                    team.name, team_match.round
+                )
+                .as_str(),
+            )?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn report_lineup_results(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let league = self
+            .find_league(
+                data.guild_id,
+                string_option(options, "league").ok_or_else(|| anyhow!("no league provided"))?,
+            )
+            .await?;
+
+        let (team, team_match) = self.captain_match(&league, member, options).await?;
+
+        let results = string_option(options, "results")
+            .ok_or_else(|| anyhow!("no results provided"))?
+            .split_whitespace()
+            .map(|r| match r.to_ascii_uppercase().as_str() {
+                "W" => Ok(true),
+                "L" => Ok(false),
+                _ => Err(anyhow!("\"{}\" is not a result, use W or L", r)),
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let lineups = LeagueLineup::find()
+            .filter(league_lineup::Column::TeamMatch.eq(team_match.id))
+            .order_by_asc(league_lineup::Column::Slot)
+            .all(self.utils.db_ref())
+            .await?;
+
+        let (own, opponent): (Vec<_>, Vec<_>) =
+            lineups.into_iter().partition(|l| l.team == team.id);
+
+        if own.is_empty() || opponent.is_empty() {
+            return Err(anyhow!(
+                "both teams have to submit their lineups before reporting results"
+            ));
+        }
+
+        if results.len() != own.len() {
+            return Err(anyhow!(
+                "report one result for each of the {} players",
+                own.len()
+            ));
+        }
+
+        for (entry, won) in own.iter().zip(&results) {
+            let mut model = entry.clone().into_active_model();
+            model.won = Set(Some(*won));
+            model.update(self.utils.db_ref()).await?;
+        }
+
+        let mut content = format!(
+            "Reported the results of **{}** for round {}.",
+            team.name, team_match.round
+        );
+
+        // The match is settled once both captains have reported every player's result.
+        if opponent.iter().all(|l| l.won.is_some()) {
+            let agrees = opponent
+                .iter()
+                .zip(&results)
+                .all(|(l, won)| l.won != Some(*won));
+
+            let mut model = team_match.clone().into_active_model();
+            if agrees {
+                model.status = Set(LeagueMatchStatus::Completed);
+                content.push_str(" Both captains agree, so the match is complete.");
+            } else {
+                model.status = Set(LeagueMatchStatus::AdminReview);
+                content.push_str(
+                    " The results don't match the other captain's, so an organizer will review them.",
+                );
+            }
+            model.update(self.utils.db_ref()).await?;
+
+            if agrees {
+                self.post_team_result(&league, &team_match, &team, &results)
+                    .await?;
+            }
+        }
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .content(content.as_str())?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn post_team_result(
+        &self,
+        league: &league::Model,
+        team_match: &league_team_match::Model,
+        team: &league_team::Model,
+        results: &[bool],
+    ) -> anyhow::Result<()> {
+        let channel = match league
+            .reporting_channel
+            .as_ref()
+            .or(league.matches_channel.as_ref())
+        {
+            Some(channel) => channel.into_id(),
+            None => return Ok(()),
+        };
+
+        let opponent = LeagueTeam::find_by_id(if team_match.team_one == team.id {
+            team_match.team_two
+        } else {
+            team_match.team_one
+        })
+        .one(self.utils.db_ref())
+        .await?
+        .ok_or_else(|| anyhow!("the opposing team no longer exists"))?;
+
+        let wins = results.iter().filter(|won| **won).count();
+
+        self.utils
+            .http_client
+            .create_message(channel)
+            .content(
+                format!(
+                    "Round {} of **{}**: **{}** {} - {} **{}**",
+                    team_match.round,
+                    league.name,
+                    team.name,
+                    wins,
+                    results.len() - wins,
+                    opponent.name
+                )
+                .as_str(),
+            )?
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find_league(
+        &self,
+        guild: Id<GuildMarker>,
+        name: &str,
+    ) -> anyhow::Result<league::Model> {
+        League::find()
+            .filter(league::Column::GuildId.eq(IdWrapper::from(guild)))
+            .filter(league::Column::Name.eq(name))
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("there is no league named \"{}\"", name))
+    }
+
+    /// Guild admins can manage every league, everyone else has to be one of the league's
+    /// organizers.
+    async fn is_organizer(
+        &self,
+        league: &league::Model,
+        guild: Id<GuildMarker>,
+        member: &PartialMember,
+    ) -> anyhow::Result<bool> {
+        if self.utils.is_guild_admin(guild, member).await? {
+            return Ok(true);
+        }
+
+        let user = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+        let player = self.utils.find_or_create_user(user.id).await?;
+
+        let participant = LeagueParticipant::find_by_id((league.id, player.user_id))
+            .one(self.utils.db_ref())
+            .await?;
+
+        Ok(participant.is_some_and(|p| p.role == LeagueRole::Organizer))
+    }
+
+    async fn add_participant(
+        &self,
+        league: &league::Model,
+        player: Uuid,
+        role: LeagueRole,
+    ) -> anyhow::Result<()> {
+        let existing = LeagueParticipant::find_by_id((league.id, player))
+            .one(self.utils.db_ref())
+            .await?;
+
+        if existing.is_none() {
+            let participant = league_participant::Model {
+                league: league.id,
+                player,
+                role,
+                joined_at: Utc::now(),
+            };
+
+            LeagueParticipant::insert(participant.into_active_model())
+                .exec(self.utils.db_ref())
+                .await?;
+
+            if role == LeagueRole::Player && league.ladder_range.is_some() {
+                self.add_ladder_position(league, player).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// New ladder players start at the bottom of the ladder.
+    async fn add_ladder_position(
+        &self,
+        league: &league::Model,
+        player: Uuid,
+    ) -> anyhow::Result<()> {
+        let existing = LadderPosition::find_by_id((league.id, player))
+            .one(self.utils.db_ref())
+            .await?;
+
+        if existing.is_some() {
+            return Ok(());
+        }
+
+        let bottom = LadderPosition::find()
+            .filter(ladder_position::Column::League.eq(league.id))
+            .order_by_desc(ladder_position::Column::Position)
+            .one(self.utils.db_ref())
+            .await?
+            .map_or(0, |p| p.position);
+
+        let position = ladder_position::Model {
+            league: league.id,
+            player,
+            position: bottom + 1,
+        };
+
+        LadderPosition::insert(position.into_active_model())
+            .exec(self.utils.db_ref())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn open_challenge(
+        &self,
+        league: &league::Model,
+        player: Uuid,
+    ) -> anyhow::Result<Option<ladder_challenge::Model>> {
+        Ok(LadderChallenge::find()
+            .filter(ladder_challenge::Column::League.eq(league.id))
+            .filter(ladder_challenge::Column::Status.is_in([
+                LadderChallengeStatus::Pending,
+                LadderChallengeStatus::Accepted,
+                LadderChallengeStatus::Disputed,
+            ]))
+            .filter(
+                Condition::any()
+                    .add(ladder_challenge::Column::Challenger.eq(player))
+                    .add(ladder_challenge::Column::Defender.eq(player)),
+            )
+            .one(self.utils.db_ref())
+            .await?)
+    }
+
+    async fn find_team(
+        &self,
+        league: &league::Model,
+        name: &str,
+    ) -> anyhow::Result<league_team::Model> {
+        LeagueTeam::find()
+            .filter(league_team::Column::League.eq(league.id))
+            .filter(league_team::Column::Name.eq(name))
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("there is no team named \"{}\"", name))
+    }
+
+    async fn team_of(
+        &self,
+        league: &league::Model,
+        player: Uuid,
+    ) -> anyhow::Result<Option<league_team::Model>> {
+        let membership = LeagueTeamMember::find()
+            .filter(league_team_member::Column::League.eq(league.id))
+            .filter(league_team_member::Column::Player.eq(player))
+            .one(self.utils.db_ref())
+            .await?;
+
+        Ok(match membership {
+            Some(membership) => {
+                LeagueTeam::find_by_id(membership.team)
+                    .one(self.utils.db_ref())
+                    .await?
+            }
+            None => None,
+        })
+    }
+
+    async fn caller_team(
+        &self,
+        league: &league::Model,
+        member: &PartialMember,
+    ) -> anyhow::Result<league_team::Model> {
+        let user = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+        let player = self.utils.find_or_create_user(user.id).await?;
+
+        self.team_of(league, player.user_id)
+            .await?
+            .ok_or_else(|| anyhow!("you aren't on a team in this league"))
+    }
+
+    /// Finds the scheduled match of the caller's team in the round given in the options, and
+    /// makes sure that the caller is the team's captain.
+    async fn captain_match(
+        &self,
+        league: &league::Model,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<(league_team::Model, league_team_match::Model)> {
+        let team = self.caller_team(league, member).await?;
+        let user = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+
+        if self.utils.discord_user_id(team.captain).await? != user.id {
+            return Err(anyhow!("only the team captain can do this"));
+        }
+
+        let round = integer_option(options, "round").ok_or_else(|| anyhow!("no round provided"))?;
+
+        let team_match = LeagueTeamMatch::find()
+            .filter(league_team_match::Column::League.eq(league.id))
+            .filter(league_team_match::Column::Round.eq(round))
+            .filter(
+                Condition::any()
+                    .add(league_team_match::Column::TeamOne.eq(team.id))
+                    .add(league_team_match::Column::TeamTwo.eq(team.id)),
+            )
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("your team doesn't play in round {}", round))?;
+
+        if team_match.status != LeagueMatchStatus::Scheduled {
+            return Err(anyhow!("this match has already been decided"));
+        }
+
+        Ok((team, team_match))
+    }
+
+    async fn add_team_member(&self, team: &league_team::Model, player: Uuid) -> anyhow::Result<()> {
+        let league = League::find_by_id(team.league)
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("that league no longer exists"))?;
+
+        match self.team_of(&league, player).await? {
+            Some(existing) if existing.id == team.id => return Ok(()),
+            Some(existing) => {
+                return Err(anyhow!(
+                    "you are already on **{}** in this league",
+                    existing.name
+                ))
+            }
+            None => {}
+        }
+
+        let membership = league_team_member::Model {
+            team: team.id,
+            player,
+            league: league.id,
+            joined_at: Utc::now(),
+        };
+
+        LeagueTeamMember::insert(membership.into_active_model())
+            .exec(self.utils.db_ref())
+            .await?;
+
+        self.add_participant(&league, player, LeagueRole::Player)
+            .await
+    }
+
+    async fn discord_ids(
+        &self,
+        users: Vec<Uuid>,
+    ) -> anyhow::Result<HashMap<Uuid, IdWrapper<UserMarker>>> {
+        Ok(Users::find()
+            .filter(users::Column::UserId.is_in(users))
+            .all(self.utils.db_ref())
+            .await?
+            .into_iter()
+            .filter_map(|u| Some((u.user_id, u.discord_user?)))
+            .collect())
+    }
+
+    async fn describe_team(&self, team: &league_team::Model) -> anyhow::Result<EmbedBuilder> {
+        let members = LeagueTeamMember::find()
+            .filter(league_team_member::Column::Team.eq(team.id))
+            .order_by_asc(league_team_member::Column::JoinedAt)
+            .all(self.utils.db_ref())
+            .await?;
+
+        let ids = self
+            .discord_ids(members.iter().map(|m| m.player).collect())
+            .await?;
+        let mention = |user: &Uuid| {
+            ids.get(user)
+                .map_or_else(|| "Unknown player".to_string(), |id| format!("<@{id}>"))
+        };
+
+        Ok(EmbedBuilder::new()
+            .title(team.name.as_str())
+            .field(EmbedFieldBuilder::new("Captain", mention(&team.captain)))
+            .field(EmbedFieldBuilder::new(
+                "Members",
+                members
+                    .iter()
+                    .map(|m| mention(&m.player))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            )))
+    }
+}
+
+/// Records the result of a ladder challenge, swapping the players' positions if the
+/// challenger won, and announces it in the league's reporting channel.
+pub async fn resolve_ladder_challenge(
+    utils: &CommonUtilities,
+    challenge: &ladder_challenge::Model,
+    challenger_won: bool,
+    status: LadderChallengeStatus,
+) -> anyhow::Result<String> {
+    let league = League::find_by_id(challenge.league)
+        .one(utils.db_ref())
+        .await?
+        .ok_or_else(|| anyhow!("that league no longer exists"))?;
+
+    let (winner, loser) = if challenger_won {
+        (challenge.challenger, challenge.defender)
+    } else {
+        (challenge.defender, challenge.challenger)
+    };
+
+    // The positions are read and swapped in one transaction, and stay locked until it's
+    // done, so that another challenge result can't move either player in between.
+    let txn = utils.db_ref().begin().await?;
+
+    // Only the first resolution moves the players, in case a report and the background
+    // loop race each other.
+    let res = LadderChallenge::update_many()
+        .set(ladder_challenge::ActiveModel {
+            status: Set(status),
+            winner: Set(Some(winner)),
+            ..Default::default()
+        })
+        .filter(ladder_challenge::Column::Id.eq(challenge.id))
+        .filter(ladder_challenge::Column::Status.eq(challenge.status))
+        .exec(&txn)
+        .await?;
+
+    if res.rows_affected == 0 {
+        return Err(anyhow!("this challenge has already been decided"));
+    }
+
+    // Both rows are locked by one ordered query, so that challenges which share a player
+    // always lock them in the same order.
+    let mut positions = LadderPosition::find()
+        .filter(ladder_position::Column::League.eq(league.id))
+        .filter(ladder_position::Column::Player.is_in([challenge.challenger, challenge.defender]))
+        .order_by_asc(ladder_position::Column::Player)
+        .lock_exclusive()
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|p| (p.player, p))
+        .collect::<HashMap<_, _>>();
+    let challenger = positions
+        .remove(&challenge.challenger)
+        .ok_or_else(|| anyhow!("the challenger is no longer on the ladder"))?;
+    let defender = positions
+        .remove(&challenge.defender)
+        .ok_or_else(|| anyhow!("the defender is no longer on the ladder"))?;
+
+    let (challenger_position, defender_position) =
+        positions_after(challenger.position, defender.position, challenger_won);
+
+    let mut model = challenger.into_active_model();
+    model.position = Set(challenger_position);
+    model.update(&txn).await?;
+
+    let mut model = defender.into_active_model();
+    model.position = Set(defender_position);
+    model.update(&txn).await?;
+
+    txn.commit().await?;
+
+    let content = format!(
+        "<@{}> {} <@{}> in **{}**. <@{}> is now #{} and <@{}> is #{}.",
+        utils.discord_user_id(winner).await?,
+        if status == LadderChallengeStatus::Forfeited {
+            "won by forfeit against"
+        } else {
+            "beat"
+        },
+        utils.discord_user_id(loser).await?,
+        league.name,
+        utils.discord_user_id(challenge.challenger).await?,
+        challenger_position,
+        utils.discord_user_id(challenge.defender).await?,
+        defender_position
+    );
+
+    if let Some(channel) = league.reporting_channel.or(league.matches_channel) {
+        utils
+            .http_client
+            .create_message(channel.into_id())
+            .content(content.as_str())?
+            .allowed_mentions(None)
+            .await?;
+    }
+
+    Ok(content)
+}
+
+/// Tallies the completed and forfeited matches of a league into its standings table.
+pub async fn standings_embed(
+    utils: &CommonUtilities,
+    league: &league::Model,
+) -> anyhow::Result<EmbedBuilder> {
+    let scoring = Scoring {
+        win: league.points_win,
+        draw: league.points_draw,
+        loss: league.points_loss,
+    };
+    let tiebreakers = Tiebreaker::parse_list(&league.tiebreakers).unwrap_or_else(|t| {
+        warn!(league = ?league.id, tiebreaker = t, "league has an unknown tiebreaker");
+        Tiebreaker::DEFAULT_ORDER.to_vec()
+    });
+
+    let (table, names) = if league.team_size.is_some() {
+        let teams = LeagueTeam::find()
+            .filter(league_team::Column::League.eq(league.id))
+            .order_by_asc(league_team::Column::CreatedAt)
+            .all(utils.db_ref())
+            .await?;
+
+        let matches = LeagueTeamMatch::find()
+            .filter(league_team_match::Column::League.eq(league.id))
+            .filter(
+                league_team_match::Column::Status
+                    .is_in([LeagueMatchStatus::Completed, LeagueMatchStatus::Forfeited]),
+            )
+            .all(utils.db_ref())
+            .await?;
+
+        let lineups = LeagueLineup::find()
+            .filter(league_lineup::Column::TeamMatch.is_in(matches.iter().map(|m| m.id)))
+            .all(utils.db_ref())
+            .await?;
+
+        // A team's games are the lineup slots that its players won.
+        let wins = |team_match: Uuid, team: Uuid| {
+            let won = lineups
+                .iter()
+                .filter(|l| l.team_match == team_match && l.team == team && l.won == Some(true))
+                .count();
+            i32::try_from(won).unwrap_or(i32::MAX)
+        };
+
+        let results = matches
+            .iter()
+            .map(|m| SetResult {
+                one: m.team_one,
+                two: m.team_two,
+                games: (m.status == LeagueMatchStatus::Completed)
+                    .then(|| (wins(m.id, m.team_one), wins(m.id, m.team_two))),
+            })
+            .collect::<Vec<_>>();
+
+        let ids = teams.iter().map(|t| t.id).collect::<Vec<_>>();
+        let names = teams
+            .into_iter()
+            .map(|t| (t.id, format!("**{}**", t.name)))
+            .collect::<HashMap<_, _>>();
+
+        (standings(&ids, &results, &scoring, &tiebreakers), names)
+    } else {
+        let players = LeagueParticipant::find()
+            .filter(league_participant::Column::League.eq(league.id))
+            .filter(league_participant::Column::Role.eq(LeagueRole::Player))
+            .order_by_asc(league_participant::Column::JoinedAt)
+            .all(utils.db_ref())
+            .await?
+            .into_iter()
+            .map(|p| p.player)
+            .collect::<Vec<_>>();
+
+        let results = LeagueMatch::find()
+            .filter(league_match::Column::League.eq(league.id))
+            .filter(
+                league_match::Column::Status
+                    .is_in([LeagueMatchStatus::Completed, LeagueMatchStatus::Forfeited]),
+            )
+            .all(utils.db_ref())
+            .await?
+            .into_iter()
+            .map(|m| SetResult {
+                one: m.player_one,
+                two: m.player_two,
+                games: m.player_one_score.zip(m.player_two_score),
+            })
+            .collect::<Vec<_>>();
+
+        let names = Users::find()
+            .filter(users::Column::UserId.is_in(players.clone()))
+            .all(utils.db_ref())
+            .await?
+            .into_iter()
+            .filter_map(|u| Some((u.user_id, format!("<@{}>", u.discord_user?))))
+            .collect::<HashMap<_, _>>();
+
+        (standings(&players, &results, &scoring, &tiebreakers), names)
+    };
+
+    let mut description = String::new();
+    for (place, standing) in (1..).zip(&table) {
+        let line = format!(
+            "`{:>2}.` {} **{}** pts ({}-{}-{}, {:+})\n",
+            place,
+            names
+                .get(&standing.entrant)
+                .map_or("Unknown", String::as_str),
+            standing.points,
+            standing.wins,
+            standing.draws,
+            standing.losses,
+            standing.game_differential()
+        );
+
+        // Embed descriptions are limited to 4096 characters.
+        if description.len() + line.len() > 4096 {
+            break;
+        }
+        description.push_str(&line);
+    }
+
+    if description.is_empty() {
+        description.push_str("Nobody has played in this league yet.");
+    }
+
+    Ok(EmbedBuilder::new()
+        .title(format!("{} standings", league.name))
+        .description(description)
+        .footer(EmbedFooterBuilder::new(
+            "Record is wins-draws-losses, followed by the game differential",
+        )))
+}
+
+/// Dates are given as `YYYY-MM-DD` and start at midnight UTC.
+fn parse_date(date: &str) -> anyhow::Result<DateTime<Utc>> {
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| anyhow!("\"{}\" is not a valid date, use YYYY-MM-DD", date))?;
+
+    Ok(DateTime::from_utc(
+        date.and_hms_opt(0, 0, 0)
+            .ok_or_else(|| anyhow!("invalid date"))?,
+        Utc,
+    ))
+}
+
+fn describe_status(status: LeagueMatchStatus, deadline: DateTime<Utc>) -> String {
+    match status {
+        LeagueMatchStatus::Scheduled => format!("due <t:{}:R>", deadline.timestamp()),
+        LeagueMatchStatus::Completed => "completed".to_string(),
+        LeagueMatchStatus::Forfeited => "forfeited".to_string(),
+        LeagueMatchStatus::AdminReview => "awaiting review".to_string(),
+    }
+}
+
+/// Groups the matches of a schedule into one embed field per round.
+fn schedule_embed(title: String, lines: Vec<(i32, String)>) -> EmbedBuilder {
+    let mut embed = EmbedBuilder::new().title(title);
+
+    if lines.is_empty() {
+        embed = embed.description("No matches have been scheduled.");
+    }
+
+    // Embeds can only hold 25 fields, so only the earliest rounds are shown.
+    let mut rounds: Vec<(i32, String)> = Vec::new();
+    for (round, line) in lines {
+        let field_count = rounds.len();
+        match rounds.last_mut() {
+            Some((last, value)) if *last == round => {
+                // Field values are limited to 1024 characters.
+                if value.len() + line.len() <= 1024 {
+                    value.push_str(&line);
+                }
+            }
+            _ if field_count == 25 => break,
+            _ => rounds.push((round, line)),
+        }
+    }
+
+    for (round, value) in rounds {
+        embed = embed.field(EmbedFieldBuilder::new(format!("Round {round}"), value));
+    }
+
+    embed
+}
+
+fn describe_league(league: &league::Model) -> EmbedBuilder {
+    let channel = |c: &Option<IdWrapper<_>>| {
+        c.as_ref()
+            .map_or_else(|| "Not set".to_string(), |c| format!("<#{c}>"))
+    };
+
+    EmbedBuilder::new()
+        .title(league.name.as_str())
+        .field(
+            EmbedFieldBuilder::new("Start", format!("<t:{}:D>", league.start_date.timestamp()))
+                .inline(),
+        )
+        .field(
+            EmbedFieldBuilder::new("End", format!("<t:{}:D>", league.end_date.timestamp()))
+                .inline(),
+        )
+        .field(EmbedFieldBuilder::new(
+            "Matches channel",
+            channel(&league.matches_channel),
+        ))
+        .field(EmbedFieldBuilder::new(
+            "Reporting channel",
+            channel(&league.reporting_channel),
+        ))
+        .field(EmbedFieldBuilder::new(
+            "Unplayed matches",
+            if league.forfeit_unplayed {
+                "Forfeited"
+            } else {
+                "Reviewed by an organizer"
+            },
+        ))
+        .field(
+            EmbedFieldBuilder::new(
+                "Scoring",
+                format!(
+                    "Win {} / Draw {} / Loss {}",
+                    league.points_win, league.points_draw, league.points_loss
+                ),
+            )
+            .inline(),
+        )
+        .field(
+            EmbedFieldBuilder::new("Tiebreakers", league.tiebreakers.replace(',', ", ")).inline(),
+        )
+        .field(EmbedFieldBuilder::new(
+            "Format",
+            match (league.team_size, league.ladder_range) {
+                (Some(size), _) => format!("Teams of {size}"),
+                (None, Some(range)) => format!(
+                    "Ladder, challenge up to {} spots above within {} hours",
+                    range, league.challenge_hours
+                ),
+                (None, None) => "Individual players".to_string(),
+            },
+        ))
+}
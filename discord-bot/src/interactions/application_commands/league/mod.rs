@@ -0,0 +1 @@
+pub mod league_handler;
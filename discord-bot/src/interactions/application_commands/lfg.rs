@@ -1,109 +1,69 @@
-use chrono::Utc;
-use dashmap::DashMap;
-use entity::matchmaking::lfg;
-use entity::{
-    sea_orm::{prelude::Uuid, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter},
-    IdWrapper,
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
 };
-use std::sync::Arc;
+
+use bot::entity::prelude::*;
+use chrono::{Duration, Utc};
+use sea_orm::{prelude::*, Condition, IntoActiveModel, QueryOrder};
 use twilight_model::{
-    application::command::{
-        ChoiceCommandOptionData, CommandOption, CommandOptionChoice, CommandOptionValue,
-        CommandType, NumberCommandOptionData,
+    application::command::CommandType,
+    channel::message::{
+        component::{ActionRow, Button, ButtonStyle},
+        Component, MessageFlags,
     },
     http::interaction::{InteractionResponse, InteractionResponseType},
-    id::{
-        marker::{GuildMarker, UserMarker},
-        Id,
-    },
+    id::{marker::GuildMarker, Id},
+};
+use twilight_util::builder::{
+    command::{CommandBuilder, IntegerBuilder, StringBuilder},
+    embed::EmbedBuilder,
+    InteractionResponseDataBuilder,
 };
-use twilight_util::builder::{command::CommandBuilder, InteractionResponseDataBuilder};
 
 use super::{
-    ApplicationCommandData, ApplicationCommandUtilities, CommandGroupDescriptor,
-    InteractionHandler, MessageComponentData,
+    matchmaking::BackgroundLoop,
+    utils::{ban_notice, integer_option, string_option},
+    ApplicationCommandData, CommandGroupDescriptor, CommonUtilities, InteractionHandler,
+    MessageComponentData, ModalSubmitData,
 };
 
+#[derive(Clone)]
 pub struct LfgCommandHandler {
-    pub utils: Arc<ApplicationCommandUtilities>,
-    pub lfg_sessions: Arc<DashMap<Uuid, Box<lfg::Model>>>,
+    utils: Arc<CommonUtilities>,
+    background: Arc<BackgroundLoop>,
 }
 
 #[async_trait]
 impl InteractionHandler for LfgCommandHandler {
     fn describe(&self) -> CommandGroupDescriptor {
         let command = CommandBuilder::new(
-            "lfg".to_string(),
-            "Look for games in the server".to_string(),
+            "lfg",
+            "Start or stop looking for games in the server",
             CommandType::ChatInput,
         )
         .dm_permission(false)
-        .option(CommandOption::Integer(NumberCommandOptionData {
-            autocomplete: false,
-            choices: vec![
-                CommandOptionChoice::Int {
-                    name: "15 minutes".to_string(),
-                    value: 15,
-                    name_localizations: None,
-                },
-                CommandOptionChoice::Int {
-                    name: "30 minutes".to_string(),
-                    value: 30,
-                    name_localizations: None,
-                },
-                CommandOptionChoice::Int {
-                    name: "1 hour".to_string(),
-                    value: 60,
-                    name_localizations: None,
-                },
-                CommandOptionChoice::Int {
-                    name: "2 hours".to_string(),
-                    value: 60 * 2,
-                    name_localizations: None,
-                },
-                CommandOptionChoice::Int {
-                    name: "3 hours".to_string(),
-                    value: 60 * 3,
-                    name_localizations: None,
-                },
-                CommandOptionChoice::Int {
-                    name: "6 hours".to_string(),
-                    value: 60 * 6,
-                    name_localizations: None,
-                },
-                CommandOptionChoice::Int {
-                    name: "12 hours".to_string(),
-                    value: 60 * 12,
-                    name_localizations: None,
-                },
-                CommandOptionChoice::Int {
-                    name: "1 day".to_string(),
-                    value: 60 * 25,
-                    name_localizations: None,
-                },
-                CommandOptionChoice::Int {
-                    name: "Forever (default)".to_string(),
-                    value: -1,
-                    name_localizations: None,
-                },
-            ],
-            description: "Start/stop looking for games after a certain amount of time".to_string(),
-            name: "howlong".to_string(),
-            required: false,
-            max_value: Some(CommandOptionValue::Integer(60 * 24 * 7)),
-            min_value: Some(CommandOptionValue::Integer(-1)),
-            description_localizations: None,
-            name_localizations: None,
-        }))
-        .option(CommandOption::String(ChoiceCommandOptionData {
-            autocomplete: false,
-            choices: vec![],
-            description: "A short message alongside your entry".to_string(),
-            name: "comment".to_string(),
-            required: false,
-            description_localizations: None,
-            name_localizations: None,
-        }))
+        .option(
+            IntegerBuilder::new(
+                "howlong",
+                "Stop looking for games after a certain amount of time",
+            )
+            .choices([
+                ("15 minutes", 15),
+                ("30 minutes", 30),
+                ("1 hour", 60),
+                ("2 hours", 60 * 2),
+                ("3 hours", 60 * 3),
+                ("6 hours", 60 * 6),
+                ("12 hours", 60 * 12),
+                ("1 day", 60 * 24),
+                ("Forever (default)", -1),
+            ]),
+        )
+        .option(StringBuilder::new("game", "The game that you want to play").max_length(80))
+        .option(
+            StringBuilder::new("comment", "A short message alongside your entry").max_length(100),
+        )
         .validate()
         .expect("\"lfg\" command failed validation")
         .build();
@@ -116,115 +76,359 @@ impl InteractionHandler for LfgCommandHandler {
     }
 
     async fn process_command(&self, data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
-        let guild_id = data
-            .command
-            .guild_id
-            .ok_or(anyhow!("Command was not run in a guild"))?;
-        let member = data
-            .command
+        let user = data
+            .interaction
             .member
             .as_ref()
-            .ok_or(anyhow!("The command was not run in a guild"))?;
-        let user = member.user.as_ref().ok_or(anyhow!(
-            "Could not get user information for user \"{}\"",
-            member
-                .nick
-                .as_ref()
-                .unwrap_or(&"No nickname found".to_string())
-        ))?;
-
-        let lfg_session = self.get_user_lfg_session(guild_id, user.id).await?;
-
-        let message = if let Some(lfg) = lfg_session {
-            // De-register the sessio
-            self.delete_session(lfg).await?;
-
-            InteractionResponse {
-                kind: InteractionResponseType::ChannelMessageWithSource,
-                data: Some(
-                    InteractionResponseDataBuilder::new()
-                        .content("Stopped looking for games...".to_string())
-                        .build(),
-                ),
-            }
+            .and_then(|m| m.user.as_ref())
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+        let options = data.command.options.as_slice();
+
+        let player = self.utils.find_or_create_user(user.id).await?;
+
+        let existing = LfgSession::find()
+            .filter(lfg_session::Column::GuildId.eq(IdWrapper::from(data.guild_id)))
+            .filter(lfg_session::Column::Player.eq(player.user_id))
+            .one(self.utils.db_ref())
+            .await?;
+
+        // Running the command again takes the player off the board.
+        let content = if let Some(session) = existing {
+            LfgSession::delete_by_id(session.id)
+                .exec(self.utils.db_ref())
+                .await?;
+
+            "You stopped looking for games.".to_string()
+        } else if let Some(ban) = self.utils.active_ban(data.guild_id, player.user_id).await? {
+            // Banned players can't post to the board, which would also alert subscribers.
+            ban_notice(&ban)
         } else {
-            // Start a new session
-            let session = entity::matchmaking::lfg::Model {
+            let settings = self.utils.get_guild_settings(data.guild_id).await?;
+            let channel = settings.channel_id.ok_or_else(|| {
+                anyhow!("The server has not enabled a default matchmaking channel")
+            })?;
+
+            let game = if let Some(name) = string_option(options, "game") {
+                Some(
+                    Game::find()
+                        .filter(game::Column::Name.eq(name))
+                        .one(self.utils.db_ref())
+                        .await?
+                        .ok_or_else(|| anyhow!("\"{}\" is not a known game", name))?,
+                )
+            } else {
+                None
+            };
+
+            let started_at = Utc::now();
+            let expires_at = integer_option(options, "howlong")
+                .filter(|minutes| *minutes > 0)
+                .map(|minutes| started_at + Duration::minutes(minutes));
+
+            let session = lfg_session::Model {
                 id: Uuid::new_v4(),
-                guild_id: guild_id.into(),
-                user_id: user.id.into(),
-                started_at: Utc::now(),
-                timeout_after: None,
+                guild_id: data.guild_id.into(),
+                player: player.user_id,
+                game: game.as_ref().map(|g| g.id),
+                comment: string_option(options, "comment").map(str::to_string),
+                started_at,
+                expires_at,
             };
 
-            self.add_new_session(session).await?;
+            LfgSession::insert(session.into_active_model())
+                .exec(self.utils.db_ref())
+                .await?;
 
-            InteractionResponse {
-                kind: InteractionResponseType::ChannelMessageWithSource,
-                data: Some(
-                    InteractionResponseDataBuilder::new()
-                        .content("Started looking for games".to_string())
-                        .build(),
-                ),
+            if let Some(game) = &game {
+                if let Err(e) = self
+                    .background
+                    .alert_subscribers(data.guild_id, game, player.user_id, "is looking for games")
+                    .await
+                {
+                    warn!(error = ?e, "could not alert the game's subscribers");
+                }
             }
+
+            format!(
+                "You're on the board in <#{}> {}.",
+                channel,
+                expires_at.map_or_else(
+                    || "until you run `/lfg` again".to_string(),
+                    |t| format!("until <t:{}:t>", t.timestamp())
+                )
+            )
         };
 
         self.utils
-            .send_message(&data.command, &message)
-            .await
-            .map_err(|e| anyhow!("Could not send message to user: {}", e))?;
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .content(&content)?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
 
-        Ok(())
+        self.refresh_board(data.guild_id).await
     }
 
     async fn process_autocomplete(&self, _data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
         unreachable!()
     }
 
-    async fn process_modal(&self, _data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
+    async fn process_modal(&self, _data: Box<ModalSubmitData>) -> anyhow::Result<()> {
         unreachable!()
     }
 
-    async fn process_component(&self, _data: Box<MessageComponentData>) -> anyhow::Result<()> {
-        unreachable!()
+    async fn process_component(&self, data: Box<MessageComponentData>) -> anyhow::Result<()> {
+        let user = data
+            .interaction
+            .member
+            .as_ref()
+            .and_then(|m| m.user.as_ref())
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+        let guild_id = data
+            .interaction
+            .guild_id
+            .ok_or_else(|| anyhow!("Command cannot be run in a DM"))?;
+
+        let (action, session) = data
+            .action
+            .split_once(':')
+            .ok_or_else(|| anyhow!("no session found on the board"))?;
+        if action != "challenge" {
+            return Err(anyhow!("unknown LFG action \"{}\"", action));
+        }
+
+        let session = LfgSession::find_by_id(session.parse::<Uuid>()?)
+            .one(self.utils.db_ref())
+            .await?
+            .filter(|s| s.expires_at.is_none_or(|t| t > Utc::now()))
+            .ok_or_else(|| anyhow!("that player is no longer looking for games"))?;
+
+        let opponent = self.utils.discord_user_id(session.player).await?;
+        if opponent == user.id {
+            return Err(anyhow!("you cannot invite yourself"));
+        }
+
+        let challenger = self.utils.find_or_create_user(user.id).await?;
+        let ban_message =
+            if let Some(ban) = self.utils.active_ban(guild_id, challenger.user_id).await? {
+                Some(ban_notice(&ban))
+            } else if self
+                .utils
+                .active_ban(guild_id, session.player)
+                .await?
+                .is_some()
+            {
+                Some(format!(
+                    "<@{opponent}> can't be invited to matches right now."
+                ))
+            } else {
+                None
+            };
+
+        let settings = self.utils.get_guild_settings(guild_id).await?;
+        let channel = settings
+            .channel_id
+            .ok_or_else(|| anyhow!("The server has not enabled a default matchmaking channel"))?
+            .into_id();
+
+        let content = if let Some(content) = ban_message {
+            content
+        } else {
+            // The player on the board is the one who gets to accept.
+            self.background
+                .send_invitation(channel, user.id, opponent, session.game)
+                .await?;
+
+            format!("Sent a request to <@{opponent}> in <#{channel}>")
+        };
+
+        self.utils
+            .send_message(
+                &data.interaction,
+                &InteractionResponse {
+                    kind: InteractionResponseType::ChannelMessageWithSource,
+                    data: Some(
+                        InteractionResponseDataBuilder::new()
+                            .content(content)
+                            .flags(MessageFlags::EPHEMERAL)
+                            .build(),
+                    ),
+                },
+            )
+            .await?;
+
+        Ok(())
     }
 }
 
 impl LfgCommandHandler {
-    async fn get_user_lfg_session(
-        &self,
-        guild_id: Id<GuildMarker>,
-        user_id: Id<UserMarker>,
-    ) -> anyhow::Result<Option<entity::matchmaking::lfg::Model>> {
-        let lfg_session = lfg::Entity::find()
-            .filter(lfg::Column::UserId.eq(Into::<IdWrapper<_>>::into(user_id)))
-            .filter(lfg::Column::GuildId.eq(Into::<IdWrapper<_>>::into(guild_id)))
-            .one(self.utils.db_ref())
-            .await?;
+    pub fn new(utils: Arc<CommonUtilities>, background: Arc<BackgroundLoop>) -> Self {
+        let handler = Self { utils, background };
+
+        // Players whose time ran out are taken off the boards.
+        let expiry = handler.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_mins(1));
+            loop {
+                interval.tick().await;
+                if let Err(e) = expiry.remove_expired_sessions().await {
+                    error!(error = ?e, "failed to remove expired LFG sessions");
+                }
+            }
+        });
 
-        Ok(lfg_session)
+        handler
     }
 
-    async fn add_new_session(&self, model: lfg::Model) -> anyhow::Result<()> {
-        let boxed = Box::new(model);
-        let _res = entity::matchmaking::lfg::Entity::insert(boxed.to_owned().into_active_model())
+    async fn remove_expired_sessions(&self) -> anyhow::Result<()> {
+        let expired = LfgSession::find()
+            .filter(lfg_session::Column::ExpiresAt.lte(Utc::now()))
+            .all(self.utils.db_ref())
+            .await?;
+
+        if expired.is_empty() {
+            return Ok(());
+        }
+
+        LfgSession::delete_many()
+            .filter(lfg_session::Column::Id.is_in(expired.iter().map(|s| s.id)))
             .exec(self.utils.db_ref())
             .await?;
 
-        // self.lfg_sessions.insert(res.last_insert_id, boxed);
+        let guilds = expired
+            .iter()
+            .map(|s| s.guild_id.into_id())
+            .collect::<HashSet<_>>();
+        for guild in guilds {
+            if let Err(e) = self.refresh_board(guild).await {
+                error!(error = ?e, guild = ?guild, "failed to refresh the LFG board");
+            }
+        }
 
         Ok(())
     }
 
-    async fn delete_session(&self, model: lfg::Model) -> anyhow::Result<()> {
-        let res = entity::matchmaking::lfg::Entity::delete(model.to_owned().into_active_model())
-            .exec(self.utils.db_ref())
+    /// Redraws the guild's board in the matchmaking channel, or posts it if it isn't there
+    /// yet.
+    pub async fn refresh_board(&self, guild: Id<GuildMarker>) -> anyhow::Result<()> {
+        let settings = self.utils.get_guild_settings(guild).await?;
+        let Some(channel) = settings.channel_id.as_ref().map(IdWrapper::into_id) else {
+            return Ok(());
+        };
+
+        let sessions = LfgSession::find()
+            .filter(lfg_session::Column::GuildId.eq(IdWrapper::from(guild)))
+            .filter(
+                Condition::any()
+                    .add(lfg_session::Column::ExpiresAt.is_null())
+                    .add(lfg_session::Column::ExpiresAt.gt(Utc::now())),
+            )
+            .order_by_asc(lfg_session::Column::StartedAt)
+            .all(self.utils.db_ref())
             .await?;
 
-        debug_assert!(res.rows_affected == 1);
+        let games = Game::find()
+            .filter(game::Column::Id.is_in(sessions.iter().filter_map(|s| s.game)))
+            .all(self.utils.db_ref())
+            .await?
+            .into_iter()
+            .map(|g| (g.id, g.name))
+            .collect::<HashMap<_, _>>();
+
+        // Each listed player gets a Challenge button, and a message can't hold more than 25.
+        let mut lines = Vec::new();
+        let mut buttons = Vec::new();
+        for session in sessions.iter().take(BOARD_SIZE) {
+            let player = self.utils.discord_user_id(session.player).await?;
 
-        // self.lfg_sessions.remove(&model.id);
+            let mut parts = vec![format!("<@{player}>")];
+            if let Some(game) = session.game.and_then(|g| games.get(&g)) {
+                parts.push(format!("**{game}**"));
+            }
+            if let Some(comment) = &session.comment {
+                parts.push(format!("\"{comment}\""));
+            }
+            if let Some(expires_at) = session.expires_at {
+                parts.push(format!("until <t:{}:t>", expires_at.timestamp()));
+            }
+            lines.push(parts.join(" · "));
+
+            let name = self
+                .utils
+                .get_user(player)
+                .await
+                .map_or_else(|_| "player".to_string(), |u| u.name);
+            buttons.push(Component::Button(Button {
+                custom_id: Some(format!("lfg:challenge:{}", session.id)),
+                disabled: false,
+                emoji: None,
+                label: Some(format!("Challenge {name}")),
+                style: ButtonStyle::Primary,
+                url: None,
+            }));
+        }
+
+        if sessions.len() > BOARD_SIZE {
+            lines.push(format!("…and {} more", sessions.len() - BOARD_SIZE));
+        }
+
+        let description = if lines.is_empty() {
+            "Nobody is looking for games right now. Use `/lfg` to get on the board.".to_string()
+        } else {
+            lines.join("\n")
+        };
+
+        let embeds = [EmbedBuilder::new()
+            .title("Looking for games")
+            .description(description)
+            .validate()?
+            .build()];
+        let components = buttons
+            .chunks(5)
+            .map(|row| {
+                Component::ActionRow(ActionRow {
+                    components: row.to_vec(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(message) = settings.lfg_board_message.as_ref() {
+            // If the old board was deleted, a new one is posted instead.
+            let updated = self
+                .utils
+                .http_client
+                .update_message(channel, message.into_id())
+                .embeds(Some(&embeds))?
+                .components(Some(&components))?
+                .await;
+
+            if updated.is_ok() {
+                return Ok(());
+            }
+        }
+
+        let msg = self
+            .utils
+            .http_client
+            .create_message(channel)
+            .embeds(&embeds)?
+            .components(&components)?
+            .await?
+            .model()
+            .await?;
+
+        MatchmakingSettings::update(matchmaking_settings::ActiveModel {
+            guild_id: Set(settings.guild_id),
+            lfg_board_message: Set(Some(msg.id.into())),
+            ..Default::default()
+        })
+        .exec(self.utils.db_ref())
+        .await?;
 
         Ok(())
     }
 }
+
+/// The most players that are listed on a board.
+const BOARD_SIZE: usize = 25;
@@ -1,6 +1,12 @@
 use bot::entity::{self, prelude::*, IdWrapper};
+use bot::matchmaking::{
+    alerts::ping_messages,
+    availability::{self, Window, WEEKDAYS},
+    queue::{find_pairs_where, QueueEntry, SearchWindow},
+    rating::Outcome,
+};
 use chrono::{DateTime, FixedOffset, Utc};
-use sea_orm::{prelude::*, IntoActiveModel, Set};
+use sea_orm::{prelude::*, Condition, IntoActiveModel, QueryOrder, Set};
 use tokio::task::JoinHandle;
 use twilight_gateway::Event;
 use twilight_model::{
@@ -17,7 +23,7 @@ use twilight_model::{
         thread::{AutoArchiveDuration, ThreadMember},
         Channel, ChannelType, Message,
     },
-    gateway::payload::incoming::ChannelDelete,
+    gateway::payload::incoming::{ChannelDelete, MessageCreate},
     guild::{Guild, Member},
     http::interaction::{InteractionResponse, InteractionResponseType},
     id::{
@@ -27,27 +33,45 @@ use twilight_model::{
     user::User,
 };
 use twilight_util::builder::{
-    command::{CommandBuilder, IntegerBuilder, SubCommandBuilder},
+    command::{
+        CommandBuilder, IntegerBuilder, StringBuilder, SubCommandBuilder, SubCommandGroupBuilder,
+        UserBuilder,
+    },
     embed::{EmbedBuilder, EmbedFieldBuilder},
     InteractionResponseDataBuilder,
 };
 
 use super::{
+    league::league_handler::{resolve_ladder_challenge, standings_embed},
+    report::ReportHandler,
+    tournament::tournament_handler::TournamentCommandHandler,
+    utils::{ban_notice, integer_option, string_option, subcommand_path},
     ApplicationCommandData, CommandGroupDescriptor, CommonUtilities, InteractionHandler,
-    MessageComponentData,
+    MessageComponentData, ModalSubmitData,
 };
 
+use dashmap::DashSet;
 use futures::StreamExt;
 
 use std::{
+    collections::{HashMap, HashSet},
     ops::Add,
     os::unix::thread,
-    sync::Arc,
+    sync::{Arc, OnceLock},
     time::{Duration, Instant},
 };
 
+/// How long a subscriber waits before they're alerted about the same game again.
+const ALERT_COOLDOWN_MINUTES: i64 = 30;
+
+/// How long players stay in the queue once their search window has reached its widest. If
+/// they still weren't paired by then, they've probably stopped waiting for a match.
+const QUEUE_GRACE_MINUTES: i64 = 15;
+
 pub struct MatchmakingCommandHandler {
     utils: Arc<CommonUtilities>,
+    background: Arc<BackgroundLoop>,
+    report_handler: ReportHandler,
     _background_task: JoinHandle<()>,
 }
 
@@ -106,6 +130,97 @@ impl InteractionHandler for MatchmakingCommandHandler {
         //     .build(),
         // )
         .option(SubCommandBuilder::new("done", "Finish your matchmaking lobby").build())
+        .option(
+            SubCommandBuilder::new(
+                "queue",
+                "Wait in the queue until you're paired with a player near your rating",
+            )
+            .option(StringBuilder::new("game", "The game that you want to play").max_length(80))
+            .build(),
+        )
+        .option(SubCommandBuilder::new("leave-queue", "Leave the matchmaking queue").build())
+        .option(
+            SubCommandBuilder::new(
+                "subscribe",
+                "Get an alert when someone is looking to play a game",
+            )
+            .option(
+                StringBuilder::new("game", "The game to hear about")
+                    .required(true)
+                    .max_length(80),
+            )
+            .option(
+                StringBuilder::new("alert", "How you're alerted (default: DM)").choices([
+                    ("Direct message", "dm"),
+                    ("Ping in the matchmaking channel", "ping"),
+                ]),
+            )
+            .build(),
+        )
+        .option(
+            SubCommandBuilder::new("unsubscribe", "Stop getting alerts for a game")
+                .option(
+                    StringBuilder::new("game", "The game to stop hearing about")
+                        .required(true)
+                        .max_length(80),
+                )
+                .build(),
+        )
+        .option(
+            SubCommandBuilder::new("subscriptions", "List the games you get alerts for").build(),
+        )
+        .option(
+            SubCommandGroupBuilder::new(
+                "availability",
+                "Choose the times of the week when you get game alerts",
+            )
+            .subcommands([
+                SubCommandBuilder::new("add", "Add a weekly window when you get alerts")
+                    .option(
+                        StringBuilder::new("day", "The day the window starts on")
+                            .required(true)
+                            .choices([
+                                ("Monday", "mon"),
+                                ("Tuesday", "tue"),
+                                ("Wednesday", "wed"),
+                                ("Thursday", "thu"),
+                                ("Friday", "fri"),
+                                ("Saturday", "sat"),
+                                ("Sunday", "sun"),
+                            ]),
+                    )
+                    .option(
+                        StringBuilder::new("start", "When the window starts, like 18:00")
+                            .required(true)
+                            .max_length(5),
+                    )
+                    .option(
+                        StringBuilder::new("end", "When the window ends, like 22:30")
+                            .required(true)
+                            .max_length(5),
+                    )
+                    .option(
+                        StringBuilder::new(
+                            "time-zone",
+                            "Your time zone, like Europe/Berlin (default: the one you used before)",
+                        )
+                        .max_length(64),
+                    ),
+                SubCommandBuilder::new("list", "Show your availability windows"),
+                SubCommandBuilder::new("remove", "Remove one of your availability windows").option(
+                    IntegerBuilder::new("window", "The window's number in the list")
+                        .required(true)
+                        .min_value(1),
+                ),
+                SubCommandBuilder::new("clear", "Get alerts at any time again"),
+            ])
+            .build(),
+        )
+        .option(
+            SubCommandBuilder::new("report-abuse", "Report a player for misconduct")
+                .option(UserBuilder::new("player", "The player to report").required(true))
+                .build(),
+        )
         .option(
             SubCommandBuilder::new(
                 "report-score".to_string(),
@@ -159,6 +274,7 @@ impl InteractionHandler for MatchmakingCommandHandler {
         let member = data
             .interaction
             .member
+            .clone()
             .ok_or_else(|| anyhow!("command cannot be run in a DM"))?;
 
         let user = member
@@ -189,6 +305,38 @@ impl InteractionHandler for MatchmakingCommandHandler {
                     return Err(anyhow!("you cannot invite yourself"));
                 }
 
+                let caller = self.utils.find_or_create_user(user.id).await?;
+                let invited_player = self.utils.find_or_create_user(invited.id).await?;
+                let ban_message = if let Some(ban) =
+                    self.utils.active_ban(data.guild_id, caller.user_id).await?
+                {
+                    Some(ban_notice(&ban))
+                } else if self
+                    .utils
+                    .active_ban(data.guild_id, invited_player.user_id)
+                    .await?
+                    .is_some()
+                {
+                    Some(format!(
+                        "<@{}> can't be invited to matches right now.",
+                        invited.id
+                    ))
+                } else {
+                    None
+                };
+
+                if let Some(content) = ban_message {
+                    self.utils
+                        .http_client
+                        .interaction(self.utils.application_id)
+                        .create_followup(data.interaction.token.as_str())
+                        .content(&content)?
+                        .flags(MessageFlags::EPHEMERAL)
+                        .await?;
+
+                    return Ok(());
+                }
+
                 let guild_settings = self.utils.get_guild_settings(data.guild_id).await?;
 
                 let channel;
@@ -202,77 +350,16 @@ impl InteractionHandler for MatchmakingCommandHandler {
                         .ok_or_else(|| anyhow!("command was not run in a channel"))?;
                 }
 
-                let msg = self
-                    .utils
-                    .http_client
-                    .create_message(channel)
-                    .embeds(&[EmbedBuilder::new()
-                        .title("New matchmaking request")
-                        .description(format!(
-                            "<@{}> has invited you to a match, <@{}>",
-                            user.id, invited.id
-                        ))
-                        .validate()?
-                        .build()])?
-                    .components(&[Component::ActionRow(ActionRow {
-                        components: vec![
-                            Component::Button(Button {
-                                custom_id: Some("matchmaking:accept".to_string()),
-                                disabled: false,
-                                emoji: None,
-                                label: Some("Accept".to_string()),
-                                style: ButtonStyle::Primary,
-                                url: None,
-                            }),
-                            Component::Button(Button {
-                                custom_id: Some("matchmaking:deny".to_string()),
-                                disabled: false,
-                                emoji: None,
-                                label: Some("Deny".to_string()),
-                                style: ButtonStyle::Danger,
-                                url: None,
-                            }),
-                        ],
-                    })])?
-                    .allowed_mentions(Some(
-                        &AllowedMentionsBuilder::new()
-                            .user_ids([user.id, invited.id])
-                            .build(),
-                    ))
-                    .await?
-                    .model()
+                self.background
+                    .send_invitation(channel, user.id, invited.id, None)
                     .await?;
 
-                let _followup = self
-                    .utils
+                self.utils
                     .http_client
                     .interaction(self.utils.application_id)
                     .create_followup(data.interaction.token.as_str())
                     .content(format!("Sent a request in <#{}>", channel).as_str())?
                     .flags(MessageFlags::EPHEMERAL)
-                    .await?
-                    .model()
-                    .await?;
-
-                let author = self.utils.find_or_create_user(user.id).await?;
-                let invited = self.utils.find_or_create_user(invited.id).await?;
-
-                let invitation = matchmaking_invitation::Model {
-                    id: Uuid::new_v4(),
-                    lobby: None,
-                    extended_to: invited.user_id,
-                    invited_by: author.user_id,
-                    game: None,
-                    description: None,
-                    message_id: Some(msg.id.into()),
-                    expires_at: Utc::now() + chrono::Duration::minutes(30),
-                    channel_id: channel.into(),
-                };
-
-                debug!(invitation = ?format!("{:?}", invitation));
-
-                MatchmakingInvitation::insert(invitation.into_active_model())
-                    .exec(self.utils.db_ref())
                     .await?;
 
                 return Ok(());
@@ -288,11 +375,11 @@ impl InteractionHandler for MatchmakingCommandHandler {
                     .one(self.utils.db_ref())
                     .await?;
 
-                if lobby.is_none() {
+                let Some(lobby) = lobby else {
                     return Err(anyhow!(
                         "You must run this command in a valid matchmaking thread."
                     ));
-                }
+                };
 
                 // TODO: Refactor get resolved user from app command data to a function.
                 let resolved = data
@@ -303,6 +390,28 @@ impl InteractionHandler for MatchmakingCommandHandler {
                     anyhow!("cannot get the user specified in \"report-score\" command")
                 })?;
 
+                // The opponent in a cross-server match isn't in this thread, they play from
+                // their own guild's.
+                if let Some(cross_server_match) =
+                    self.background.cross_server_match(lobby.id).await?
+                {
+                    let options = match &subcommand.value {
+                        CommandOptionValue::SubCommand(options) => options.as_slice(),
+                        _ => &[],
+                    };
+
+                    return self
+                        .report_cross_server_score(
+                            &data.interaction.token,
+                            &user,
+                            opponent,
+                            &cross_server_match,
+                            integer_option(options, "wins").unwrap_or(0),
+                            integer_option(options, "loses").unwrap_or(0),
+                        )
+                        .await;
+                }
+
                 let response = self.utils.http_client.channel(chan_id).await?;
 
                 if !response.status().is_success() {
@@ -370,6 +479,24 @@ impl InteractionHandler for MatchmakingCommandHandler {
                     _ => 0 as i64,
                 };
 
+                // The score is saved, so that both ratings are updated once the opponent
+                // accepts it.
+                let result = matchmaking_result::Model {
+                    id: Uuid::new_v4(),
+                    lobby: lobby.id,
+                    guild_id: data.guild_id.into(),
+                    game: lobby.game,
+                    reported_by: self.utils.find_or_create_user(user.id).await?.user_id,
+                    opponent: self.utils.find_or_create_user(opponent.id).await?.user_id,
+                    reporter_wins: i32::try_from(wins)?,
+                    opponent_wins: i32::try_from(loses)?,
+                    reported_at: Utc::now(),
+                    confirmed_at: None,
+                };
+                MatchmakingResult::insert(result.clone().into_active_model())
+                    .exec(self.utils.db_ref())
+                    .await?;
+
                 // TODO: Refactor all this message stuff to reusable functions with parameters?
                 // TODO: Add an embed with options for the opponent to accept or dispute the score report.
                 // TODO: Format the message so it tags both the User & Opponent.
@@ -400,7 +527,7 @@ impl InteractionHandler for MatchmakingCommandHandler {
                     .components(&[Component::ActionRow(ActionRow {
                         components: vec![
                             Component::Button(Button {
-                                custom_id: Some("matchmaking:accept-score-report".to_string()),
+                                custom_id: Some(format!("matchmaking:score:accept:{}", result.id)),
                                 disabled: false,
                                 emoji: None,
                                 label: Some("Accept".to_string()),
@@ -408,7 +535,7 @@ impl InteractionHandler for MatchmakingCommandHandler {
                                 url: None,
                             }),
                             Component::Button(Button {
-                                custom_id: Some("matchmaking:deny-score-report".to_string()),
+                                custom_id: Some(format!("matchmaking:score:dispute:{}", result.id)),
                                 disabled: false,
                                 emoji: None,
                                 label: Some("Dispute".to_string()),
@@ -472,6 +599,8 @@ impl InteractionHandler for MatchmakingCommandHandler {
                     .filter(matchmaking_lobbies::Column::TimeoutAfter.gte(Utc::now()))
                     .exec(self.utils.db_ref())
                     .await?;
+
+                    self.background.close_mirrored_lobby(lobby.id).await?;
                 } else {
                     return Err(anyhow!(
                         "You must run this command in a valid matchmaking thread."
@@ -480,6 +609,47 @@ impl InteractionHandler for MatchmakingCommandHandler {
 
                 Ok(())
             }
+            "queue" => {
+                let options = match &subcommand.value {
+                    CommandOptionValue::SubCommand(options) => options.as_slice(),
+                    _ => &[],
+                };
+
+                let game = options
+                    .iter()
+                    .find(|o| o.name == "game")
+                    .and_then(|o| match &o.value {
+                        CommandOptionValue::String(game) => Some(game.as_str()),
+                        _ => None,
+                    });
+
+                self.join_queue(data.guild_id, &data.interaction.token, &user, game)
+                    .await
+            }
+            "leave-queue" => {
+                self.leave_queue(data.guild_id, &data.interaction.token, &user)
+                    .await
+            }
+            "subscribe" | "unsubscribe" | "subscriptions" => {
+                let options = match &subcommand.value {
+                    CommandOptionValue::SubCommand(options) => options.as_slice(),
+                    _ => &[],
+                };
+
+                self.update_subscription(
+                    data.guild_id,
+                    &data.interaction.token,
+                    &user,
+                    action.as_str(),
+                    options,
+                )
+                .await
+            }
+            "availability" => {
+                self.update_availability(&data.interaction.token, &user, &data.command.options)
+                    .await
+            }
+            "report-abuse" => self.report_handler.process_command(data).await,
             _ => return Err(anyhow!("command handler for \"{}\" not found.", action)),
         }
     }
@@ -488,11 +658,31 @@ impl InteractionHandler for MatchmakingCommandHandler {
         unreachable!()
     }
 
-    async fn process_modal(&self, _data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
-        unreachable!()
+    async fn process_modal(&self, mut data: Box<ModalSubmitData>) -> anyhow::Result<()> {
+        match data.action.split_once(':') {
+            Some(("report", action)) => {
+                data.action = action.to_string();
+                self.report_handler.process_modal(data).await
+            }
+            _ => Err(anyhow!("unknown matchmaking modal: {}", data.action)),
+        }
     }
 
-    async fn process_component(&self, data: Box<MessageComponentData>) -> anyhow::Result<()> {
+    async fn process_component(&self, mut data: Box<MessageComponentData>) -> anyhow::Result<()> {
+        if let Some(action) = data.action.strip_prefix("report:") {
+            data.action = action.to_string();
+            return self.report_handler.process_component(data).await;
+        }
+
+        if let Some(action) = data.action.strip_prefix("score:") {
+            return self.resolve_score_report(&data, action).await;
+        }
+
+        // Score reports of cross-server matches are confirmed from the opponent's thread.
+        if let Some(action) = data.action.strip_prefix("result:") {
+            return self.resolve_cross_server_report(&data, action).await;
+        }
+
         let member = data
             .interaction
             .member
@@ -550,6 +740,55 @@ impl InteractionHandler for MatchmakingCommandHandler {
                     return Ok(());
                 }
 
+                let ban_message =
+                    if let Some(ban) = self.utils.active_ban(guild_id, user_model.user_id).await? {
+                        Some(ban_notice(&ban))
+                    } else if self
+                        .utils
+                        .active_ban(guild_id, invitation.invited_by)
+                        .await?
+                        .is_some()
+                    {
+                        Some("The player who invited you can't play matches right now.".to_string())
+                    } else {
+                        None
+                    };
+
+                if let Some(content) = ban_message {
+                    self.utils
+                        .http_client
+                        .interaction(self.utils.application_id)
+                        .create_response(
+                            data.interaction.id,
+                            data.interaction.token.as_str(),
+                            &InteractionResponse {
+                                kind: InteractionResponseType::ChannelMessageWithSource,
+                                data: Some(
+                                    InteractionResponseDataBuilder::new()
+                                        .content(content)
+                                        .flags(MessageFlags::EPHEMERAL)
+                                        .build(),
+                                ),
+                            },
+                        )
+                        .await?;
+
+                    return Ok(());
+                }
+
+                let challenge = LadderChallenge::find()
+                    .filter(ladder_challenge::Column::Invitation.eq(invitation.id))
+                    .filter(ladder_challenge::Column::Status.eq(LadderChallengeStatus::Pending))
+                    .one(self.utils.db_ref())
+                    .await?;
+
+                if challenge
+                    .as_ref()
+                    .is_some_and(|c| c.accept_by <= Utc::now())
+                {
+                    return Err(anyhow!("this challenge has expired"));
+                }
+
                 let opponent = Users::find_by_id(invitation.invited_by)
                     .one(self.utils.db_ref())
                     .await?
@@ -557,96 +796,48 @@ impl InteractionHandler for MatchmakingCommandHandler {
                         anyhow!("could not find user information for the person that invited you")
                     })?;
 
-                let author_data: Member = self
-                    .utils
-                    .http_client
-                    .guild_member(
-                        guild_id,
-                        opponent
-                            .discord_user
-                            .ok_or_else(|| anyhow!("user does not have a discord id"))?
-                            .into(),
-                    )
-                    .await?
-                    .model()
-                    .await?;
-
-                let opponent_data: Member = self
-                    .utils
-                    .http_client
-                    .guild_member(
-                        guild_id,
-                        user_model
-                            .discord_user
-                            .ok_or_else(|| anyhow!("user does not have a discord id"))?
-                            .into(),
-                    )
-                    .await?
-                    .model()
-                    .await?;
                 let message_id = invitation
                     .message_id
                     .ok_or_else(|| anyhow!("no invitation message id found"))?;
 
-                let thread = self
-                    .start_matchmaking_thread(
+                self.background
+                    .open_lobby(
                         guild_id,
                         message_id.into_id(),
-                        format!(
-                            "{} vs {}",
-                            author_data.nick.unwrap_or(author_data.user.name),
-                            opponent_data.nick.unwrap_or(opponent_data.user.name)
-                        ),
+                        opponent
+                            .discord_user
+                            .ok_or_else(|| anyhow!("user does not have a discord id"))?
+                            .into_id(),
+                        user.id,
+                        invitation.game,
                     )
                     .await?;
 
-                let users = vec![author_data.user.id, opponent_data.user.id];
-                let res = self.add_users_to_thread(thread.id, &users).await;
-
-                if let Err(e) = res {
-                    // Close the thread and send an error.
-
-                    self.utils.http_client.delete_channel(thread.id).await?;
-
-                    return Err(e);
-                }
-
-                self.send_thread_opening_message(&users, thread.id).await?;
-
-                let started_at = Utc::now();
-
-                let owner = self.utils.find_or_create_user(author_data.user.id).await?;
-
-                let lobby = matchmaking_lobbies::Model {
-                    id: Uuid::new_v4(),
-                    started_at,
-                    timeout_after: started_at + chrono::Duration::hours(3),
-                    channel_id: thread.id.into(),
-                    description: None,
-                    owner: owner.user_id,
-                    privacy: LobbyPrivacy::Open,
-                    game: None,
-                    game_other: None,
-                    ended_at: None,
-                    timeout_warning_message: None,
-                };
-
-                for user in users {
-                    // Create a discord user, in case they don't exist.
-                    // TODO: Do this in bulk
-                    self.utils.find_or_create_user(user).await?;
-                }
-
-                let _res = matchmaking_lobbies::Entity::insert(lobby.into_active_model())
-                    .exec(self.utils.db_ref())
-                    .await?;
-
                 self.utils
                     .http_client
                     .update_message(invitation.channel_id.into(), message_id.into_id())
                     .components(Some(&[]))?
                     .await?;
 
+                if let Some(challenge) = challenge {
+                    let league = League::find_by_id(challenge.league)
+                        .one(self.utils.db_ref())
+                        .await?
+                        .ok_or_else(|| anyhow!("that league no longer exists"))?;
+
+                    // Players get as long to play the match as the defender had to accept it.
+                    LadderChallenge::update(ladder_challenge::ActiveModel {
+                        id: Set(challenge.id),
+                        status: Set(LadderChallengeStatus::Accepted),
+                        play_by: Set(Some(
+                            Utc::now() + chrono::Duration::hours(i64::from(league.challenge_hours)),
+                        )),
+                        ..Default::default()
+                    })
+                    .exec(self.utils.db_ref())
+                    .await?;
+                }
+
                 Ok(())
             }
             "deny" => {
@@ -693,6 +884,33 @@ impl InteractionHandler for MatchmakingCommandHandler {
                     return Err(anyhow!("not authorized to deny that invitation"));
                 }
 
+                // Declining a ladder challenge forfeits it, while the challenger can withdraw it.
+                let challenge = LadderChallenge::find()
+                    .filter(ladder_challenge::Column::Invitation.eq(invitation.id))
+                    .filter(ladder_challenge::Column::Status.eq(LadderChallengeStatus::Pending))
+                    .one(self.utils.db_ref())
+                    .await?;
+
+                if let Some(challenge) = challenge {
+                    if user_model.user_id == challenge.defender {
+                        resolve_ladder_challenge(
+                            &self.utils,
+                            &challenge,
+                            true,
+                            LadderChallengeStatus::Forfeited,
+                        )
+                        .await?;
+                    } else {
+                        LadderChallenge::update(ladder_challenge::ActiveModel {
+                            id: Set(challenge.id),
+                            status: Set(LadderChallengeStatus::Cancelled),
+                            ..Default::default()
+                        })
+                        .exec(self.utils.db_ref())
+                        .await?;
+                    }
+                }
+
                 let dm_res = self
                     .dm_users_upon_cancellation(&invitation, &user, &guild)
                     .await;
@@ -765,9 +983,9 @@ impl InteractionHandler for MatchmakingCommandHandler {
 impl MatchmakingCommandHandler {
     pub fn new(utils: Arc<CommonUtilities>) -> Self {
         // TODO: Start a thread to keep track of the matchmaking instances.
-        let utils_bg = utils.clone();
+        let background = Arc::new(BackgroundLoop::new(utils.clone()));
+        let bg = background.clone();
         let background_task = tokio::task::spawn(async move {
-            let bg = BackgroundLoop::new(utils_bg);
             loop {
                 if let Err(e) = bg.background_loop().await {
                     error!(error = ?e, "background loop update failed");
@@ -776,59 +994,860 @@ impl MatchmakingCommandHandler {
         });
 
         Self {
+            report_handler: ReportHandler::new(utils.clone(), background.clone()),
             utils,
+            background,
             _background_task: background_task,
         }
     }
 
-    async fn send_thread_opening_message(
-        &self,
-        users: impl IntoIterator<Item = &Id<UserMarker>>,
-        channel: Id<ChannelMarker>,
-    ) -> anyhow::Result<()> {
-        let _msg = self
-            .utils
-            .http_client
-            .create_message(channel)
-            .allowed_mentions(Some(
-                &AllowedMentionsBuilder::new()
-                    .user_ids(users.into_iter().copied())
-                    .build(),
-            ))
-            .embeds(&[EmbedBuilder::new()
-                .description(
-                    "**Thank you for using Runback. \
-                        Below are a list of commands to assist you during your matches.**",
-                )
-                .field(
-                    EmbedFieldBuilder::new(
-                        "/matchmaking report",
-                        "Report the score for your match",
-                    )
-                    .build(),
-                )
-                .field(
-                    EmbedFieldBuilder::new(
-                        "/matchmaking done",
-                        "Finish matchmaking and finalize results",
-                    )
-                    .build(),
-                )
-                .field(
-                    EmbedFieldBuilder::new(
-                        "/matchmaking settings",
-                        "Set the settings of the lobby.",
-                    )
-                    .build(),
-                )
-                .validate()?
-                .build()])?
-            .await?;
-
-        Ok(())
+    pub fn background(&self) -> Arc<BackgroundLoop> {
+        self.background.clone()
     }
 
-    async fn start_matchmaking_thread(
+    async fn join_queue(
+        &self,
+        guild_id: Id<GuildMarker>,
+        token: &str,
+        user: &User,
+        game: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let settings = self.utils.get_guild_settings(guild_id).await?;
+        let channel = settings
+            .channel_id
+            .ok_or_else(|| anyhow!("The server has not enabled a default matchmaking channel"))?;
+
+        let game = if let Some(name) = game {
+            Some(
+                Game::find()
+                    .filter(game::Column::Name.eq(name))
+                    .one(self.utils.db_ref())
+                    .await?
+                    .ok_or_else(|| anyhow!("\"{}\" is not a known game", name))?,
+            )
+        } else {
+            None
+        };
+
+        let player = self.utils.find_or_create_user(user.id).await?;
+
+        let existing = MatchmakingQueue::find()
+            .filter(matchmaking_queue::Column::GuildId.eq(IdWrapper::from(guild_id)))
+            .filter(matchmaking_queue::Column::Player.eq(player.user_id))
+            .one(self.utils.db_ref())
+            .await?;
+
+        let ban = self.utils.active_ban(guild_id, player.user_id).await?;
+
+        let message = if let Some(ban) = ban {
+            ban_notice(&ban)
+        } else if existing.is_some() {
+            "You're already waiting in the queue. Use `/matchmaking leave-queue` to leave it."
+                .to_string()
+        } else {
+            let rating = self
+                .utils
+                .get_rating(guild_id, player.user_id, game.as_ref().map(|g| g.id))
+                .await?;
+
+            let entry = matchmaking_queue::Model {
+                id: Uuid::new_v4(),
+                guild_id: guild_id.into(),
+                player: player.user_id,
+                game: game.as_ref().map(|g| g.id),
+                rating,
+                joined_at: Utc::now(),
+            };
+
+            MatchmakingQueue::insert(entry.into_active_model())
+                .exec(self.utils.db_ref())
+                .await?;
+
+            if let Some(game) = &game {
+                if let Err(e) = self
+                    .background
+                    .alert_subscribers(guild_id, game, player.user_id, "is waiting in the queue")
+                    .await
+                {
+                    warn!(error = ?e, "could not alert the game's subscribers");
+                }
+            }
+
+            format!(
+                "You joined the queue{}. You'll be pinged in <#{}> once you've been paired with an opponent.",
+                game.map(|g| format!(" for {}", g.name)).unwrap_or_default(),
+                channel
+            )
+        };
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(token)
+            .content(message.as_str())?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn leave_queue(
+        &self,
+        guild_id: Id<GuildMarker>,
+        token: &str,
+        user: &User,
+    ) -> anyhow::Result<()> {
+        let player = self.utils.find_or_create_user(user.id).await?;
+
+        let res = MatchmakingQueue::delete_many()
+            .filter(matchmaking_queue::Column::GuildId.eq(IdWrapper::from(guild_id)))
+            .filter(matchmaking_queue::Column::Player.eq(player.user_id))
+            .exec(self.utils.db_ref())
+            .await?;
+
+        let message = if res.rows_affected > 0 {
+            "You left the matchmaking queue."
+        } else {
+            "You're not waiting in the matchmaking queue."
+        };
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(token)
+            .content(message)?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Subscribes the user to a game, unsubscribes them, or lists their subscriptions,
+    /// depending on the subcommand.
+    async fn update_subscription(
+        &self,
+        guild_id: Id<GuildMarker>,
+        token: &str,
+        user: &User,
+        action: &str,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let player = self.utils.find_or_create_user(user.id).await?;
+
+        let subscriptions = GameSubscription::find()
+            .filter(game_subscription::Column::GuildId.eq(IdWrapper::from(guild_id)))
+            .filter(game_subscription::Column::Player.eq(player.user_id))
+            .find_also_related(Game)
+            .all(self.utils.db_ref())
+            .await?;
+
+        let message = if action == "subscriptions" {
+            if subscriptions.is_empty() {
+                "You aren't subscribed to any games. Use `/matchmaking subscribe` to get alerts."
+                    .to_string()
+            } else {
+                subscriptions
+                    .iter()
+                    .map(|(s, game)| {
+                        let alert = match s.alert {
+                            SubscriptionAlert::Dm => "direct message",
+                            SubscriptionAlert::Ping => "ping",
+                        };
+                        format!(
+                            "**{}** by {}",
+                            game.as_ref().map_or("Unknown game", |g| g.name.as_str()),
+                            alert
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        } else {
+            let name = string_option(options, "game").ok_or_else(|| anyhow!("no game provided"))?;
+            let game = Game::find()
+                .filter(game::Column::Name.eq(name))
+                .one(self.utils.db_ref())
+                .await?
+                .ok_or_else(|| anyhow!("\"{}\" is not a known game", name))?;
+
+            let existing = subscriptions
+                .into_iter()
+                .map(|(s, _)| s)
+                .find(|s| s.game == game.id);
+
+            if action == "unsubscribe" {
+                let existing =
+                    existing.ok_or_else(|| anyhow!("you aren't subscribed to {}", game.name))?;
+                GameSubscription::delete_by_id(existing.id)
+                    .exec(self.utils.db_ref())
+                    .await?;
+                self.sync_alert_role(guild_id, user.id, game.id, false)
+                    .await?;
+
+                format!("You'll no longer get alerts for **{}**.", game.name)
+            } else {
+                let alert = match string_option(options, "alert") {
+                    Some("ping") => SubscriptionAlert::Ping,
+                    _ => SubscriptionAlert::Dm,
+                };
+
+                if let Some(existing) = existing {
+                    let mut model = existing.into_active_model();
+                    model.alert = Set(alert);
+                    model.update(self.utils.db_ref()).await?;
+                } else {
+                    let subscription = game_subscription::Model {
+                        id: Uuid::new_v4(),
+                        guild_id: guild_id.into(),
+                        player: player.user_id,
+                        game: game.id,
+                        alert,
+                        last_alerted_at: None,
+                    };
+
+                    GameSubscription::insert(subscription.into_active_model())
+                        .exec(self.utils.db_ref())
+                        .await?;
+                }
+                self.sync_alert_role(guild_id, user.id, game.id, alert == SubscriptionAlert::Ping)
+                    .await?;
+
+                let how = match alert {
+                    SubscriptionAlert::Dm => "by direct message",
+                    SubscriptionAlert::Ping => "with a ping in the matchmaking channel",
+                };
+                format!(
+                    "You'll be alerted {} when someone is looking to play **{}**, at most once every {} minutes.",
+                    how, game.name, ALERT_COOLDOWN_MINUTES
+                )
+            }
+        };
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(token)
+            .content(message.as_str())?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Gives the member the game's alert role if they want to be pinged for it, and takes it
+    /// away otherwise. Nothing changes if the guild hasn't set an alert role for the game.
+    async fn sync_alert_role(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user: Id<UserMarker>,
+        game: Uuid,
+        wants_pings: bool,
+    ) -> anyhow::Result<()> {
+        let settings = self.utils.get_guild_settings(guild_id).await?;
+        let Some(role) = settings.alert_role(game) else {
+            return Ok(());
+        };
+
+        // The bot may not be allowed to manage the role, which shouldn't stop the subscription.
+        let res = if wants_pings {
+            self.utils
+                .http_client
+                .add_guild_member_role(guild_id, user, role)
+                .await
+        } else {
+            self.utils
+                .http_client
+                .remove_guild_member_role(guild_id, user, role)
+                .await
+        };
+        if let Err(e) = res {
+            warn!(error = ?e, user = ?user, "could not update a subscriber's alert role");
+        }
+
+        Ok(())
+    }
+
+    /// Saves a score report for a cross-server match, and asks the opponent to confirm it
+    /// in their own thread.
+    async fn report_cross_server_score(
+        &self,
+        token: &str,
+        user: &User,
+        opponent: &User,
+        cross_server_match: &cross_server_match::Model,
+        wins: i64,
+        losses: i64,
+    ) -> anyhow::Result<()> {
+        if cross_server_match.completed_at.is_some() {
+            return Err(anyhow!("The result of this match was already recorded"));
+        }
+
+        let reporter = self.utils.find_or_create_user(user.id).await?.user_id;
+        if reporter != cross_server_match.first_player
+            && reporter != cross_server_match.second_player
+        {
+            return Err(anyhow!("user isn't part of the lobby"));
+        }
+        if self
+            .utils
+            .discord_user_id(cross_server_match.opponent_of(reporter))
+            .await?
+            != opponent.id
+        {
+            return Err(anyhow!("opponent isn't part of the lobby"));
+        }
+
+        let (wins, losses) = (i32::try_from(wins)?, i32::try_from(losses)?);
+        let (first_player_wins, second_player_wins) = if reporter == cross_server_match.first_player
+        {
+            (wins, losses)
+        } else {
+            (losses, wins)
+        };
+
+        CrossServerMatch::update(cross_server_match::ActiveModel {
+            id: Set(cross_server_match.id),
+            reported_by: Set(Some(reporter)),
+            first_player_wins: Set(Some(first_player_wins)),
+            second_player_wins: Set(Some(second_player_wins)),
+            ..Default::default()
+        })
+        .exec(self.utils.db_ref())
+        .await?;
+
+        let lobby = if reporter == cross_server_match.first_player {
+            cross_server_match.second_lobby
+        } else {
+            cross_server_match.first_lobby
+        };
+        let thread = MatchmakingLobbies::find_by_id(lobby)
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("could not find the opponent's lobby"))?
+            .channel_id
+            .into_id();
+
+        self.utils
+            .http_client
+            .create_message(thread)
+            .content(format!("<@{}>", opponent.id).as_str())?
+            .embeds(&[EmbedBuilder::new()
+                .title("Score report")
+                .description("If the reported score is correct press the Accept button, if not you can press the Dispute button to resolve the conflict.")
+                .field(EmbedFieldBuilder::new(&user.name, wins.to_string()).inline())
+                .field(EmbedFieldBuilder::new(&opponent.name, losses.to_string()).inline())
+                .validate()?
+                .build()])?
+            .components(&[Component::ActionRow(ActionRow {
+                components: vec![
+                    Component::Button(Button {
+                        custom_id: Some(format!(
+                            "matchmaking:result:accept:{}",
+                            cross_server_match.id
+                        )),
+                        disabled: false,
+                        emoji: None,
+                        label: Some("Accept".to_string()),
+                        style: ButtonStyle::Primary,
+                        url: None,
+                    }),
+                    Component::Button(Button {
+                        custom_id: Some(format!(
+                            "matchmaking:result:dispute:{}",
+                            cross_server_match.id
+                        )),
+                        disabled: false,
+                        emoji: None,
+                        label: Some("Dispute".to_string()),
+                        style: ButtonStyle::Danger,
+                        url: None,
+                    }),
+                ],
+            })])?
+            .allowed_mentions(Some(
+                &AllowedMentionsBuilder::new()
+                    .user_ids([opponent.id])
+                    .build(),
+            ))
+            .await?;
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(token)
+            .content(
+                format!(
+                    "Reported {wins} - {losses}. Waiting for {} to accept the score.",
+                    opponent.name
+                )
+                .as_str(),
+            )?
+            .await?;
+
+        Ok(())
+    }
+
+    /// Accepts or disputes the score that the opponent reported in a lobby thread. Accepting
+    /// it updates both players' ratings.
+    async fn resolve_score_report(
+        &self,
+        data: &MessageComponentData,
+        action: &str,
+    ) -> anyhow::Result<()> {
+        let user = data
+            .interaction
+            .member
+            .as_ref()
+            .and_then(|m| m.user.as_ref())
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+        let channel = data
+            .interaction
+            .channel_id
+            .ok_or_else(|| anyhow!("could not get channel of message component"))?;
+        let guild_id = data
+            .interaction
+            .guild_id
+            .ok_or_else(|| anyhow!("Command cannot be run in a DM"))?;
+
+        let (action, id) = action
+            .split_once(':')
+            .ok_or_else(|| anyhow!("no result found on the score report"))?;
+        let result = MatchmakingResult::find_by_id(id.parse::<Uuid>()?)
+            .filter(matchmaking_result::Column::GuildId.eq(IdWrapper::from(guild_id)))
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("could not find that score report"))?;
+
+        if result.confirmed_at.is_some() {
+            return Err(anyhow!("The result of this match was already recorded"));
+        }
+
+        let player = self.utils.find_or_create_user(user.id).await?.user_id;
+        if player != result.opponent {
+            return Err(anyhow!(
+                "only the opponent of the player who reported the score can respond to it"
+            ));
+        }
+
+        let reporter = self
+            .utils
+            .get_user(self.utils.discord_user_id(result.reported_by).await?)
+            .await?;
+
+        let announcement = match action {
+            "accept" => {
+                let res = MatchmakingResult::update_many()
+                    .set(matchmaking_result::ActiveModel {
+                        confirmed_at: Set(Some(Utc::now())),
+                        ..Default::default()
+                    })
+                    .filter(matchmaking_result::Column::Id.eq(result.id))
+                    .filter(matchmaking_result::Column::ConfirmedAt.is_null())
+                    .exec(self.utils.db_ref())
+                    .await?;
+
+                if res.rows_affected == 0 {
+                    return Err(anyhow!("The result of this match was already recorded"));
+                }
+
+                let (reporter_rating, opponent_rating) = self
+                    .utils
+                    .update_ratings(
+                        (guild_id, result.reported_by),
+                        (guild_id, result.opponent),
+                        result.game,
+                        Outcome::from_score(result.reporter_wins, result.opponent_wins),
+                    )
+                    .await?;
+
+                format!(
+                    "The result was recorded: **{}** {} - {} **{}**\n\
+                    New ratings: **{}** {reporter_rating}, **{}** {opponent_rating}",
+                    reporter.name,
+                    result.reporter_wins,
+                    result.opponent_wins,
+                    user.name,
+                    reporter.name,
+                    user.name,
+                )
+            }
+            "dispute" => {
+                MatchmakingResult::delete_many()
+                    .filter(matchmaking_result::Column::Id.eq(result.id))
+                    .filter(matchmaking_result::Column::ConfirmedAt.is_null())
+                    .exec(self.utils.db_ref())
+                    .await?;
+
+                format!(
+                    "**{}** disputed the reported score. Report it again with \
+                    `/matchmaking report-score` once you agree on it.",
+                    user.name
+                )
+            }
+            _ => return Err(anyhow!("unknown score report action \"{action}\"")),
+        };
+
+        // Remove the Accept/Dispute buttons from the report
+        self.utils
+            .send_message(
+                &data.interaction,
+                &InteractionResponse {
+                    kind: InteractionResponseType::UpdateMessage,
+                    data: Some(InteractionResponseDataBuilder::new().components([]).build()),
+                },
+            )
+            .await?;
+
+        self.utils
+            .http_client
+            .create_message(channel)
+            .content(&announcement)?
+            .await?;
+
+        Ok(())
+    }
+
+    /// Accepts or disputes the score that the opponent reported for a cross-server match.
+    /// The result is recorded once, no matter which side's thread it's accepted from.
+    async fn resolve_cross_server_report(
+        &self,
+        data: &MessageComponentData,
+        action: &str,
+    ) -> anyhow::Result<()> {
+        let user = data
+            .interaction
+            .member
+            .as_ref()
+            .and_then(|m| m.user.as_ref())
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+
+        let (action, id) = action
+            .split_once(':')
+            .ok_or_else(|| anyhow!("no match found on the score report"))?;
+        let cross_server_match = CrossServerMatch::find_by_id(id.parse::<Uuid>()?)
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("could not find that match"))?;
+
+        if cross_server_match.completed_at.is_some() {
+            return Err(anyhow!("The result of this match was already recorded"));
+        }
+        let reporter = cross_server_match
+            .reported_by
+            .ok_or_else(|| anyhow!("there is no score report to respond to"))?;
+
+        let player = self.utils.find_or_create_user(user.id).await?.user_id;
+        if player != cross_server_match.opponent_of(reporter) {
+            return Err(anyhow!(
+                "only the opponent of the player who reported the score can respond to it"
+            ));
+        }
+
+        let names = [
+            self.utils
+                .get_user(
+                    self.utils
+                        .discord_user_id(cross_server_match.first_player)
+                        .await?,
+                )
+                .await?
+                .name,
+            self.utils
+                .get_user(
+                    self.utils
+                        .discord_user_id(cross_server_match.second_player)
+                        .await?,
+                )
+                .await?
+                .name,
+        ];
+
+        let announcement = match action {
+            "accept" => {
+                // Both threads share the match, so only the first confirmation is recorded.
+                let res = CrossServerMatch::update_many()
+                    .set(cross_server_match::ActiveModel {
+                        completed_at: Set(Some(Utc::now())),
+                        ..Default::default()
+                    })
+                    .filter(cross_server_match::Column::Id.eq(cross_server_match.id))
+                    .filter(cross_server_match::Column::CompletedAt.is_null())
+                    .exec(self.utils.db_ref())
+                    .await?;
+
+                if res.rows_affected == 0 {
+                    return Err(anyhow!("The result of this match was already recorded"));
+                }
+
+                // Each player is rated in the guild they played from.
+                let mut guilds = Vec::new();
+                for lobby in [
+                    cross_server_match.first_lobby,
+                    cross_server_match.second_lobby,
+                ] {
+                    let thread = MatchmakingLobbies::find_by_id(lobby)
+                        .one(self.utils.db_ref())
+                        .await?
+                        .ok_or_else(|| anyhow!("could not find a lobby of the match"))?
+                        .channel_id
+                        .into_id();
+                    let thread: Channel = self
+                        .utils
+                        .http_client
+                        .channel(thread)
+                        .await?
+                        .model()
+                        .await?;
+                    guilds.push(
+                        thread
+                            .guild_id
+                            .ok_or_else(|| anyhow!("lobby thread is not in a guild"))?,
+                    );
+                }
+
+                let (first_wins, second_wins) = (
+                    cross_server_match.first_player_wins.unwrap_or(0),
+                    cross_server_match.second_player_wins.unwrap_or(0),
+                );
+                let (first_rating, second_rating) = self
+                    .utils
+                    .update_ratings(
+                        (guilds[0], cross_server_match.first_player),
+                        (guilds[1], cross_server_match.second_player),
+                        cross_server_match.game,
+                        Outcome::from_score(first_wins, second_wins),
+                    )
+                    .await?;
+
+                format!(
+                    "The result was recorded: **{}** {first_wins} - {second_wins} **{}**\n\
+                    New ratings: **{}** {first_rating}, **{}** {second_rating}",
+                    names[0], names[1], names[0], names[1]
+                )
+            }
+            "dispute" => {
+                CrossServerMatch::update(cross_server_match::ActiveModel {
+                    id: Set(cross_server_match.id),
+                    reported_by: Set(None),
+                    first_player_wins: Set(None),
+                    second_player_wins: Set(None),
+                    ..Default::default()
+                })
+                .exec(self.utils.db_ref())
+                .await?;
+
+                format!(
+                    "**{}** disputed the reported score. Report it again with \
+                    `/matchmaking report-score` once you agree on it.",
+                    user.name
+                )
+            }
+            _ => return Err(anyhow!("unknown score report action \"{action}\"")),
+        };
+
+        // Remove the Accept/Dispute buttons from the report
+        self.utils
+            .send_message(
+                &data.interaction,
+                &InteractionResponse {
+                    kind: InteractionResponseType::UpdateMessage,
+                    data: Some(InteractionResponseDataBuilder::new().components([]).build()),
+                },
+            )
+            .await?;
+
+        self.background
+            .announce_to_match(&cross_server_match, &announcement)
+            .await
+    }
+
+    /// Adds, lists, removes or clears the user's availability windows. Times are shown with
+    /// Discord timestamps, so everyone sees them in their own time zone.
+    async fn update_availability(
+        &self,
+        token: &str,
+        user: &User,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let (path, options) = subcommand_path(options);
+        let player = self.utils.find_or_create_user(user.id).await?;
+        let now = Utc::now();
+
+        let windows = AvailabilityWindow::find()
+            .filter(availability_window::Column::Player.eq(player.user_id))
+            .order_by_asc(availability_window::Column::Weekday)
+            .order_by_asc(availability_window::Column::StartTime)
+            .all(self.utils.db_ref())
+            .await?;
+
+        let message = match path.as_slice() {
+            [_, "add"] => {
+                let day =
+                    string_option(options, "day").ok_or_else(|| anyhow!("no day provided"))?;
+                let weekday = day
+                    .parse::<chrono::Weekday>()
+                    .map_err(|_| anyhow!("\"{}\" is not a day of the week", day))?;
+                let start = availability::parse_time(
+                    string_option(options, "start").ok_or_else(|| anyhow!("no start provided"))?,
+                )?;
+                let end = availability::parse_time(
+                    string_option(options, "end").ok_or_else(|| anyhow!("no end provided"))?,
+                )?;
+                if start == end {
+                    return Err(anyhow!("a window can't start and end at the same time"));
+                }
+
+                let time_zone = match string_option(options, "time-zone") {
+                    Some(zone) => availability::parse_time_zone(zone)?,
+                    None => availability::parse_time_zone(
+                        &windows
+                            .first()
+                            .ok_or_else(|| {
+                                anyhow!(
+                                    "pick a time zone for your first window, like Europe/Berlin"
+                                )
+                            })?
+                            .time_zone,
+                    )?,
+                };
+
+                let window = availability_window::Model {
+                    id: Uuid::new_v4(),
+                    player: player.user_id,
+                    weekday: i32::try_from(weekday.num_days_from_monday())?,
+                    start_time: start,
+                    end_time: end,
+                    time_zone: time_zone.name().to_string(),
+                };
+
+                AvailabilityWindow::insert(window.into_active_model())
+                    .exec(self.utils.db_ref())
+                    .await?;
+
+                // A player has one time zone, so a new one moves the other windows along.
+                AvailabilityWindow::update_many()
+                    .set(availability_window::ActiveModel {
+                        time_zone: Set(time_zone.name().to_string()),
+                        ..Default::default()
+                    })
+                    .filter(availability_window::Column::Player.eq(player.user_id))
+                    .exec(self.utils.db_ref())
+                    .await?;
+
+                format!(
+                    "You'll get game alerts {}.",
+                    describe_window(
+                        &Window {
+                            weekday,
+                            start,
+                            end,
+                            time_zone,
+                        },
+                        now
+                    )
+                )
+            }
+            [_, "list"] => {
+                if windows.is_empty() {
+                    "You haven't set any availability windows, so you get game alerts at any time."
+                        .to_string()
+                } else {
+                    windows
+                        .iter()
+                        .filter_map(availability_window)
+                        .enumerate()
+                        .map(|(i, w)| format!("{}. {}", i + 1, describe_window(&w, now)))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                }
+            }
+            [_, "remove"] => {
+                let number = integer_option(options, "window")
+                    .ok_or_else(|| anyhow!("no window provided"))?;
+                let window = usize::try_from(number - 1)
+                    .ok()
+                    .and_then(|i| windows.get(i))
+                    .ok_or_else(|| anyhow!("you don't have a window number {}", number))?;
+
+                AvailabilityWindow::delete_by_id(window.id)
+                    .exec(self.utils.db_ref())
+                    .await?;
+
+                "Removed the window.".to_string()
+            }
+            [_, "clear"] => {
+                AvailabilityWindow::delete_many()
+                    .filter(availability_window::Column::Player.eq(player.user_id))
+                    .exec(self.utils.db_ref())
+                    .await?;
+
+                "Removed your availability windows, so you'll get game alerts at any time."
+                    .to_string()
+            }
+            _ => return Err(anyhow!("command handler for \"{:?}\" not found.", path)),
+        };
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(token)
+            .content(message.as_str())?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl BackgroundLoop {
+    async fn send_thread_opening_message(
+        &self,
+        users: impl IntoIterator<Item = &Id<UserMarker>>,
+        channel: Id<ChannelMarker>,
+    ) -> anyhow::Result<()> {
+        let _msg = self
+            .utils
+            .http_client
+            .create_message(channel)
+            .allowed_mentions(Some(
+                &AllowedMentionsBuilder::new()
+                    .user_ids(users.into_iter().copied())
+                    .build(),
+            ))
+            .embeds(&[EmbedBuilder::new()
+                .description(
+                    "**Thank you for using Runback. \
+                        Below are a list of commands to assist you during your matches.**",
+                )
+                .field(
+                    EmbedFieldBuilder::new(
+                        "/matchmaking report",
+                        "Report the score for your match",
+                    )
+                    .build(),
+                )
+                .field(
+                    EmbedFieldBuilder::new(
+                        "/matchmaking done",
+                        "Finish matchmaking and finalize results",
+                    )
+                    .build(),
+                )
+                .field(
+                    EmbedFieldBuilder::new(
+                        "/matchmaking settings",
+                        "Set the settings of the lobby.",
+                    )
+                    .build(),
+                )
+                .validate()?
+                .build()])?
+            .await?;
+
+        Ok(())
+    }
+
+    async fn start_matchmaking_thread(
         &self,
         guild: Id<GuildMarker>,
         message: Id<MessageMarker>,
@@ -836,144 +1855,1425 @@ impl MatchmakingCommandHandler {
     ) -> anyhow::Result<Channel> {
         let settings = self.utils.get_guild_settings(guild).await?;
 
-        if let Some(channel) = settings.channel_id {
-            let channel = channel.into_id();
+        if let Some(channel) = settings.channel_id {
+            let channel = channel.into_id();
+
+            // Private threads can't be started from a message. Only the members that are
+            // added to them can see them.
+            let thread = if settings.threads_are_private {
+                self.utils
+                    .http_client
+                    .create_thread(channel, name.as_str(), ChannelType::PrivateThread)?
+                    .invitable(false)
+                    .auto_archive_duration(AutoArchiveDuration::Day)
+                    .await?
+                    .model()
+                    .await?
+            } else {
+                self.utils
+                    .http_client
+                    .create_thread_from_message(channel, message, name.as_str())?
+                    // .invitable(true)
+                    // archive in 3 hours
+                    .auto_archive_duration(AutoArchiveDuration::Day)
+                    .await?
+                    .model()
+                    .await?
+            };
+
+            return Ok(thread);
+        }
+
+        Err(anyhow!(
+            "The server has not enabled a default matchmaking channel"
+        ))
+    }
+
+    async fn add_users_to_thread(
+        &self,
+        thread_id: Id<ChannelMarker>,
+        users: impl IntoIterator<Item = &Id<UserMarker>>,
+    ) -> anyhow::Result<()> {
+        self.utils.http_client.join_thread(thread_id).await?;
+
+        for user in users {
+            self.utils
+                .http_client
+                .add_thread_member(thread_id, *user)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl MatchmakingCommandHandler {
+    async fn dm_users_upon_cancellation(
+        &self,
+        invitation: &matchmaking_invitation::Model,
+        user: &User,
+        guild: &Guild,
+    ) -> anyhow::Result<()> {
+        let user_model = self.utils.find_or_create_user(user.id).await?;
+
+        if user_model.user_id != invitation.invited_by {
+            let author = Users::find_by_id(invitation.invited_by)
+                .one(self.utils.db_ref())
+                .await?
+                .ok_or_else(|| anyhow!("no user found with that id"))?;
+            let res = self
+                .dm_invited(
+                    author
+                        .discord_user
+                        .ok_or_else(|| anyhow!("user does not have a valid discord user"))?
+                        .into_id(),
+                    user,
+                    guild,
+                    invitation,
+                )
+                .await;
+        } else {
+            let user_model = Users::find_by_id(invitation.extended_to)
+                .one(self.utils.db_ref())
+                .await?
+                .ok_or_else(|| anyhow!("no user found with that id"))?;
+            self.dm_invited(
+                user_model
+                    .discord_user
+                    .ok_or_else(|| anyhow!("user does not have a valid discord user"))?
+                    .into_id(),
+                user,
+                guild,
+                invitation,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn dm_invited(
+        &self,
+        user: Id<UserMarker>,
+        // The person that cancelled the invitation
+        canceller: &User,
+        guild: &Guild,
+        _invitation: &matchmaking_invitation::Model,
+    ) -> anyhow::Result<Message> {
+        // TODO: Cache this
+        let dm = self
+            .utils
+            .http_client
+            .create_private_channel(user)
+            .await?
+            .model()
+            .await?;
+
+        debug_assert_eq!(
+            dm.kind,
+            ChannelType::Private,
+            "DM channel created by Discord was not private"
+        );
+
+        let msg = self
+            .utils
+            .http_client
+            .create_message(dm.id)
+            .content(
+                format!(
+                    "\"{}@{}\" cancelled your matchmaking request in \"{}\"",
+                    canceller.name, canceller.discriminator, guild.name
+                )
+                .as_str(),
+            )?
+            .await?
+            .model()
+            .await?;
+
+        Ok(msg)
+    }
+}
+
+pub struct BackgroundLoop {
+    utils: Arc<CommonUtilities>,
+    /// The threads of open cross-server lobbies, so that other messages are ignored without
+    /// a database query.
+    relayed_threads: DashSet<Id<ChannelMarker>>,
+    /// Syncs bridged tournaments and checks called matches. Set once the tournament handler
+    /// is built, since that handler needs this loop itself.
+    tournaments: OnceLock<TournamentCommandHandler>,
+}
+
+impl BackgroundLoop {
+    fn new(utils: Arc<CommonUtilities>) -> Self {
+        Self {
+            utils: utils.clone(),
+            relayed_threads: DashSet::new(),
+            tournaments: OnceLock::new(),
+        }
+    }
+
+    /// Runs the tournament handler's periodic jobs on every update.
+    pub fn set_tournaments(&self, tournaments: TournamentCommandHandler) {
+        if self.tournaments.set(tournaments).is_err() {
+            warn!("the tournament handler was already set");
+        }
+    }
+
+    /// Posts a match request with Accept and Deny buttons in `channel`, and records the
+    /// invitation that the buttons act on.
+    pub async fn send_invitation(
+        &self,
+        channel: Id<ChannelMarker>,
+        author: Id<UserMarker>,
+        invited: Id<UserMarker>,
+        game: Option<Uuid>,
+    ) -> anyhow::Result<matchmaking_invitation::Model> {
+        let msg = self
+            .utils
+            .http_client
+            .create_message(channel)
+            .embeds(&[EmbedBuilder::new()
+                .title("New matchmaking request")
+                .description(format!(
+                    "<@{author}> has invited you to a match, <@{invited}>"
+                ))
+                .validate()?
+                .build()])?
+            .components(&[Component::ActionRow(ActionRow {
+                components: vec![
+                    Component::Button(Button {
+                        custom_id: Some("matchmaking:accept".to_string()),
+                        disabled: false,
+                        emoji: None,
+                        label: Some("Accept".to_string()),
+                        style: ButtonStyle::Primary,
+                        url: None,
+                    }),
+                    Component::Button(Button {
+                        custom_id: Some("matchmaking:deny".to_string()),
+                        disabled: false,
+                        emoji: None,
+                        label: Some("Deny".to_string()),
+                        style: ButtonStyle::Danger,
+                        url: None,
+                    }),
+                ],
+            })])?
+            .allowed_mentions(Some(
+                &AllowedMentionsBuilder::new()
+                    .user_ids([author, invited])
+                    .build(),
+            ))
+            .await?
+            .model()
+            .await?;
+
+        let author = self.utils.find_or_create_user(author).await?;
+        let invited = self.utils.find_or_create_user(invited).await?;
+
+        let invitation = matchmaking_invitation::Model {
+            id: Uuid::new_v4(),
+            lobby: None,
+            extended_to: invited.user_id,
+            invited_by: author.user_id,
+            game,
+            description: None,
+            message_id: Some(msg.id.into()),
+            expires_at: Utc::now() + chrono::Duration::minutes(30),
+            channel_id: channel.into(),
+        };
+
+        debug!(invitation = ?format!("{:?}", invitation));
+
+        let invitation = MatchmakingInvitation::insert(invitation.into_active_model())
+            .exec_with_returning(self.utils.db_ref())
+            .await?;
+
+        Ok(invitation)
+    }
+
+    /// Lets the players who subscribed to `game` know that `player` is looking to play it.
+    /// `activity` finishes the sentence, like "is waiting in the queue". Each subscriber is
+    /// alerted at most once every [`ALERT_COOLDOWN_MINUTES`] for a game.
+    pub async fn alert_subscribers(
+        &self,
+        guild_id: Id<GuildMarker>,
+        game: &game::Model,
+        player: Uuid,
+        activity: &str,
+    ) -> anyhow::Result<()> {
+        let now = Utc::now();
+        let subscriptions = GameSubscription::find()
+            .filter(game_subscription::Column::GuildId.eq(IdWrapper::from(guild_id)))
+            .filter(game_subscription::Column::Game.eq(game.id))
+            .filter(game_subscription::Column::Player.ne(player))
+            .filter(
+                Condition::any()
+                    .add(game_subscription::Column::LastAlertedAt.is_null())
+                    .add(
+                        game_subscription::Column::LastAlertedAt
+                            .lte(now - chrono::Duration::minutes(ALERT_COOLDOWN_MINUTES)),
+                    ),
+            )
+            .all(self.utils.db_ref())
+            .await?;
+
+        // Subscribers only hear about games during their availability windows.
+        let windows = AvailabilityWindow::find()
+            .filter(
+                availability_window::Column::Player.is_in(subscriptions.iter().map(|s| s.player)),
+            )
+            .all(self.utils.db_ref())
+            .await?;
+        let subscriptions = subscriptions
+            .into_iter()
+            .filter(|s| {
+                let windows = windows
+                    .iter()
+                    .filter(|w| w.player == s.player)
+                    .filter_map(availability_window)
+                    .collect::<Vec<_>>();
+                availability::is_available(&windows, now)
+            })
+            .collect::<Vec<_>>();
+
+        if subscriptions.is_empty() {
+            return Ok(());
+        }
+
+        // The subscribers are marked as alerted first, so that an alert that fails isn't
+        // sent again to the subscribers who already got it.
+        GameSubscription::update_many()
+            .set(game_subscription::ActiveModel {
+                last_alerted_at: Set(Some(now)),
+                ..Default::default()
+            })
+            .filter(game_subscription::Column::Id.is_in(subscriptions.iter().map(|s| s.id)))
+            .exec(self.utils.db_ref())
+            .await?;
+
+        let player = self.utils.discord_user_id(player).await?;
+        let settings = self.utils.get_guild_settings(guild_id).await?;
+        let channel = settings.channel_id.clone();
+        let content = format!("<@{}> {} for **{}**", player, activity, game.name);
+
+        // Pinged subscribers share as few messages as it takes, so a busy game doesn't flood
+        // the channel.
+        let mut pinged = Vec::new();
+        for subscription in &subscriptions {
+            let subscriber = self.utils.discord_user_id(subscription.player).await?;
+            match subscription.alert {
+                SubscriptionAlert::Dm => {
+                    let where_to = channel
+                        .as_ref()
+                        .map_or_else(String::new, |c| format!(" in <#{c}>"));
+
+                    // Members can turn off DMs, which shouldn't stop the others from hearing.
+                    if let Err(e) = self
+                        .send_dm(subscriber, &format!("{content}{where_to}."))
+                        .await
+                    {
+                        warn!(error = ?e, user = ?subscriber, "could not DM a subscriber");
+                    }
+                }
+                SubscriptionAlert::Ping => pinged.push(subscriber),
+            }
+        }
+
+        let Some(channel) = channel.filter(|_| !pinged.is_empty()) else {
+            return Ok(());
+        };
+        let channel = channel.into_id();
+
+        // With an alert role, one ping reaches every subscriber who wants pings.
+        if let Some(role) = settings.alert_role(game.id) {
+            self.utils
+                .http_client
+                .create_message(channel)
+                .content(&format!("<@&{role}> {content}."))?
+                .allowed_mentions(Some(
+                    &AllowedMentionsBuilder::new().role_ids([role]).build(),
+                ))
+                .await?;
+
+            return Ok(());
+        }
+
+        for (users, message) in ping_messages(&pinged, &format!(" {content}.")) {
+            if let Err(e) = self
+                .utils
+                .http_client
+                .create_message(channel)
+                .content(&message)?
+                .allowed_mentions(Some(&AllowedMentionsBuilder::new().user_ids(users).build()))
+                .await
+            {
+                warn!(error = ?e, "could not ping a game's subscribers");
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn send_dm(&self, user: Id<UserMarker>, content: &str) -> anyhow::Result<()> {
+        let dm = self
+            .utils
+            .http_client
+            .create_private_channel(user)
+            .await?
+            .model()
+            .await?;
+
+        self.utils
+            .http_client
+            .create_message(dm.id)
+            .content(content)?
+            .await?;
+
+        Ok(())
+    }
+
+    /// Creates a lobby thread from `message` for both players and records the lobby.
+    /// The `owner` is the player that started the match.
+    pub async fn open_lobby(
+        &self,
+        guild_id: Id<GuildMarker>,
+        message: Id<MessageMarker>,
+        owner: Id<UserMarker>,
+        opponent: Id<UserMarker>,
+        game: Option<Uuid>,
+    ) -> anyhow::Result<matchmaking_lobbies::Model> {
+        let owner_data: Member = self
+            .utils
+            .http_client
+            .guild_member(guild_id, owner)
+            .await?
+            .model()
+            .await?;
+
+        let opponent_data: Member = self
+            .utils
+            .http_client
+            .guild_member(guild_id, opponent)
+            .await?
+            .model()
+            .await?;
+
+        let thread = self
+            .start_matchmaking_thread(
+                guild_id,
+                message,
+                format!(
+                    "{} vs {}",
+                    owner_data.nick.unwrap_or(owner_data.user.name),
+                    opponent_data.nick.unwrap_or(opponent_data.user.name)
+                ),
+            )
+            .await?;
+
+        let users = vec![owner, opponent];
+        let res = self.add_users_to_thread(thread.id, &users).await;
+
+        if let Err(e) = res {
+            // Close the thread and send an error.
+
+            self.utils.http_client.delete_channel(thread.id).await?;
+
+            return Err(e);
+        }
+
+        self.send_thread_opening_message(&users, thread.id).await?;
+
+        for user in users {
+            // Create a discord user, in case they don't exist.
+            // TODO: Do this in bulk
+            self.utils.find_or_create_user(user).await?;
+        }
+
+        let owner = self.utils.find_or_create_user(owner).await?;
+
+        self.insert_lobby(thread.id, owner.user_id, game).await
+    }
+
+    /// Records an open lobby in `thread`.
+    async fn insert_lobby(
+        &self,
+        thread: Id<ChannelMarker>,
+        owner: Uuid,
+        game: Option<Uuid>,
+    ) -> anyhow::Result<matchmaking_lobbies::Model> {
+        let started_at = Utc::now();
+
+        let lobby = matchmaking_lobbies::Model {
+            id: Uuid::new_v4(),
+            started_at,
+            timeout_after: started_at + chrono::Duration::hours(3),
+            channel_id: thread.into(),
+            description: None,
+            owner,
+            privacy: LobbyPrivacy::Open,
+            game,
+            game_other: None,
+            ended_at: None,
+            timeout_warning_message: None,
+        };
+
+        let lobby = matchmaking_lobbies::Entity::insert(lobby.into_active_model())
+            .exec_with_returning(self.utils.db_ref())
+            .await?;
+
+        Ok(lobby)
+    }
+
+    /// Pairs up the players waiting in the matchmaking queue and opens a lobby for each pair.
+    #[instrument(skip_all)]
+    async fn pair_queued_players(&self) -> anyhow::Result<()> {
+        let queued = MatchmakingQueue::find().all(self.utils.db_ref()).await?;
+        let now = Utc::now();
+
+        let settings = MatchmakingSettings::find()
+            .filter(
+                matchmaking_settings::Column::GuildId
+                    .is_in(queued.iter().map(|entry| entry.guild_id.clone())),
+            )
+            .all(self.utils.db_ref())
+            .await?
+            .into_iter()
+            .map(|s| (s.guild_id.into_id().get(), s))
+            .collect::<HashMap<_, _>>();
+
+        // Players still playing in a lobby stay queued until it closes.
+        let busy = MatchmakingPlayerLobby::find()
+            .inner_join(MatchmakingLobbies)
+            .filter(matchmaking_lobbies::Column::EndedAt.is_null())
+            .filter(
+                matchmaking_player_lobby::Column::Player
+                    .is_in(queued.iter().map(|entry| entry.player)),
+            )
+            .all(self.utils.db_ref())
+            .await?
+            .into_iter()
+            .map(|p| p.player)
+            .collect::<HashSet<_>>();
+
+        // Players outside their availability windows aren't paired, so they aren't pinged
+        // about a match they didn't want to hear about. They stay queued until a window opens
+        // or their entry times out.
+        let windows = AvailabilityWindow::find()
+            .filter(
+                availability_window::Column::Player.is_in(queued.iter().map(|entry| entry.player)),
+            )
+            .all(self.utils.db_ref())
+            .await?;
+        let unavailable = queued
+            .iter()
+            .map(|entry| entry.player)
+            .filter(|player| {
+                let windows = windows
+                    .iter()
+                    .filter(|w| w.player == *player)
+                    .filter_map(availability_window)
+                    .collect::<Vec<_>>();
+                !availability::is_available(&windows, now)
+            })
+            .collect::<HashSet<_>>();
+
+        // Players are paired with others in the same guild, waiting for the same game. Guilds
+        // that share a game's queue put their players into one pool for that game, which has
+        // no guild.
+        let mut pools = HashMap::<_, Vec<QueueEntry<usize>>>::new();
+        for (i, entry) in queued.iter().enumerate() {
+            if busy.contains(&entry.player) || unavailable.contains(&entry.player) {
+                continue;
+            }
+
+            let guild = entry.guild_id.into_id().get();
+            let shared = entry.game.is_some_and(|game| {
+                settings
+                    .get(&guild)
+                    .is_some_and(|s| s.cross_server_games().contains(&game))
+            });
+
+            pools
+                .entry(((!shared).then_some(guild), entry.game))
+                .or_default()
+                .push(QueueEntry {
+                    key: i,
+                    rating: entry.rating,
+                    waited: now - entry.joined_at,
+                });
+        }
+
+        // In a shared pool, either guild can refuse to play the other, and a player queueing
+        // in two guilds can't be paired with themselves.
+        let blocks = |a: u64, b: u64| {
+            settings
+                .get(&a)
+                .is_some_and(|s| s.blocked_guilds().contains(&b))
+        };
+        let allowed = |a: &usize, b: &usize| {
+            let (a, b) = (&queued[*a], &queued[*b]);
+            let (guild_a, guild_b) = (a.guild_id.into_id().get(), b.guild_id.into_id().get());
+            a.player != b.player && !blocks(guild_a, guild_b) && !blocks(guild_b, guild_a)
+        };
+
+        let window = SearchWindow::default();
+        for pool in pools.values() {
+            for (a, b) in find_pairs_where(pool, &window, allowed) {
+                if let Err(e) = self.open_queued_match(&queued[a], &queued[b]).await {
+                    error!(error = ?e, "could not open a lobby for queued players");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn open_queued_match(
+        &self,
+        first: &matchmaking_queue::Model,
+        second: &matchmaking_queue::Model,
+    ) -> anyhow::Result<()> {
+        if first.guild_id != second.guild_id {
+            return self.open_cross_server_match(first, second).await;
+        }
+
+        let guild_id = first.guild_id.into_id();
+        let settings = self.utils.get_guild_settings(guild_id).await?;
+        let channel = settings
+            .channel_id
+            .ok_or_else(|| anyhow!("The server has not enabled a default matchmaking channel"))?
+            .into_id();
+
+        let players = [
+            self.utils.discord_user_id(first.player).await?,
+            self.utils.discord_user_id(second.player).await?,
+        ];
+
+        let msg = self
+            .utils
+            .http_client
+            .create_message(channel)
+            .content(format!("<@{}> <@{}>", players[0], players[1]).as_str())?
+            .embeds(&[EmbedBuilder::new()
+                .title("Match found")
+                .description(format!(
+                    "<@{}> ({}) vs <@{}> ({})",
+                    players[0], first.rating, players[1], second.rating
+                ))
+                .validate()?
+                .build()])?
+            .allowed_mentions(Some(
+                &AllowedMentionsBuilder::new()
+                    .user_ids(players.iter().copied())
+                    .build(),
+            ))
+            .await?
+            .model()
+            .await?;
+
+        self.open_lobby(guild_id, msg.id, players[0], players[1], first.game)
+            .await?;
+
+        // The players only leave the queue once their lobby is open, so they're paired again
+        // if it couldn't be.
+        MatchmakingQueue::delete_many()
+            .filter(matchmaking_queue::Column::Id.is_in([first.id, second.id]))
+            .exec(self.utils.db_ref())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Opens a lobby thread for each player in their own guild, and links the two lobbies
+    /// into one match.
+    async fn open_cross_server_match(
+        &self,
+        first: &matchmaking_queue::Model,
+        second: &matchmaking_queue::Model,
+    ) -> anyhow::Result<()> {
+        let entries = [first, second];
+
+        let mut players = Vec::new();
+        let mut guilds = Vec::new();
+        let mut names = Vec::new();
+        for entry in entries {
+            let guild_id = entry.guild_id.into_id();
+            let player = self.utils.discord_user_id(entry.player).await?;
+            let member: Member = self
+                .utils
+                .http_client
+                .guild_member(guild_id, player)
+                .await?
+                .model()
+                .await?;
+
+            players.push(player);
+            guilds.push(
+                self.utils
+                    .http_client
+                    .guild(guild_id)
+                    .await?
+                    .model()
+                    .await?,
+            );
+            names.push(member.nick.unwrap_or(member.user.name));
+        }
+
+        let mut lobbies = Vec::new();
+        for (side, entry) in entries.iter().enumerate() {
+            let other = 1 - side;
+            let lobby = self
+                .open_mirrored_lobby(
+                    &guilds[side],
+                    players[side],
+                    format!("{} vs {}", names[side], names[other]),
+                    format!(
+                        "<@{}> ({}) vs **{}** ({}) from **{}**",
+                        players[side],
+                        entry.rating,
+                        names[other],
+                        entries[other].rating,
+                        guilds[other].name
+                    ),
+                    &guilds[other].name,
+                    entry,
+                )
+                .await;
+
+            match lobby {
+                Ok(lobby) => lobbies.push(lobby),
+                Err(e) => {
+                    // A match with only one side can't be played.
+                    for lobby in &lobbies {
+                        self.utils
+                            .http_client
+                            .delete_channel(lobby.channel_id.into_id())
+                            .await?;
+                        self.close_lobby(lobby).await?;
+                    }
+
+                    return Err(e);
+                }
+            }
+        }
+
+        let cross_server_match = cross_server_match::Model {
+            id: Uuid::new_v4(),
+            game: first.game,
+            first_lobby: lobbies[0].id,
+            second_lobby: lobbies[1].id,
+            first_player: first.player,
+            second_player: second.player,
+            reported_by: None,
+            first_player_wins: None,
+            second_player_wins: None,
+            started_at: Utc::now(),
+            completed_at: None,
+        };
+
+        CrossServerMatch::insert(cross_server_match.into_active_model())
+            .exec(self.utils.db_ref())
+            .await?;
+
+        for lobby in &lobbies {
+            self.relayed_threads.insert(lobby.channel_id.into_id());
+        }
+
+        MatchmakingQueue::delete_many()
+            .filter(matchmaking_queue::Column::Id.is_in([first.id, second.id]))
+            .exec(self.utils.db_ref())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Opens one side of a cross-server match: a lobby thread in the player's own guild.
+    async fn open_mirrored_lobby(
+        &self,
+        guild: &Guild,
+        player: Id<UserMarker>,
+        thread_name: String,
+        description: String,
+        opponent_guild: &str,
+        entry: &matchmaking_queue::Model,
+    ) -> anyhow::Result<matchmaking_lobbies::Model> {
+        let settings = self.utils.get_guild_settings(guild.id).await?;
+        let channel = settings
+            .channel_id
+            .ok_or_else(|| anyhow!("The server has not enabled a default matchmaking channel"))?
+            .into_id();
+
+        let msg = self
+            .utils
+            .http_client
+            .create_message(channel)
+            .content(format!("<@{player}>").as_str())?
+            .embeds(&[EmbedBuilder::new()
+                .title("Cross-server match found")
+                .description(description)
+                .validate()?
+                .build()])?
+            .allowed_mentions(Some(
+                &AllowedMentionsBuilder::new().user_ids([player]).build(),
+            ))
+            .await?
+            .model()
+            .await?;
+
+        let thread = self
+            .start_matchmaking_thread(guild.id, msg.id, thread_name)
+            .await?;
+
+        if let Err(e) = self.add_users_to_thread(thread.id, &[player]).await {
+            self.utils.http_client.delete_channel(thread.id).await?;
+            return Err(e);
+        }
+
+        self.send_thread_opening_message(&[player], thread.id)
+            .await?;
+
+        self.utils
+            .http_client
+            .create_message(thread.id)
+            .content(
+                format!(
+                    "Your opponent plays from **{opponent_guild}**. Messages sent here are \
+                    relayed to their thread, and theirs to this one."
+                )
+                .as_str(),
+            )?
+            .await?;
+
+        self.insert_lobby(thread.id, entry.player, entry.game).await
+    }
+
+    /// The cross-server match that `lobby` is one side of, if any.
+    pub async fn cross_server_match(
+        &self,
+        lobby: Uuid,
+    ) -> anyhow::Result<Option<cross_server_match::Model>> {
+        Ok(CrossServerMatch::find()
+            .filter(
+                Condition::any()
+                    .add(cross_server_match::Column::FirstLobby.eq(lobby))
+                    .add(cross_server_match::Column::SecondLobby.eq(lobby)),
+            )
+            .one(self.utils.db_ref())
+            .await?)
+    }
+
+    /// Posts `content` in the open lobby threads on both sides of the match.
+    pub async fn announce_to_match(
+        &self,
+        cross_server_match: &cross_server_match::Model,
+        content: &str,
+    ) -> anyhow::Result<()> {
+        let lobbies = MatchmakingLobbies::find()
+            .filter(matchmaking_lobbies::Column::Id.is_in([
+                cross_server_match.first_lobby,
+                cross_server_match.second_lobby,
+            ]))
+            .filter(matchmaking_lobbies::Column::EndedAt.is_null())
+            .all(self.utils.db_ref())
+            .await?;
+
+        for lobby in lobbies {
+            self.utils
+                .http_client
+                .create_message(lobby.channel_id.into_id())
+                .content(content)?
+                .allowed_mentions(Some(&AllowedMentionsBuilder::new().build()))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Closes the other side of a cross-server match once `lobby` is done.
+    pub async fn close_mirrored_lobby(&self, lobby: Uuid) -> anyhow::Result<()> {
+        let Some(cross_server_match) = self.cross_server_match(lobby).await? else {
+            return Ok(());
+        };
+
+        let mirror = MatchmakingLobbies::find_by_id(cross_server_match.mirror_of(lobby))
+            .filter(matchmaking_lobbies::Column::EndedAt.is_null())
+            .one(self.utils.db_ref())
+            .await?;
+
+        if let Some(mirror) = mirror {
+            let thread = mirror.channel_id.into_id();
+            self.utils
+                .http_client
+                .create_message(thread)
+                .content("Your opponent finished the match. Thanks for using runback!")?
+                .await?;
+            self.utils
+                .http_client
+                .update_thread(thread)
+                .archived(true)
+                .locked(true)
+                .await?;
+            self.close_lobby(&mirror).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Relays messages sent in one side of a cross-server match to the other side's thread.
+    #[instrument(skip_all)]
+    async fn on_message_create(&self, message: Box<MessageCreate>) -> anyhow::Result<()> {
+        if message.author.bot || !self.relayed_threads.contains(&message.channel_id) {
+            return Ok(());
+        }
+
+        let lobby = MatchmakingLobbies::find()
+            .filter(matchmaking_lobbies::Column::ChannelId.eq(IdWrapper::from(message.channel_id)))
+            .filter(matchmaking_lobbies::Column::EndedAt.is_null())
+            .one(self.utils.db_ref())
+            .await?;
+        let Some(lobby) = lobby else {
+            return Ok(());
+        };
+        let Some(cross_server_match) = self.cross_server_match(lobby.id).await? else {
+            return Ok(());
+        };
+
+        let mirror = MatchmakingLobbies::find_by_id(cross_server_match.mirror_of(lobby.id))
+            .filter(matchmaking_lobbies::Column::EndedAt.is_null())
+            .one(self.utils.db_ref())
+            .await?;
+        let Some(mirror) = mirror else {
+            return Ok(());
+        };
+
+        let mut content = format!("**{}**: {}", message.author.name, message.content);
+        for attachment in &message.attachments {
+            content.push('\n');
+            content.push_str(&attachment.url);
+        }
+        // Messages can't be longer than 2000 characters.
+        let content = content.chars().take(2000).collect::<String>();
+
+        // Nobody in the other guild gets pinged by a relayed message.
+        self.utils
+            .http_client
+            .create_message(mirror.channel_id.into_id())
+            .content(&content)?
+            .allowed_mentions(Some(&AllowedMentionsBuilder::new().build()))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Rebuilds the set of relayed threads from the open cross-server lobbies, which picks up
+    /// the lobbies that were open before a restart.
+    async fn refresh_relayed_threads(&self) -> anyhow::Result<()> {
+        let lobbies = MatchmakingLobbies::find()
+            .filter(matchmaking_lobbies::Column::EndedAt.is_null())
+            .all(self.utils.db_ref())
+            .await?;
+        let ids = lobbies.iter().map(|l| l.id).collect::<Vec<_>>();
+
+        let matches = CrossServerMatch::find()
+            .filter(
+                Condition::any()
+                    .add(cross_server_match::Column::FirstLobby.is_in(ids.clone()))
+                    .add(cross_server_match::Column::SecondLobby.is_in(ids)),
+            )
+            .all(self.utils.db_ref())
+            .await?;
+        let mirrored = matches
+            .iter()
+            .flat_map(|m| [m.first_lobby, m.second_lobby])
+            .collect::<HashSet<_>>();
+
+        self.relayed_threads.clear();
+        for lobby in lobbies.iter().filter(|l| mirrored.contains(&l.id)) {
+            self.relayed_threads.insert(lobby.channel_id.into_id());
+        }
+
+        Ok(())
+    }
+
+    /// Takes the players who have waited too long out of the queue, and lets them know.
+    /// Everyone gets the full width of the search window before they're taken out.
+    #[instrument(skip_all)]
+    async fn expire_queue_entries(&self) -> anyhow::Result<()> {
+        let timeout =
+            SearchWindow::default().time_to_max() + chrono::Duration::minutes(QUEUE_GRACE_MINUTES);
+        let expired = MatchmakingQueue::find()
+            .filter(matchmaking_queue::Column::JoinedAt.lte(Utc::now() - timeout))
+            .all(self.utils.db_ref())
+            .await?;
+
+        for entry in &expired {
+            MatchmakingQueue::delete_by_id(entry.id)
+                .exec(self.utils.db_ref())
+                .await?;
+
+            let res = async {
+                let player = self.utils.discord_user_id(entry.player).await?;
+                self.send_dm(
+                    player,
+                    &format!(
+                        "You were taken out of the matchmaking queue after waiting for {} minutes without a match. Use `/matchmaking queue` to join it again.",
+                        timeout.num_minutes()
+                    ),
+                )
+                .await
+            }
+            .await;
+
+            if let Err(e) = res {
+                warn!(error = ?e, entry = ?entry.id, "could not tell a player that they left the queue");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reminds players of league matches that are due soon, and resolves the matches that
+    /// weren't played before their deadline.
+    #[instrument(skip_all)]
+    async fn update_league_matches(&self) -> anyhow::Result<()> {
+        let now = Utc::now();
+
+        let due_soon = LeagueMatch::find()
+            .filter(league_match::Column::Status.eq(LeagueMatchStatus::Scheduled))
+            .filter(league_match::Column::ReminderSent.eq(false))
+            .filter(league_match::Column::Deadline.gt(now))
+            .filter(league_match::Column::Deadline.lte(now + chrono::Duration::hours(24)))
+            .all(self.utils.db_ref())
+            .await?;
+
+        for m in &due_soon {
+            if let Err(e) = self.remind_league_match(m).await {
+                error!(error = ?e, league_match = ?m.id, "could not send league match reminder");
+            }
+        }
+
+        let overdue = LeagueMatch::find()
+            .filter(league_match::Column::Status.eq(LeagueMatchStatus::Scheduled))
+            .filter(league_match::Column::Deadline.lte(now))
+            .all(self.utils.db_ref())
+            .await?;
+
+        for m in &overdue {
+            if let Err(e) = self.resolve_unplayed_league_match(m).await {
+                error!(error = ?e, league_match = ?m.id, "could not resolve unplayed league match");
+            }
+        }
+
+        let teams_due_soon = LeagueTeamMatch::find()
+            .filter(league_team_match::Column::Status.eq(LeagueMatchStatus::Scheduled))
+            .filter(league_team_match::Column::ReminderSent.eq(false))
+            .filter(league_team_match::Column::Deadline.gt(now))
+            .filter(league_team_match::Column::Deadline.lte(now + chrono::Duration::hours(24)))
+            .all(self.utils.db_ref())
+            .await?;
+
+        for m in &teams_due_soon {
+            if let Err(e) = self.remind_league_team_match(m).await {
+                error!(error = ?e, league_team_match = ?m.id, "could not send team match reminder");
+            }
+        }
+
+        let teams_overdue = LeagueTeamMatch::find()
+            .filter(league_team_match::Column::Status.eq(LeagueMatchStatus::Scheduled))
+            .filter(league_team_match::Column::Deadline.lte(now))
+            .all(self.utils.db_ref())
+            .await?;
+
+        for m in &teams_overdue {
+            if let Err(e) = self.resolve_unplayed_league_team_match(m).await {
+                error!(error = ?e, league_team_match = ?m.id, "could not resolve unplayed team match");
+            }
+        }
+
+        Ok(())
+    }
 
-            let thread = self
-                .utils
-                .http_client
-                .create_thread_from_message(channel, message, name.as_str())?
-                // .invitable(true)
-                // archive in 3 hours
-                .auto_archive_duration(AutoArchiveDuration::Day)
-                .await?
-                .model()
-                .await?;
+    async fn remind_league_match(&self, m: &league_match::Model) -> anyhow::Result<()> {
+        let league = League::find_by_id(m.league)
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("league match does not belong to a league"))?;
+
+        let players = [
+            self.utils.discord_user_id(m.player_one).await?,
+            self.utils.discord_user_id(m.player_two).await?,
+        ];
+
+        let content = format!(
+            "<@{}> <@{}>, your round {} match in **{}** is due <t:{}:R>.",
+            players[0],
+            players[1],
+            m.round,
+            league.name,
+            m.deadline.timestamp()
+        );
 
-            return Ok(thread);
+        // The reminder is only tried once, so a player who can't be reached isn't reminded
+        // again every minute.
+        if let Err(e) = self.send_league_reminder(&league, players, &content).await {
+            warn!(error = ?e, league_match = ?m.id, "could not send a league match reminder");
         }
 
-        Err(anyhow!(
-            "The server has not enabled a default matchmaking channel"
-        ))
+        LeagueMatch::update(league_match::ActiveModel {
+            id: Set(m.id),
+            reminder_sent: Set(true),
+            ..Default::default()
+        })
+        .exec(self.utils.db_ref())
+        .await?;
+
+        Ok(())
     }
 
-    async fn add_users_to_thread(
+    /// Team match reminders go to the captains, since they submit the lineups.
+    async fn remind_league_team_match(&self, m: &league_team_match::Model) -> anyhow::Result<()> {
+        let league = League::find_by_id(m.league)
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("team match does not belong to a league"))?;
+        let (team_one, team_two) = self.league_match_teams(m).await?;
+
+        let captains = [
+            self.utils.discord_user_id(team_one.captain).await?,
+            self.utils.discord_user_id(team_two.captain).await?,
+        ];
+
+        let content = format!(
+            "<@{}> <@{}>, the round {} match between **{}** and **{}** in **{}** is due <t:{}:R>.",
+            captains[0],
+            captains[1],
+            m.round,
+            team_one.name,
+            team_two.name,
+            league.name,
+            m.deadline.timestamp()
+        );
+
+        if let Err(e) = self.send_league_reminder(&league, captains, &content).await {
+            warn!(error = ?e, team_match = ?m.id, "could not send a team match reminder");
+        }
+
+        LeagueTeamMatch::update(league_team_match::ActiveModel {
+            id: Set(m.id),
+            reminder_sent: Set(true),
+            ..Default::default()
+        })
+        .exec(self.utils.db_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn send_league_reminder(
         &self,
-        thread_id: Id<ChannelMarker>,
-        users: impl IntoIterator<Item = &Id<UserMarker>>,
+        league: &league::Model,
+        players: [Id<UserMarker>; 2],
+        content: &str,
     ) -> anyhow::Result<()> {
-        self.utils.http_client.join_thread(thread_id).await?;
+        if let Some(channel) = &league.matches_channel {
+            self.utils
+                .http_client
+                .create_message(channel.into_id())
+                .content(content)?
+                .allowed_mentions(Some(
+                    &AllowedMentionsBuilder::new().user_ids(players).build(),
+                ))
+                .await?;
+        } else {
+            // Without a matches channel, remind both players in their DMs instead. One player
+            // having their DMs closed doesn't stop the other from being reminded.
+            for player in players {
+                if let Err(e) = self.send_dm(player, content).await {
+                    warn!(error = ?e, player = ?player, "could not DM a league reminder");
+                }
+            }
+        }
 
-        for user in users {
+        Ok(())
+    }
+
+    async fn resolve_unplayed_league_match(&self, m: &league_match::Model) -> anyhow::Result<()> {
+        let league = League::find_by_id(m.league)
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("league match does not belong to a league"))?;
+
+        // A match with an unconfirmed report was played, so an organizer settles the score.
+        let (status, outcome) = if league.forfeit_unplayed && m.reported_by.is_none() {
+            (LeagueMatchStatus::Forfeited, "has been forfeited")
+        } else {
+            (
+                LeagueMatchStatus::AdminReview,
+                "needs to be reviewed by an organizer",
+            )
+        };
+
+        LeagueMatch::update(league_match::ActiveModel {
+            id: Set(m.id),
+            status: Set(status),
+            ..Default::default()
+        })
+        .exec(self.utils.db_ref())
+        .await?;
+
+        if let Some(channel) = league.reporting_channel.or(league.matches_channel) {
             self.utils
                 .http_client
-                .add_thread_member(thread_id, *user)
+                .create_message(channel.into_id())
+                .content(
+                    format!(
+                        "The round {} match between <@{}> and <@{}> in **{}** wasn't played before its deadline and {}.",
+                        m.round,
+                        self.utils.discord_user_id(m.player_one).await?,
+                        self.utils.discord_user_id(m.player_two).await?,
+                        league.name,
+                        outcome
+                    )
+                    .as_str(),
+                )?
+                .allowed_mentions(None)
                 .await?;
         }
 
         Ok(())
     }
 
-    async fn dm_users_upon_cancellation(
+    async fn resolve_unplayed_league_team_match(
         &self,
-        invitation: &matchmaking_invitation::Model,
-        user: &User,
-        guild: &Guild,
+        m: &league_team_match::Model,
     ) -> anyhow::Result<()> {
-        let user_model = self.utils.find_or_create_user(user.id).await?;
+        let league = League::find_by_id(m.league)
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("team match does not belong to a league"))?;
 
-        if user_model.user_id != invitation.invited_by {
-            let author = Users::find_by_id(invitation.invited_by)
-                .one(self.utils.db_ref())
-                .await?
-                .ok_or_else(|| anyhow!("no user found with that id"))?;
-            let res = self
-                .dm_invited(
-                    author
-                        .discord_user
-                        .ok_or_else(|| anyhow!("user does not have a valid discord user"))?
-                        .into_id(),
-                    user,
-                    guild,
-                    invitation,
-                )
-                .await;
+        let (status, outcome) = if league.forfeit_unplayed {
+            (LeagueMatchStatus::Forfeited, "has been forfeited")
         } else {
-            let user_model = Users::find_by_id(invitation.extended_to)
-                .one(self.utils.db_ref())
-                .await?
-                .ok_or_else(|| anyhow!("no user found with that id"))?;
-            self.dm_invited(
-                user_model
-                    .discord_user
-                    .ok_or_else(|| anyhow!("user does not have a valid discord user"))?
-                    .into_id(),
-                user,
-                guild,
-                invitation,
+            (
+                LeagueMatchStatus::AdminReview,
+                "needs to be reviewed by an organizer",
             )
-            .await?;
+        };
+
+        LeagueTeamMatch::update(league_team_match::ActiveModel {
+            id: Set(m.id),
+            status: Set(status),
+            ..Default::default()
+        })
+        .exec(self.utils.db_ref())
+        .await?;
+
+        if let Some(channel) = league.reporting_channel.or(league.matches_channel) {
+            let (team_one, team_two) = self.league_match_teams(m).await?;
+
+            self.utils
+                .http_client
+                .create_message(channel.into_id())
+                .content(
+                    format!(
+                        "The round {} match between **{}** and **{}** in **{}** wasn't played before its deadline and {}.",
+                        m.round, team_one.name, team_two.name, league.name, outcome
+                    )
+                    .as_str(),
+                )?
+                .await?;
         }
+
         Ok(())
     }
 
-    async fn dm_invited(
-        &self,
-        user: Id<UserMarker>,
-        // The person that cancelled the invitation
-        canceller: &User,
-        guild: &Guild,
-        _invitation: &matchmaking_invitation::Model,
-    ) -> anyhow::Result<Message> {
-        // TODO: Cache this
-        let dm = self
-            .utils
-            .http_client
-            .create_private_channel(user)
-            .await?
-            .model()
+    /// Posts the standings of every league that ended since the last update.
+    #[instrument(skip_all)]
+    async fn post_final_standings(&self) -> anyhow::Result<()> {
+        let ended = League::find()
+            .filter(league::Column::EndDate.lte(Utc::now()))
+            .filter(league::Column::StandingsPosted.eq(false))
+            .all(self.utils.db_ref())
             .await?;
 
-        debug_assert_eq!(
-            dm.kind,
-            ChannelType::Private,
-            "DM channel created by Discord was not private"
-        );
+        for league in ended {
+            // The standings are only tried once, so a league whose channel is gone isn't
+            // retried every minute.
+            if let Err(e) = self.post_league_standings(&league).await {
+                error!(error = ?e, league = ?league.id, "could not post league standings");
+            }
 
-        let msg = self
-            .utils
-            .http_client
-            .create_message(dm.id)
-            .content(
-                format!(
-                    "\"{}@{}\" cancelled your matchmaking request in \"{}\"",
-                    canceller.name, canceller.discriminator, guild.name
-                )
-                .as_str(),
-            )?
+            let res = League::update(league::ActiveModel {
+                id: Set(league.id),
+                standings_posted: Set(true),
+                ..Default::default()
+            })
+            .exec(self.utils.db_ref())
+            .await;
+            if let Err(e) = res {
+                error!(error = ?e, league = ?league.id, "could not mark league standings posted");
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn post_league_standings(&self, league: &league::Model) -> anyhow::Result<()> {
+        let Some(channel) = league
+            .reporting_channel
+            .as_ref()
+            .or(league.matches_channel.as_ref())
+        else {
+            return Ok(());
+        };
+
+        let embed = standings_embed(&self.utils, league)
             .await?
-            .model()
+            .title(format!("{} final standings", league.name))
+            .validate()?
+            .build();
+
+        self.utils
+            .http_client
+            .create_message(channel.into_id())
+            .embeds(&[embed])?
             .await?;
 
-        Ok(msg)
+        Ok(())
     }
-}
 
-struct BackgroundLoop {
-    utils: Arc<CommonUtilities>,
-}
+    /// Ladder players who don't accept or play a challenge in time forfeit their position.
+    #[instrument(skip_all)]
+    async fn forfeit_expired_challenges(&self) -> anyhow::Result<()> {
+        let expired = LadderChallenge::find()
+            .filter(ladder_challenge::Column::Status.eq(LadderChallengeStatus::Pending))
+            .filter(ladder_challenge::Column::AcceptBy.lte(Utc::now()))
+            .all(self.utils.db_ref())
+            .await?;
 
-impl BackgroundLoop {
-    fn new(utils: Arc<CommonUtilities>) -> Self {
-        Self {
-            utils: utils.clone(),
+        for challenge in &expired {
+            let invitation = MatchmakingInvitation::find_by_id(challenge.invitation)
+                .one(self.utils.db_ref())
+                .await?;
+
+            // Remove the Accept/Deny buttons from the challenge
+            if let Some(invitation) = invitation {
+                if let Some(msg_id) = invitation.message_id {
+                    let res = self
+                        .utils
+                        .http_client
+                        .update_message(invitation.channel_id.into_id(), msg_id.into_id())
+                        .components(Some(&[]))?
+                        .await;
+
+                    if let Err(e) = res {
+                        warn!(error = ?e, challenge = ?challenge.id, "could not remove challenge buttons");
+                    }
+                }
+            }
+
+            let res = resolve_ladder_challenge(
+                &self.utils,
+                challenge,
+                true,
+                LadderChallengeStatus::Forfeited,
+            )
+            .await;
+
+            if let Err(e) = res {
+                error!(error = ?e, challenge = ?challenge.id, "could not forfeit expired challenge");
+            }
         }
+
+        // Accepted challenges that weren't reported in time count as a forfeit by the
+        // defender, while an unconfirmed report stands once the deadline passes.
+        let unplayed = LadderChallenge::find()
+            .filter(ladder_challenge::Column::Status.eq(LadderChallengeStatus::Accepted))
+            .filter(ladder_challenge::Column::PlayBy.lte(Utc::now()))
+            .all(self.utils.db_ref())
+            .await?;
+
+        for challenge in &unplayed {
+            let (challenger_won, status) = match challenge.winner {
+                Some(winner) if challenge.reported_by.is_some() => (
+                    winner == challenge.challenger,
+                    LadderChallengeStatus::Completed,
+                ),
+                _ => (true, LadderChallengeStatus::Forfeited),
+            };
+
+            let res =
+                resolve_ladder_challenge(&self.utils, challenge, challenger_won, status).await;
+
+            if let Err(e) = res {
+                error!(error = ?e, challenge = ?challenge.id, "could not resolve unplayed challenge");
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn league_match_teams(
+        &self,
+        m: &league_team_match::Model,
+    ) -> anyhow::Result<(league_team::Model, league_team::Model)> {
+        let team_one = LeagueTeam::find_by_id(m.team_one)
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("team no longer exists"))?;
+        let team_two = LeagueTeam::find_by_id(m.team_two)
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("team no longer exists"))?;
+
+        Ok((team_one, team_two))
     }
 
     /// Queries and updates the sessions and invitations.
     #[instrument(skip_all)]
     async fn update(&self) -> anyhow::Result<()> {
+        // Each step runs even if the ones before it failed, so that one broken step doesn't
+        // hold up the others.
+        if let Err(e) = self.update_lobbies().await {
+            error!(error = ?e, "could not update lobbies");
+        }
+        if let Err(e) = self.refresh_relayed_threads().await {
+            error!(error = ?e, "could not refresh the relayed threads");
+        }
+        if let Err(e) = self.expire_queue_entries().await {
+            error!(error = ?e, "could not expire queue entries");
+        }
+        if let Err(e) = self.pair_queued_players().await {
+            error!(error = ?e, "could not pair queued players");
+        }
+        if let Err(e) = self.update_league_matches().await {
+            error!(error = ?e, "could not update league matches");
+        }
+        if let Err(e) = self.post_final_standings().await {
+            error!(error = ?e, "could not post final league standings");
+        }
+        if let Err(e) = self.forfeit_expired_challenges().await {
+            error!(error = ?e, "could not forfeit expired ladder challenges");
+        }
+        if let Err(e) = self.lift_expired_bans().await {
+            error!(error = ?e, "could not lift expired bans");
+        }
+        if let Some(tournaments) = self.tournaments.get() {
+            if let Err(e) = tournaments.sync_remote_tournaments().await {
+                error!(error = ?e, "could not sync bridged tournaments");
+            }
+            if let Err(e) = tournaments.check_called_matches().await {
+                error!(error = ?e, "could not check called matches for no-shows");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Times out expired lobbies, and warns the ones that are about to expire.
+    async fn update_lobbies(&self) -> anyhow::Result<()> {
         // TODO: Aggregate errors
 
         // Timeout expired sessions
@@ -1005,6 +3305,53 @@ impl BackgroundLoop {
         Ok(())
     }
 
+    /// Removes the matchmaking bans that ran out, and lets the players know that they can
+    /// play again.
+    #[instrument(skip_all)]
+    async fn lift_expired_bans(&self) -> anyhow::Result<()> {
+        let expired = MatchmakingBan::find()
+            .filter(matchmaking_ban::Column::ExpiresAt.lte(Utc::now()))
+            .all(self.utils.db_ref())
+            .await?;
+
+        if expired.is_empty() {
+            return Ok(());
+        }
+
+        MatchmakingBan::delete_many()
+            .filter(matchmaking_ban::Column::Id.is_in(expired.iter().map(|b| b.id)))
+            .exec(self.utils.db_ref())
+            .await?;
+
+        for ban in &expired {
+            if let Err(e) = self.notify_ban_lifted(ban).await {
+                warn!(error = ?e, ban = ?ban.id, "could not tell the player their ban ended");
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn notify_ban_lifted(&self, ban: &matchmaking_ban::Model) -> anyhow::Result<()> {
+        let guild = self
+            .utils
+            .http_client
+            .guild(ban.guild_id.into_id())
+            .await?
+            .model()
+            .await?;
+        let player = self.utils.discord_user_id(ban.player).await?;
+
+        self.send_dm(
+            player,
+            &format!(
+                "Your matchmaking ban in **{}** has ended. You can play matches again.",
+                guild.name
+            ),
+        )
+        .await
+    }
+
     #[instrument(skip_all)]
     async fn get_expiring_lobbies(&self) -> Result<Vec<matchmaking_lobbies::Model>, anyhow::Error> {
         let lobbies = MatchmakingLobbies::find()
@@ -1020,7 +3367,7 @@ impl BackgroundLoop {
         Ok(lobbies)
     }
 
-    async fn extend_lobby(&self, s: &matchmaking_lobbies::Model) -> anyhow::Result<()> {
+    pub async fn extend_lobby(&self, s: &matchmaking_lobbies::Model) -> anyhow::Result<()> {
         let lobby = matchmaking_lobbies::ActiveModel {
             id: Set(s.id),
             timeout_after: Set(Utc::now() + chrono::Duration::minutes(30)),
@@ -1177,7 +3524,7 @@ impl BackgroundLoop {
         Ok(())
     }
 
-    async fn close_lobby(&self, lobby: &matchmaking_lobbies::Model) -> anyhow::Result<()> {
+    pub async fn close_lobby(&self, lobby: &matchmaking_lobbies::Model) -> anyhow::Result<()> {
         let _update_res = entity::matchmaking_invitation::Entity::update_many()
             .filter(matchmaking_invitation::Column::Lobby.eq(lobby.id))
             .filter(matchmaking_invitation::Column::ExpiresAt.gt(Utc::now()))
@@ -1195,6 +3542,8 @@ impl BackgroundLoop {
         })
         .exec(self.utils.db_ref())
         .await?;
+
+        self.relayed_threads.remove(&lobby.channel_id.into_id());
         Ok(())
     }
 
@@ -1215,7 +3564,7 @@ impl BackgroundLoop {
             self.utils
                 .standby
                 .wait_for_event_stream(move |e: &Event| match e {
-                    Event::ChannelDelete(_) => true,
+                    Event::ChannelDelete(_) | Event::MessageCreate(_) => true,
                     _ => false,
                 })
         };
@@ -1235,12 +3584,20 @@ impl BackgroundLoop {
                     let end = start.elapsed();
                     debug!(end = ?end, time_ms = ?end.as_millis(), "Finished filtering lobbies");
                 }
-                Some(chan_delete) = stream.next() => {
-                    debug!(del = ?format!("{:?}", chan_delete), "Channel was deleted. Remove any lobbies attached to this channel.");
-                    if let Event::ChannelDelete(chan) = chan_delete {
-                        if let Err(e) = self.on_channel_delete(chan).await {
-                            error!(error = ?e, "encountered error when dealing with deleted channel");
+                Some(event) = stream.next() => {
+                    match event {
+                        Event::ChannelDelete(chan) => {
+                            debug!(del = ?format!("{:?}", chan), "Channel was deleted. Remove any lobbies attached to this channel.");
+                            if let Err(e) = self.on_channel_delete(chan).await {
+                                error!(error = ?e, "encountered error when dealing with deleted channel");
+                            }
+                        }
+                        Event::MessageCreate(message) => {
+                            if let Err(e) = self.on_message_create(message).await {
+                                error!(error = ?e, "could not relay a cross-server lobby message");
+                            }
                         }
+                        _ => {}
                     }
                 }
             }
@@ -1262,3 +3619,27 @@ impl BackgroundLoop {
         Ok(())
     }
 }
+
+/// Windows with a time zone that's no longer in the database are skipped.
+fn availability_window(model: &availability_window::Model) -> Option<Window> {
+    Some(Window {
+        weekday: *WEEKDAYS.get(usize::try_from(model.weekday).ok()?)?,
+        start: model.start_time,
+        end: model.end_time,
+        time_zone: availability::parse_time_zone(&model.time_zone).ok()?,
+    })
+}
+
+/// Describes the window's next occurrence in each reader's own time zone.
+fn describe_window(window: &Window, now: DateTime<Utc>) -> String {
+    window.next(now).map_or_else(
+        || "never".to_string(),
+        |(start, end)| {
+            format!(
+                "every week from <t:{}:F> to <t:{}:t>",
+                start.timestamp(),
+                end.timestamp()
+            )
+        },
+    )
+}
@@ -1,10 +1,10 @@
 pub mod admin;
 pub mod eula;
-// pub mod lfg;
-
-#[deprecated(note = "Revisiting this later")]
-pub mod lfg {}
+pub mod league;
+pub mod lfg;
 pub mod matchmaking;
+pub mod report;
+pub mod tournament;
 pub mod utils;
 
 pub use utils::CommonUtilities;
@@ -17,7 +17,7 @@ use twilight_model::{
         command::{Command, CommandType},
         interaction::{
             application_command::CommandData, message_component::MessageComponentInteractionData,
-            Interaction,
+            modal::ModalInteractionData, Interaction,
         },
     },
     channel::message::MessageFlags,
@@ -45,7 +45,7 @@ pub trait InteractionHandler {
     fn describe(&self) -> CommandGroupDescriptor;
     async fn process_command(&self, data: Box<ApplicationCommandData>) -> anyhow::Result<()>;
     async fn process_autocomplete(&self, data: Box<ApplicationCommandData>) -> anyhow::Result<()>;
-    async fn process_modal(&self, data: Box<ApplicationCommandData>) -> anyhow::Result<()>;
+    async fn process_modal(&self, data: Box<ModalSubmitData>) -> anyhow::Result<()>;
     async fn process_component(&self, data: Box<MessageComponentData>) -> anyhow::Result<()>;
 }
 
@@ -65,3 +65,11 @@ pub struct MessageComponentData {
     pub id: Uuid,
     // pub cancellation_token
 }
+
+#[derive(Debug)]
+pub struct ModalSubmitData {
+    pub interaction: Interaction,
+    pub modal: ModalInteractionData,
+    /// The modal's custom ID, without the handler's name.
+    pub action: String,
+}
@@ -0,0 +1,601 @@
+use std::sync::Arc;
+
+use bot::entity::prelude::*;
+
+use chrono::Utc;
+use sea_orm::{prelude::*, Condition, IntoActiveModel};
+use twilight_model::{
+    application::interaction::{application_command::CommandOptionValue, Interaction},
+    channel::message::{
+        component::{
+            ActionRow, Button, ButtonStyle, SelectMenu, SelectMenuOption, TextInput, TextInputStyle,
+        },
+        Component, Embed, MessageFlags,
+    },
+    http::interaction::{InteractionResponse, InteractionResponseType},
+    id::{
+        marker::{GuildMarker, UserMarker},
+        Id,
+    },
+};
+use twilight_util::builder::{
+    embed::{EmbedBuilder, EmbedFieldBuilder, EmbedFooterBuilder},
+    InteractionResponseDataBuilder,
+};
+
+use super::{
+    matchmaking::BackgroundLoop, utils::user_option, ApplicationCommandData,
+    CommandGroupDescriptor, CommonUtilities, InteractionHandler, MessageComponentData,
+    ModalSubmitData,
+};
+
+/// What a player can be reported for, with the names shown to the reporter.
+pub const REPORT_CATEGORIES: [(&str, ReportCategory); 5] = [
+    ("Cheating", ReportCategory::Cheating),
+    ("Harassment or bullying", ReportCategory::Harassment),
+    ("Unsportsmanlike conduct", ReportCategory::Unsportsmanlike),
+    ("Didn't show up or left early", ReportCategory::NoShow),
+    ("Something else", ReportCategory::Other),
+];
+
+/// How many attachment links are kept with a report.
+const MAX_ATTACHMENTS: usize = 5;
+
+/// Takes misconduct reports from players, and posts them in the guild's report channel for
+/// the staff to claim and close.
+pub struct ReportHandler {
+    utils: Arc<CommonUtilities>,
+    background: Arc<BackgroundLoop>,
+}
+
+#[async_trait]
+impl InteractionHandler for ReportHandler {
+    fn describe(&self) -> CommandGroupDescriptor {
+        // This is not a top-level command handler.
+        // This function should never be registered into the InteractionProcessor/
+        CommandGroupDescriptor {
+            name: "report",
+            description: "Reports players for misconduct",
+            commands: Box::new([]),
+        }
+    }
+
+    /// Asks what the player did. The modal can't be shown here, as the command has already
+    /// been deferred, so it's opened once a category is picked.
+    async fn process_command(&self, data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
+        let reporter = data
+            .interaction
+            .member
+            .as_ref()
+            .and_then(|m| m.user.as_ref())
+            .map(|u| u.id)
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+
+        let options = data
+            .command
+            .options
+            .first()
+            .and_then(|o| match &o.value {
+                CommandOptionValue::SubCommand(options) => Some(options.as_slice()),
+                _ => None,
+            })
+            .unwrap_or_default();
+        let player = user_option(options, "player").ok_or_else(|| anyhow!("no player provided"))?;
+
+        if player == reporter {
+            return Err(anyhow!("you cannot report yourself"));
+        }
+
+        let settings = self.utils.get_guild_settings(data.guild_id).await?;
+        if settings.report_channel.is_none() {
+            return Err(anyhow!("The server is not taking reports yet"));
+        }
+
+        let options = REPORT_CATEGORIES
+            .iter()
+            .map(|(name, category)| SelectMenuOption {
+                default: false,
+                description: None,
+                emoji: None,
+                label: (*name).to_string(),
+                value: category_name(*category),
+            })
+            .collect();
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .content(&format!("What did <@{player}> do?"))?
+            .components(&[Component::ActionRow(ActionRow {
+                components: vec![Component::SelectMenu(SelectMenu {
+                    custom_id: format!("matchmaking:report:category:{player}"),
+                    disabled: false,
+                    max_values: Some(1),
+                    min_values: Some(1),
+                    options,
+                    placeholder: Some("What happened".to_string()),
+                })],
+            })])?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn process_autocomplete(&self, _data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
+        unreachable!()
+    }
+
+    async fn process_modal(&self, data: Box<ModalSubmitData>) -> anyhow::Result<()> {
+        // The modal carries who was reported and for what, as "submit:<player>:<category>".
+        let mut parts = data
+            .action
+            .strip_prefix("submit:")
+            .ok_or_else(|| anyhow!("unknown report modal: {}", data.action))?
+            .splitn(2, ':');
+        let player = parts
+            .next()
+            .and_then(|p| p.parse::<u64>().ok())
+            .and_then(Id::new_checked)
+            .ok_or_else(|| anyhow!("no player given"))?;
+        let category = parts
+            .next()
+            .and_then(parse_category)
+            .ok_or_else(|| anyhow!("no category given"))?;
+
+        let content = self.submit_report(&data, player, category).await?;
+
+        self.reply(&data.interaction, content).await
+    }
+
+    async fn process_component(&self, data: Box<MessageComponentData>) -> anyhow::Result<()> {
+        let (action, target) = data
+            .action
+            .split_once(':')
+            .ok_or_else(|| anyhow!("no report given"))?;
+
+        match action {
+            "category" => self.open_report_modal(&data, target).await,
+            "claim" | "resolve" | "dismiss" => {
+                let report = target.parse::<Uuid>()?;
+                self.handle_report(&data, action, report).await
+            }
+            _ => Err(anyhow!("unknown report action: {action}")),
+        }
+    }
+}
+
+impl ReportHandler {
+    pub fn new(utils: Arc<CommonUtilities>, background: Arc<BackgroundLoop>) -> Self {
+        Self { utils, background }
+    }
+
+    async fn reply(&self, interaction: &Interaction, content: String) -> anyhow::Result<()> {
+        self.utils
+            .send_message(
+                interaction,
+                &InteractionResponse {
+                    kind: InteractionResponseType::ChannelMessageWithSource,
+                    data: Some(
+                        InteractionResponseDataBuilder::new()
+                            .content(content)
+                            .flags(MessageFlags::EPHEMERAL)
+                            .build(),
+                    ),
+                },
+            )
+            .await
+    }
+
+    /// Shows the report form. When it's opened from a lobby thread, the lobby is filled in.
+    async fn open_report_modal(
+        &self,
+        data: &MessageComponentData,
+        player: &str,
+    ) -> anyhow::Result<()> {
+        let guild_id = data
+            .interaction
+            .guild_id
+            .ok_or_else(|| anyhow!("Command cannot be run in a DM"))?;
+        let category = data
+            .message
+            .values
+            .first()
+            .ok_or_else(|| anyhow!("no category picked"))?;
+
+        let lobby_link = match data.interaction.channel_id {
+            Some(channel) => MatchmakingLobbies::find()
+                .filter(matchmaking_lobbies::Column::ChannelId.eq(IdWrapper::from(channel)))
+                .one(self.utils.db_ref())
+                .await?
+                .map(|_| format!("https://discord.com/channels/{guild_id}/{channel}")),
+            None => None,
+        };
+
+        let text_input = |custom_id: &str, label: &str, style, required, value| {
+            Component::ActionRow(ActionRow {
+                components: vec![Component::TextInput(TextInput {
+                    custom_id: custom_id.to_string(),
+                    label: label.to_string(),
+                    max_length: Some(1000),
+                    min_length: None,
+                    placeholder: None,
+                    required: Some(required),
+                    style,
+                    value,
+                })],
+            })
+        };
+
+        self.utils
+            .send_message(
+                &data.interaction,
+                &InteractionResponse {
+                    kind: InteractionResponseType::Modal,
+                    data: Some(
+                        InteractionResponseDataBuilder::new()
+                            .custom_id(format!("matchmaking:report:submit:{player}:{category}"))
+                            .title("Report a player")
+                            .components([
+                                text_input(
+                                    "description",
+                                    "What happened?",
+                                    TextInputStyle::Paragraph,
+                                    true,
+                                    None,
+                                ),
+                                text_input(
+                                    "lobby",
+                                    "Link to the lobby thread (optional)",
+                                    TextInputStyle::Short,
+                                    false,
+                                    lobby_link,
+                                ),
+                                text_input(
+                                    "attachments",
+                                    "Links to screenshots or clips, one per line",
+                                    TextInputStyle::Paragraph,
+                                    false,
+                                    None,
+                                ),
+                            ])
+                            .build(),
+                    ),
+                },
+            )
+            .await
+    }
+
+    /// Records the report and posts it in the report channel. Returns what the reporter is
+    /// told.
+    async fn submit_report(
+        &self,
+        data: &ModalSubmitData,
+        player: Id<UserMarker>,
+        category: ReportCategory,
+    ) -> anyhow::Result<String> {
+        let guild_id = data
+            .interaction
+            .guild_id
+            .ok_or_else(|| anyhow!("Command cannot be run in a DM"))?;
+        let reporter = data
+            .interaction
+            .member
+            .as_ref()
+            .and_then(|m| m.user.as_ref())
+            .map(|u| u.id)
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+
+        let settings = self.utils.get_guild_settings(guild_id).await?;
+        let Some(channel) = settings.report_channel else {
+            return Ok("This server is not taking reports right now.".to_string());
+        };
+
+        let field = |custom_id: &str| {
+            data.modal
+                .components
+                .iter()
+                .flat_map(|row| &row.components)
+                .find(|c| c.custom_id == custom_id)
+                .and_then(|c| c.value.as_deref())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+
+        let description = field("description")
+            .ok_or_else(|| anyhow!("no description given"))?
+            .to_string();
+        let lobby_link = field("lobby").map(str::to_string);
+        let attachments = field("attachments")
+            .map(|links| {
+                links
+                    .lines()
+                    .map(str::trim)
+                    .filter(|link| link.starts_with("https://") || link.starts_with("http://"))
+                    .take(MAX_ATTACHMENTS)
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let report = report::Model {
+            id: Uuid::new_v4(),
+            guild_id: guild_id.into(),
+            reporter: self.utils.find_or_create_user(reporter).await?.user_id,
+            reported: self.utils.find_or_create_user(player).await?.user_id,
+            category,
+            description,
+            lobby_link,
+            attachments: serde_json::to_value(attachments)?,
+            status: ReportStatus::Open,
+            handled_by: None,
+            created_at: Utc::now(),
+            closed_at: None,
+        };
+
+        Report::insert(report.clone().into_active_model())
+            .exec(self.utils.db_ref())
+            .await?;
+
+        let (embed, components) = self.report_message(&report).await?;
+        self.utils
+            .http_client
+            .create_message(channel.into_id())
+            .embeds(&[embed])?
+            .components(&components)?
+            .await?;
+
+        Ok(format!(
+            "Thanks, your report about <@{player}> was sent to the staff. \
+            You'll get a DM once they've looked into it."
+        ))
+    }
+
+    /// Claims, resolves or dismisses a report from the staff queue, and tells the reporter
+    /// once it's closed.
+    async fn handle_report(
+        &self,
+        data: &MessageComponentData,
+        action: &str,
+        id: Uuid,
+    ) -> anyhow::Result<()> {
+        let guild_id = data
+            .interaction
+            .guild_id
+            .ok_or_else(|| anyhow!("Command cannot be run in a DM"))?;
+        let member = data
+            .interaction
+            .member
+            .as_ref()
+            .ok_or_else(|| anyhow!("cannot get member data"))?;
+        let staff = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?
+            .id;
+
+        if !self.utils.is_guild_admin(guild_id, member).await? {
+            return self
+                .reply(
+                    &data.interaction,
+                    "Only staff can handle reports.".to_string(),
+                )
+                .await;
+        }
+
+        let (status, audit_action, verb) = match action {
+            "claim" => (ReportStatus::Claimed, AuditAction::ReportClaimed, "claimed"),
+            "resolve" => (
+                ReportStatus::Resolved,
+                AuditAction::ReportResolved,
+                "resolved",
+            ),
+            _ => (
+                ReportStatus::Dismissed,
+                AuditAction::ReportDismissed,
+                "dismissed",
+            ),
+        };
+        let closed = status != ReportStatus::Claimed;
+        let handler = self.utils.find_or_create_user(staff).await?.user_id;
+
+        // A claimed report can only be closed by the staff member who claimed it.
+        let from = if closed {
+            Condition::any()
+                .add(report::Column::Status.eq(ReportStatus::Open))
+                .add(
+                    Condition::all()
+                        .add(report::Column::Status.eq(ReportStatus::Claimed))
+                        .add(report::Column::HandledBy.eq(handler)),
+                )
+        } else {
+            Condition::all().add(report::Column::Status.eq(ReportStatus::Open))
+        };
+
+        // Only moves the report along if nobody else got to it first.
+        let res = Report::update_many()
+            .set(report::ActiveModel {
+                status: Set(status),
+                handled_by: Set(Some(handler)),
+                closed_at: Set(closed.then(Utc::now)),
+                ..Default::default()
+            })
+            .filter(report::Column::Id.eq(id))
+            .filter(report::Column::GuildId.eq(IdWrapper::from(guild_id)))
+            .filter(from)
+            .exec(self.utils.db_ref())
+            .await?;
+
+        let report = Report::find_by_id(id)
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("could not find the report"))?;
+
+        if res.rows_affected == 0 {
+            let content = match (report.status, report.handled_by) {
+                (ReportStatus::Claimed, Some(claimer)) => format!(
+                    "<@{}> claimed this report, so only they can close it.",
+                    self.utils.discord_user_id(claimer).await?
+                ),
+                _ => "Someone else already handled this report.".to_string(),
+            };
+            return self.reply(&data.interaction, content).await;
+        }
+
+        let player = self.utils.discord_user_id(report.reported).await?;
+        self.utils
+            .audit(
+                guild_id,
+                staff,
+                audit_action,
+                Some(player),
+                format!("{verb} the report about <@{player}>"),
+            )
+            .await?;
+
+        let (embed, components) = self.report_message(&report).await?;
+        self.utils
+            .send_message(
+                &data.interaction,
+                &InteractionResponse {
+                    kind: InteractionResponseType::UpdateMessage,
+                    data: Some(
+                        InteractionResponseDataBuilder::new()
+                            .embeds([embed])
+                            .components(components)
+                            .build(),
+                    ),
+                },
+            )
+            .await?;
+
+        if closed {
+            if let Err(e) = self.notify_reporter(guild_id, &report).await {
+                warn!(error = ?e, report = ?report.id, "could not tell the reporter the outcome");
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn notify_reporter(
+        &self,
+        guild: Id<GuildMarker>,
+        report: &report::Model,
+    ) -> anyhow::Result<()> {
+        let guild = self.utils.http_client.guild(guild).await?.model().await?;
+        let reporter = self.utils.discord_user_id(report.reporter).await?;
+        let player = self.utils.discord_user_id(report.reported).await?;
+
+        let outcome = if report.status == ReportStatus::Resolved {
+            "was resolved by the staff. Thanks for helping keep matchmaking fair."
+        } else {
+            "was reviewed and dismissed by the staff."
+        };
+
+        self.background
+            .send_dm(
+                reporter,
+                &format!(
+                    "Your report about <@{player}> in **{}** {outcome}",
+                    guild.name
+                ),
+            )
+            .await
+    }
+
+    /// The staff queue message for a report. Closed reports have no buttons left.
+    async fn report_message(
+        &self,
+        report: &report::Model,
+    ) -> anyhow::Result<(Embed, Vec<Component>)> {
+        let reporter = self.utils.discord_user_id(report.reporter).await?;
+        let player = self.utils.discord_user_id(report.reported).await?;
+        let handled_by = match report.handled_by {
+            Some(staff) => Some(self.utils.discord_user_id(staff).await?),
+            None => None,
+        };
+
+        let status = match (report.status, handled_by) {
+            (ReportStatus::Open, _) | (_, None) => "Open".to_string(),
+            (ReportStatus::Claimed, Some(staff)) => format!("Claimed by <@{staff}>"),
+            (ReportStatus::Resolved, Some(staff)) => format!("Resolved by <@{staff}>"),
+            (ReportStatus::Dismissed, Some(staff)) => format!("Dismissed by <@{staff}>"),
+        };
+
+        let attachments = report.attachments();
+        let category = REPORT_CATEGORIES
+            .iter()
+            .find(|(_, category)| *category == report.category)
+            .map_or("Report", |(name, _)| *name);
+
+        let embed = EmbedBuilder::new()
+            .title(format!("Report: {category}"))
+            .description(&report.description)
+            .field(EmbedFieldBuilder::new("Reported player", format!("<@{player}>")).inline())
+            .field(EmbedFieldBuilder::new("Reported by", format!("<@{reporter}>")).inline())
+            .field(EmbedFieldBuilder::new("Status", status).inline())
+            .field(EmbedFieldBuilder::new(
+                "Lobby",
+                report.lobby_link.as_deref().unwrap_or("None"),
+            ))
+            .field(EmbedFieldBuilder::new(
+                "Attachments",
+                if attachments.is_empty() {
+                    "None".to_string()
+                } else {
+                    attachments.join("\n")
+                },
+            ))
+            .footer(EmbedFooterBuilder::new(format!(
+                "Reported at {}",
+                report.created_at.format("%Y-%m-%d %H:%M UTC")
+            )))
+            .validate()?
+            .build();
+
+        let button = |action: &str, label: &str, style| {
+            Component::Button(Button {
+                custom_id: Some(format!("matchmaking:report:{action}:{}", report.id)),
+                disabled: false,
+                emoji: None,
+                label: Some(label.to_string()),
+                style,
+                url: None,
+            })
+        };
+
+        let mut buttons = Vec::new();
+        if report.status == ReportStatus::Open {
+            buttons.push(button("claim", "Claim", ButtonStyle::Primary));
+        }
+        if matches!(report.status, ReportStatus::Open | ReportStatus::Claimed) {
+            buttons.push(button("resolve", "Resolve", ButtonStyle::Success));
+            buttons.push(button("dismiss", "Dismiss", ButtonStyle::Secondary));
+        }
+
+        let components = if buttons.is_empty() {
+            Vec::new()
+        } else {
+            vec![Component::ActionRow(ActionRow {
+                components: buttons,
+            })]
+        };
+
+        Ok((embed, components))
+    }
+}
+
+/// The value that a category is stored as, which is also how it's passed between the
+/// report's select menu and modal.
+fn category_name(category: ReportCategory) -> String {
+    category.to_value()
+}
+
+fn parse_category(name: &str) -> Option<ReportCategory> {
+    REPORT_CATEGORIES
+        .iter()
+        .map(|(_, category)| *category)
+        .find(|category| category_name(*category) == name)
+}
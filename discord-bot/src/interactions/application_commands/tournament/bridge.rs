@@ -0,0 +1,727 @@
+use std::{collections::HashMap, sync::Arc};
+
+use bot::{
+    bridge::{
+        challonge::{self, Challonge},
+        startgg::StartGg,
+        BracketService, SetScore, SetState,
+    },
+    entity::prelude::*,
+};
+use chrono::Utc;
+use sea_orm::{prelude::*, Condition, IntoActiveModel, Set};
+use twilight_model::{
+    application::interaction::application_command::CommandDataOption,
+    channel::message::{
+        allowed_mentions::AllowedMentionsBuilder,
+        component::{ActionRow, Button, ButtonStyle},
+        Component, MessageFlags,
+    },
+    guild::PartialMember,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+    id::{marker::GuildMarker, Id},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::interactions::application_commands::{
+    utils::{channel_option, string_option},
+    ApplicationCommandData, MessageComponentData,
+};
+
+use super::{
+    reporting::{parse_score, DISPUTED_RESULT},
+    tournament_handler::TournamentCommandHandler,
+};
+
+impl TournamentCommandHandler {
+    pub(super) async fn import_tournament(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        if !self.utils.is_guild_admin(data.guild_id, member).await? {
+            return Err(anyhow!("only admins can import tournaments"));
+        }
+
+        let name = string_option(options, "name").ok_or_else(|| anyhow!("no name provided"))?;
+        let event = string_option(options, "event").ok_or_else(|| anyhow!("no event provided"))?;
+        let source = parse_source(string_option(options, "service"))?;
+        let event = match source {
+            BracketSource::StartGg => event.to_string(),
+            BracketSource::Challonge => challonge::event_id(event),
+        };
+
+        let existing = Tournament::find()
+            .filter(tournament::Column::GuildId.eq(IdWrapper::from(data.guild_id)))
+            .filter(tournament::Column::Name.eq(name))
+            .one(self.utils.db_ref())
+            .await?;
+
+        if existing.is_some() {
+            return Err(anyhow!("a tournament named \"{}\" already exists", name));
+        }
+
+        let channel = channel_option(options, "channel")
+            .or(data.interaction.channel_id)
+            .ok_or_else(|| anyhow!("command was not run in a channel"))?;
+
+        let entrants = self
+            .bridge(source, data.guild_id)
+            .await?
+            .entrants(&event)
+            .await?;
+
+        let user = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+        let organizer = self.utils.find_or_create_user(user.id).await?;
+
+        // The bracket itself lives on the service, so the format is only a placeholder.
+        let tournament = tournament::Model {
+            id: Uuid::new_v4(),
+            guild_id: data.guild_id.into(),
+            name: name.to_string(),
+            game: None,
+            format: TournamentFormat::SingleElimination,
+            state: TournamentState::InProgress,
+            channel: Some(channel.into()),
+            swiss_rounds: None,
+            top_cut: None,
+            pool_count: None,
+            pool_advance: None,
+            max_entrants: None,
+            starts_at: None,
+            checkin_minutes: None,
+            dq_minutes: None,
+            panel_message: None,
+            separate_teams: false,
+            bracket_message: None,
+            source: Some(source),
+            source_event: Some(event),
+            created_by: organizer.user_id,
+            created_at: Utc::now(),
+            started_at: Some(Utc::now()),
+            advanced_to: 0,
+        };
+
+        let tournament = Tournament::insert(tournament.into_active_model())
+            .exec_with_returning(self.utils.db_ref())
+            .await?;
+
+        // Entrants can only be matched up with Discord users if they linked their account on
+        // the service.
+        let mut unlinked = Vec::new();
+        let mut imported = 0;
+        for entrant in entrants {
+            let Some(discord_id) = entrant.discord_id.and_then(Id::new_checked) else {
+                unlinked.push(entrant.name);
+                continue;
+            };
+
+            let player = self.utils.find_or_create_user(discord_id).await?;
+            let model = tournament_entrant::Model {
+                tournament: tournament.id,
+                player: player.user_id,
+                seed: None,
+                pool: None,
+                registered_at: Utc::now(),
+                waitlisted: false,
+                checked_in_at: None,
+                remote_id: Some(entrant.id),
+            };
+
+            TournamentEntrant::insert(model.into_active_model())
+                .exec(self.utils.db_ref())
+                .await?;
+            imported += 1;
+        }
+
+        self.sync_remote(&tournament).await?;
+
+        let mut content = format!(
+            "Imported **{}** from {} with {} entrants.",
+            tournament.name,
+            source_name(source),
+            imported
+        );
+        if !unlinked.is_empty() {
+            content.push_str(
+                " These entrants haven't linked their Discord account, so their sets won't be \
+                 called here: ",
+            );
+            content.push_str(&unlinked.join(", "));
+        }
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .content(&content)?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn set_credentials(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        if !self.utils.is_guild_admin(data.guild_id, member).await? {
+            return Err(anyhow!(
+                "only admins can change bracket service credentials"
+            ));
+        }
+
+        let source = parse_source(string_option(options, "service"))?;
+        let existing = self.credentials(data.guild_id, source).await?;
+
+        let content = match (string_option(options, "api-key").map(str::trim), existing) {
+            (Some(api_key), Some(existing)) if !api_key.is_empty() => {
+                let mut model = existing.into_active_model();
+                model.api_key = Set(api_key.to_string());
+                model.updated_at = Set(Utc::now());
+                model.update(self.utils.db_ref()).await?;
+
+                format!("Updated this server's {} API key.", source_name(source))
+            }
+            (Some(api_key), None) if !api_key.is_empty() => {
+                let model = bracket_credentials::Model {
+                    id: Uuid::new_v4(),
+                    guild_id: data.guild_id.into(),
+                    source,
+                    api_key: api_key.to_string(),
+                    updated_at: Utc::now(),
+                };
+                BracketCredentials::insert(model.into_active_model())
+                    .exec(self.utils.db_ref())
+                    .await?;
+
+                format!(
+                    "Tournaments in this server can now use {}.",
+                    source_name(source)
+                )
+            }
+            (_, Some(existing)) => {
+                BracketCredentials::delete_by_id(existing.id)
+                    .exec(self.utils.db_ref())
+                    .await?;
+
+                format!("Removed this server's {} API key.", source_name(source))
+            }
+            (_, None) => return Err(anyhow!("no API key provided")),
+        };
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .content(&content)?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn credentials(
+        &self,
+        guild: Id<GuildMarker>,
+        source: BracketSource,
+    ) -> anyhow::Result<Option<bracket_credentials::Model>> {
+        Ok(BracketCredentials::find()
+            .filter(bracket_credentials::Column::GuildId.eq(IdWrapper::from(guild)))
+            .filter(bracket_credentials::Column::Source.eq(source))
+            .one(self.utils.db_ref())
+            .await?)
+    }
+
+    /// The client for a guild's tournaments on a service. A guild's own API key is used if
+    /// it set one, and start.gg falls back to the bot's token.
+    async fn bridge(
+        &self,
+        source: BracketSource,
+        guild: Id<GuildMarker>,
+    ) -> anyhow::Result<Arc<dyn BracketService>> {
+        match (source, self.credentials(guild, source).await?) {
+            (BracketSource::StartGg, Some(credentials)) => Ok(Arc::new(StartGg::new(
+                crate::CONFIG.startgg.api_url.clone(),
+                credentials.api_key,
+            ))),
+            (BracketSource::StartGg, None) => self
+                .startgg
+                .clone()
+                .ok_or_else(|| anyhow!("start.gg isn't set up on this bot")),
+            (BracketSource::Challonge, Some(credentials)) => Ok(Arc::new(Challonge::new(
+                crate::CONFIG.challonge.api_url.clone(),
+                credentials.api_key,
+            ))),
+            (BracketSource::Challonge, None) => Err(anyhow!(
+                "this server doesn't have a Challonge API key, so an admin needs to add one \
+                 with /tournament credentials"
+            )),
+        }
+    }
+
+    /// Polls every bridged tournament for sets the service has called since the last pass.
+    pub(crate) async fn sync_remote_tournaments(&self) -> anyhow::Result<()> {
+        let tournaments = Tournament::find()
+            .filter(tournament::Column::State.eq(TournamentState::InProgress))
+            .filter(tournament::Column::Source.is_not_null())
+            .all(self.utils.db_ref())
+            .await?;
+
+        // One broken event shouldn't hold up the others.
+        for tournament in tournaments {
+            if let Err(e) = self.sync_remote(&tournament).await {
+                warn!(error = ?e, tournament = ?tournament.id, "failed to sync a bridged tournament");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copies the sets from the service, and opens a lobby thread for every set that has
+    /// been called.
+    async fn sync_remote(&self, tournament: &tournament::Model) -> anyhow::Result<()> {
+        let (Some(source), Some(event)) = (tournament.source, tournament.source_event.as_deref())
+        else {
+            return Ok(());
+        };
+
+        let sets = self
+            .bridge(source, tournament.guild_id.into_id())
+            .await?
+            .sets(event)
+            .await?;
+
+        let players = TournamentEntrant::find()
+            .filter(tournament_entrant::Column::Tournament.eq(tournament.id))
+            .all(self.utils.db_ref())
+            .await?
+            .into_iter()
+            .filter_map(|e| Some((e.remote_id?, e.player)))
+            .collect::<HashMap<_, _>>();
+        let player = |id: &Option<String>| id.as_ref().and_then(|id| players.get(id)).copied();
+
+        let mut stored = TournamentRemoteSet::find()
+            .filter(tournament_remote_set::Column::Tournament.eq(tournament.id))
+            .all(self.utils.db_ref())
+            .await?
+            .into_iter()
+            .map(|s| (s.remote_id.clone(), s))
+            .collect::<HashMap<_, _>>();
+
+        for set in &sets {
+            let entrant_one = player(&set.entrants[0]);
+            let entrant_two = player(&set.entrants[1]);
+            let winner = player(&set.winner);
+
+            let local = if let Some(local) = stored.remove(&set.id) {
+                local
+            } else {
+                let model = tournament_remote_set::Model {
+                    id: Uuid::new_v4(),
+                    tournament: tournament.id,
+                    remote_id: set.id.clone(),
+                    round: set.round.clone(),
+                    entrant_one,
+                    entrant_two,
+                    winner: None,
+                    reported_by: None,
+                    reported_winner: None,
+                    winner_games: None,
+                    loser_games: None,
+                    lobby: None,
+                    completed_at: None,
+                };
+
+                TournamentRemoteSet::insert(model.into_active_model())
+                    .exec_with_returning(self.utils.db_ref())
+                    .await?
+            };
+
+            let completed = set.state == SetState::Completed && local.completed_at.is_none();
+            let call = set.state == SetState::Called && local.lobby.is_none();
+            if !completed
+                && !call
+                && local.entrant_one == entrant_one
+                && local.entrant_two == entrant_two
+            {
+                continue;
+            }
+
+            let lobby = local.lobby;
+            let mut model = local.into_active_model();
+            model.entrant_one = Set(entrant_one);
+            model.entrant_two = Set(entrant_two);
+
+            if completed {
+                // The result was reported on the service, or by a tournament organizer.
+                model.winner = Set(winner);
+                model.completed_at = Set(Some(Utc::now()));
+                self.close_lobby(lobby).await?;
+            } else if let (true, Some(one), Some(two)) = (call, entrant_one, entrant_two) {
+                let title = format!("**{}** {}", tournament.name, set.round);
+                model.lobby = Set(self.open_match(tournament, &title, one, two).await?);
+            }
+
+            model.update(self.utils.db_ref()).await?;
+        }
+
+        // Sets that haven't started can change their IDs once the bracket is published.
+        let stale = stored
+            .into_values()
+            .filter(|s| s.lobby.is_none() && s.completed_at.is_none())
+            .map(|s| s.id)
+            .collect::<Vec<_>>();
+        if !stale.is_empty() {
+            TournamentRemoteSet::delete_many()
+                .filter(tournament_remote_set::Column::Id.is_in(stale))
+                .exec(self.utils.db_ref())
+                .await?;
+        }
+
+        if !sets.is_empty() && sets.iter().all(|s| s.state == SetState::Completed) {
+            self.post(
+                tournament,
+                EmbedBuilder::new()
+                    .title(format!("{} is over", tournament.name))
+                    .description(format!(
+                        "Every set has been played. See the results on {}.",
+                        source_name(source)
+                    )),
+            )
+            .await?;
+            self.complete(tournament).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Records the caller's result for their set. Reporting the same winner as the opponent
+    /// confirms their report, and otherwise the opponent is asked to confirm this one before
+    /// it's sent to the service.
+    pub(super) async fn report_remote_set(
+        &self,
+        tournament: &tournament::Model,
+        player: Uuid,
+        won: bool,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<String> {
+        let set = TournamentRemoteSet::find()
+            .filter(tournament_remote_set::Column::Tournament.eq(tournament.id))
+            .filter(tournament_remote_set::Column::CompletedAt.is_null())
+            .filter(tournament_remote_set::Column::EntrantOne.is_not_null())
+            .filter(tournament_remote_set::Column::EntrantTwo.is_not_null())
+            .filter(
+                Condition::any()
+                    .add(tournament_remote_set::Column::EntrantOne.eq(player))
+                    .add(tournament_remote_set::Column::EntrantTwo.eq(player)),
+            )
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("you don't have a set to report"))?;
+
+        let opponent = if set.entrant_one == Some(player) {
+            set.entrant_two
+        } else {
+            set.entrant_one
+        }
+        .ok_or_else(|| anyhow!("your opponent isn't known yet"))?;
+
+        let games = string_option(options, "score")
+            .map(parse_score)
+            .transpose()?;
+        let games = match games {
+            Some((mine, theirs)) if (mine > theirs) != won => {
+                return Err(anyhow!("the score doesn't match the result"));
+            }
+            Some((mine, theirs)) if won => Some((mine, theirs)),
+            Some((mine, theirs)) => Some((theirs, mine)),
+            None => None,
+        };
+        let winner = if won { player } else { opponent };
+
+        if set.reported_by.is_some_and(|r| r != player) && set.reported_winner == Some(winner) {
+            let games = match games {
+                Some(games) => Some(games),
+                None => reported_games(&set)?,
+            };
+            let content = self
+                .record_remote_set(tournament, set, winner, games)
+                .await?;
+            self.sync_remote(tournament).await?;
+
+            return Ok(content);
+        }
+
+        let lobby = set.lobby;
+        let set_id = set.id;
+        let res = TournamentRemoteSet::update_many()
+            .set(tournament_remote_set::ActiveModel {
+                reported_by: Set(Some(player)),
+                reported_winner: Set(Some(winner)),
+                winner_games: Set(games.map(|g| i32::try_from(g.0)).transpose()?),
+                loser_games: Set(games.map(|g| i32::try_from(g.1)).transpose()?),
+                ..Default::default()
+            })
+            .filter(tournament_remote_set::Column::Id.eq(set_id))
+            .filter(tournament_remote_set::Column::CompletedAt.is_null())
+            .exec(self.utils.db_ref())
+            .await?;
+
+        if res.rows_affected == 0 {
+            return Err(anyhow!("this set has already been reported"));
+        }
+
+        // The confirmation goes in the set's thread if it has one.
+        let channel = match lobby {
+            Some(lobby) => MatchmakingLobbies::find_by_id(lobby)
+                .one(self.utils.db_ref())
+                .await?
+                .map(|l| l.channel_id),
+            None => None,
+        }
+        .or_else(|| tournament.channel.clone())
+        .ok_or_else(|| anyhow!("the tournament doesn't have a channel"))?;
+
+        let reporter = self.utils.discord_user_id(player).await?;
+        let confirmer = self.utils.discord_user_id(opponent).await?;
+        let score = games.map_or_else(String::new, |(w, l)| format!(" {w}-{l}"));
+
+        self.utils
+            .http_client
+            .create_message(channel.into_id())
+            .content(&format!(
+                "<@{}>, <@{}> reported that <@{}> won{}. Is that right?",
+                confirmer,
+                reporter,
+                self.utils.discord_user_id(winner).await?,
+                score
+            ))?
+            .allowed_mentions(Some(
+                &AllowedMentionsBuilder::new().user_ids([confirmer]).build(),
+            ))
+            .components(&[Component::ActionRow(ActionRow {
+                components: vec![
+                    Component::Button(Button {
+                        custom_id: Some(format!("tournament:confirm:{}:{}", tournament.id, set_id)),
+                        disabled: false,
+                        emoji: None,
+                        label: Some("Confirm".to_string()),
+                        style: ButtonStyle::Success,
+                        url: None,
+                    }),
+                    Component::Button(Button {
+                        custom_id: Some(format!("tournament:dispute:{}:{}", tournament.id, set_id)),
+                        disabled: false,
+                        emoji: None,
+                        label: Some("Dispute".to_string()),
+                        style: ButtonStyle::Danger,
+                        url: None,
+                    }),
+                ],
+            })])?
+            .await?;
+
+        Ok("Your result has been sent to your opponent to confirm.".to_string())
+    }
+
+    /// Handles the answer to a reported result from the reporter's opponent or an
+    /// organizer. Confirmed results are reported to the service, and disputed ones are
+    /// cleared so they can be reported again.
+    pub(super) async fn confirm_remote_set(
+        &self,
+        data: &MessageComponentData,
+        tournament: &tournament::Model,
+        set: Option<&str>,
+        confirmed: bool,
+    ) -> anyhow::Result<()> {
+        let member = data
+            .interaction
+            .member
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get member data for caller"))?;
+        let user = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+        let player = self.utils.find_or_create_user(user.id).await?.user_id;
+
+        let set_id: Uuid = set.ok_or_else(|| anyhow!("no set was given"))?.parse()?;
+        let set = TournamentRemoteSet::find_by_id(set_id)
+            .filter(tournament_remote_set::Column::Tournament.eq(tournament.id))
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("that set no longer exists"))?;
+
+        let (Some(reporter), Some(winner), None) =
+            (set.reported_by, set.reported_winner, set.completed_at)
+        else {
+            return Err(anyhow!("this result has already been handled"));
+        };
+        self.check_answer(
+            tournament,
+            member,
+            player,
+            reporter,
+            [set.entrant_one, set.entrant_two],
+        )
+        .await?;
+
+        let content = if confirmed {
+            let games = reported_games(&set)?;
+            self.record_remote_set(tournament, set, winner, games)
+                .await?
+        } else {
+            TournamentRemoteSet::update_many()
+                .set(tournament_remote_set::ActiveModel {
+                    reported_by: Set(None),
+                    reported_winner: Set(None),
+                    winner_games: Set(None),
+                    loser_games: Set(None),
+                    ..Default::default()
+                })
+                .filter(tournament_remote_set::Column::Id.eq(set.id))
+                .filter(tournament_remote_set::Column::CompletedAt.is_null())
+                .exec(self.utils.db_ref())
+                .await?;
+
+            DISPUTED_RESULT.to_string()
+        };
+
+        self.utils
+            .send_message(
+                &data.interaction,
+                &InteractionResponse {
+                    kind: InteractionResponseType::UpdateMessage,
+                    data: Some(
+                        InteractionResponseDataBuilder::new()
+                            .content(content)
+                            .components([])
+                            .build(),
+                    ),
+                },
+            )
+            .await?;
+
+        // The next set might have been called now.
+        if confirmed {
+            self.sync_remote(tournament).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Reports the winner of a set to the service, and closes the set's thread.
+    async fn record_remote_set(
+        &self,
+        tournament: &tournament::Model,
+        set: tournament_remote_set::Model,
+        winner: Uuid,
+        games: Option<(u32, u32)>,
+    ) -> anyhow::Result<String> {
+        let loser = if set.entrant_one == Some(winner) {
+            set.entrant_two
+        } else {
+            set.entrant_one
+        }
+        .ok_or_else(|| anyhow!("the set is missing a player"))?;
+
+        let remote_ids = TournamentEntrant::find()
+            .filter(tournament_entrant::Column::Tournament.eq(tournament.id))
+            .filter(tournament_entrant::Column::Player.is_in([winner, loser]))
+            .all(self.utils.db_ref())
+            .await?
+            .into_iter()
+            .filter_map(|e| Some((e.player, e.remote_id?)))
+            .collect::<HashMap<_, _>>();
+        let remote_id = |player: Uuid| {
+            remote_ids
+                .get(&player)
+                .cloned()
+                .ok_or_else(|| anyhow!("a player isn't linked to the service"))
+        };
+        let score = SetScore {
+            winner: remote_id(winner)?,
+            loser: remote_id(loser)?,
+            games,
+        };
+
+        let (Some(source), Some(event)) = (tournament.source, tournament.source_event.as_deref())
+        else {
+            return Err(anyhow!("this tournament isn't hosted on another service"));
+        };
+        let bridge = self.bridge(source, tournament.guild_id.into_id()).await?;
+
+        // Claiming the set first means that it's only sent to the service once, even when
+        // it's confirmed twice at the same time.
+        let res = TournamentRemoteSet::update_many()
+            .set(tournament_remote_set::ActiveModel {
+                winner: Set(Some(winner)),
+                winner_games: Set(games.map(|g| i32::try_from(g.0)).transpose()?),
+                loser_games: Set(games.map(|g| i32::try_from(g.1)).transpose()?),
+                completed_at: Set(Some(Utc::now())),
+                ..Default::default()
+            })
+            .filter(tournament_remote_set::Column::Id.eq(set.id))
+            .filter(tournament_remote_set::Column::CompletedAt.is_null())
+            .exec(self.utils.db_ref())
+            .await?;
+
+        if res.rows_affected != 1 {
+            return Err(anyhow!("this result has already been handled"));
+        }
+
+        if let Err(e) = bridge.report(event, &set.remote_id, &score).await {
+            // The service doesn't have the result, so the set is released to be confirmed
+            // again.
+            TournamentRemoteSet::update_many()
+                .set(tournament_remote_set::ActiveModel {
+                    winner: Set(None),
+                    completed_at: Set(None),
+                    ..Default::default()
+                })
+                .filter(tournament_remote_set::Column::Id.eq(set.id))
+                .exec(self.utils.db_ref())
+                .await?;
+
+            return Err(e.into());
+        }
+
+        self.close_lobby(set.lobby).await?;
+
+        Ok(format!(
+            "<@{}> won, and the result has been sent to {}.",
+            self.utils.discord_user_id(winner).await?,
+            source_name(source)
+        ))
+    }
+}
+
+pub(super) fn source_name(source: BracketSource) -> &'static str {
+    match source {
+        BracketSource::StartGg => "start.gg",
+        BracketSource::Challonge => "Challonge",
+    }
+}
+
+fn parse_source(service: Option<&str>) -> anyhow::Result<BracketSource> {
+    match service {
+        Some("startgg") | None => Ok(BracketSource::StartGg),
+        Some("challonge") => Ok(BracketSource::Challonge),
+        Some(other) => Err(anyhow!("\"{}\" is not a known service", other)),
+    }
+}
+
+/// The game count that was reported for a set, if the reporter gave one.
+fn reported_games(set: &tournament_remote_set::Model) -> anyhow::Result<Option<(u32, u32)>> {
+    Ok(match (set.winner_games, set.loser_games) {
+        (Some(w), Some(l)) => Some((u32::try_from(w)?, u32::try_from(l)?)),
+        _ => None,
+    })
+}
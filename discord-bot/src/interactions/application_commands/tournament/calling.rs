@@ -0,0 +1,438 @@
+use bot::entity::prelude::*;
+use chrono::{Duration, Utc};
+use sea_orm::{prelude::*, Condition, IntoActiveModel, QueryOrder, Set};
+use twilight_model::{
+    channel::message::{
+        allowed_mentions::AllowedMentionsBuilder,
+        component::{ActionRow, Button, ButtonStyle},
+        Component, MessageFlags,
+    },
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use crate::interactions::application_commands::MessageComponentData;
+
+use super::{reporting::match_name, tournament_handler::TournamentCommandHandler};
+
+impl TournamentCommandHandler {
+    /// Calls every bracket match that has both of its players and hasn't been called yet.
+    /// A lobby thread is opened for each one, and the players are asked to check in.
+    pub(super) async fn call_matches(&self, tournament: &tournament::Model) -> anyhow::Result<()> {
+        let matches = TournamentMatch::find()
+            .filter(tournament_match::Column::Tournament.eq(tournament.id))
+            .filter(tournament_match::Column::Winner.is_null())
+            .filter(tournament_match::Column::CalledAt.is_null())
+            .filter(tournament_match::Column::EntrantOne.is_not_null())
+            .filter(tournament_match::Column::EntrantTwo.is_not_null())
+            .order_by_asc(tournament_match::Column::Position)
+            .all(self.utils.db_ref())
+            .await?;
+
+        // One match that can't be called shouldn't hold up the others.
+        for m in matches {
+            let id = m.id;
+            if let Err(e) = self.call_match(tournament, m).await {
+                error!(error = ?e, tournament = ?tournament.id, tournament_match = ?id, "failed to call a match");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Opens the match's lobby thread, and asks its players to check in.
+    async fn call_match(
+        &self,
+        tournament: &tournament::Model,
+        m: tournament_match::Model,
+    ) -> anyhow::Result<()> {
+        let (Some(one), Some(two)) = (m.entrant_one, m.entrant_two) else {
+            return Ok(());
+        };
+
+        let round = match_name(tournament, m.side, m.round);
+        let title = format!("**{}** {}", tournament.name, round);
+        let lobby = self.open_match(tournament, &title, one, two).await?;
+
+        // The match is marked as called before anything else can fail, so that it isn't
+        // announced again with a second thread.
+        let match_id = m.id;
+        let mut model = m.into_active_model();
+        model.lobby = Set(lobby);
+        model.called_at = Set(Some(Utc::now()));
+        model.update(self.utils.db_ref()).await?;
+
+        // The call goes in the match's thread if it has one.
+        let channel = match lobby {
+            Some(lobby) => MatchmakingLobbies::find_by_id(lobby)
+                .one(self.utils.db_ref())
+                .await?
+                .map(|l| l.channel_id),
+            None => None,
+        }
+        .or_else(|| tournament.channel.clone())
+        .ok_or_else(|| anyhow!("the tournament doesn't have a channel"))?;
+
+        let names = self.player_names(&[one, two]).await?;
+        let ids = [
+            self.utils.discord_user_id(one).await?,
+            self.utils.discord_user_id(two).await?,
+        ];
+        let custom_id =
+            |action: &str| format!("tournament:{}:{}:{}", action, tournament.id, match_id);
+
+        let timer = tournament.dq_minutes.map_or_else(String::new, |minutes| {
+            format!(" Press **I'm here** within {minutes} minutes, or you may be disqualified.")
+        });
+        let content = format!(
+            "<@{}> <@{}>, your {} match is ready.{} Report the winner with the buttons \
+             below when you're done.",
+            ids[0], ids[1], round, timer
+        );
+
+        self.utils
+            .http_client
+            .create_message(channel.into_id())
+            .content(&content)?
+            .allowed_mentions(Some(&AllowedMentionsBuilder::new().user_ids(ids).build()))
+            .components(&[Component::ActionRow(ActionRow {
+                components: vec![
+                    Component::Button(Button {
+                        custom_id: Some(custom_id("ready")),
+                        disabled: false,
+                        emoji: None,
+                        label: Some("I'm here".to_string()),
+                        style: ButtonStyle::Primary,
+                        url: None,
+                    }),
+                    Component::Button(Button {
+                        custom_id: Some(format!("{}:0", custom_id("win"))),
+                        disabled: false,
+                        emoji: None,
+                        label: Some(format!("{} won", names[0])),
+                        style: ButtonStyle::Secondary,
+                        url: None,
+                    }),
+                    Component::Button(Button {
+                        custom_id: Some(format!("{}:1", custom_id("win"))),
+                        disabled: false,
+                        emoji: None,
+                        label: Some(format!("{} won", names[1])),
+                        style: ButtonStyle::Secondary,
+                        url: None,
+                    }),
+                ],
+            })])?
+            .await?;
+
+        Ok(())
+    }
+
+    /// Asks the organizer whether to disqualify the players of called matches who haven't
+    /// checked in before their tournament's DQ timer ran out.
+    pub(crate) async fn check_called_matches(&self) -> anyhow::Result<()> {
+        let matches = TournamentMatch::find()
+            .filter(tournament_match::Column::CalledAt.is_not_null())
+            .filter(tournament_match::Column::Winner.is_null())
+            .filter(tournament_match::Column::DqPromptedAt.is_null())
+            .filter(
+                Condition::any()
+                    .add(tournament_match::Column::EntrantOneReadyAt.is_null())
+                    .add(tournament_match::Column::EntrantTwoReadyAt.is_null()),
+            )
+            .find_also_related(Tournament)
+            .all(self.utils.db_ref())
+            .await?;
+
+        let now = Utc::now();
+        for (m, tournament) in matches {
+            let Some(tournament) = tournament else {
+                continue;
+            };
+            let (Some(called_at), Some(minutes)) = (m.called_at, tournament.dq_minutes) else {
+                continue;
+            };
+            if tournament.state != TournamentState::InProgress
+                || now < called_at + Duration::minutes(minutes.into())
+            {
+                continue;
+            }
+
+            let absent = [
+                (m.entrant_one, m.entrant_one_ready_at),
+                (m.entrant_two, m.entrant_two_ready_at),
+            ]
+            .into_iter()
+            .enumerate()
+            .filter_map(|(slot, (player, ready))| Some((slot, player?)).filter(|_| ready.is_none()))
+            .collect::<Vec<_>>();
+
+            if let Err(e) = self.prompt_dq(&tournament, &m, &absent).await {
+                warn!(error = ?e, tournament = ?tournament.id, "failed to ask for a DQ");
+                continue;
+            }
+
+            let mut model = m.into_active_model();
+            model.dq_prompted_at = Set(Some(now));
+            model.update(self.utils.db_ref()).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Posts a DQ button for every absent player in the tournament's channel, and pings the
+    /// organizer.
+    async fn prompt_dq(
+        &self,
+        tournament: &tournament::Model,
+        m: &tournament_match::Model,
+        absent: &[(usize, Uuid)],
+    ) -> anyhow::Result<()> {
+        let channel = tournament
+            .channel
+            .as_ref()
+            .ok_or_else(|| anyhow!("the tournament doesn't have a channel"))?
+            .into_id();
+
+        let organizer = self.utils.discord_user_id(tournament.created_by).await?;
+        let players = absent.iter().map(|(_, p)| *p).collect::<Vec<_>>();
+        let names = self.player_names(&players).await?;
+
+        let mut mentions = Vec::with_capacity(players.len());
+        for player in &players {
+            mentions.push(format!("<@{}>", self.utils.discord_user_id(*player).await?));
+        }
+
+        let buttons = absent
+            .iter()
+            .zip(&names)
+            .map(|((slot, _), name)| {
+                Component::Button(Button {
+                    custom_id: Some(format!("tournament:dq:{}:{}:{}", tournament.id, m.id, slot)),
+                    disabled: false,
+                    emoji: None,
+                    label: Some(format!("DQ {name}")),
+                    style: ButtonStyle::Danger,
+                    url: None,
+                })
+            })
+            .collect();
+
+        self.utils
+            .http_client
+            .create_message(channel)
+            .content(&format!(
+                "<@{}>, {} didn't check in for their {} match in **{}**.",
+                organizer,
+                mentions.join(" and "),
+                match_name(tournament, m.side, m.round),
+                tournament.name
+            ))?
+            .allowed_mentions(Some(
+                &AllowedMentionsBuilder::new().user_ids([organizer]).build(),
+            ))
+            .components(&[Component::ActionRow(ActionRow {
+                components: buttons,
+            })])?
+            .await?;
+
+        Ok(())
+    }
+
+    /// Handles the buttons on a called match: checking in, reporting the winner, and the
+    /// organizer's DQ button.
+    pub(super) async fn match_action(
+        &self,
+        data: &MessageComponentData,
+        tournament: &tournament::Model,
+        action: &str,
+        match_id: Option<&str>,
+        slot: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let member = data
+            .interaction
+            .member
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get member data for caller"))?;
+        let user = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+        let player = self.utils.find_or_create_user(user.id).await?.user_id;
+
+        let m = TournamentMatch::find_by_id(
+            match_id
+                .ok_or_else(|| anyhow!("no match found in the message"))?
+                .parse::<Uuid>()?,
+        )
+        .one(self.utils.db_ref())
+        .await?
+        .filter(|m| m.tournament == tournament.id)
+        .ok_or_else(|| anyhow!("that match no longer exists"))?;
+
+        if m.winner.is_some() {
+            return Err(anyhow!("this match has already been reported"));
+        }
+
+        let slot = slot.map(str::parse::<usize>).transpose()?;
+        let entrants = [m.entrant_one, m.entrant_two];
+        let is_player = entrants.contains(&Some(player));
+
+        let (content, components) = match (action, slot) {
+            ("ready", _) => {
+                if !is_player {
+                    return Err(anyhow!("only the players in this match can check in"));
+                }
+
+                let mut model = m.clone().into_active_model();
+                if m.entrant_one == Some(player) {
+                    model.entrant_one_ready_at = Set(Some(Utc::now()));
+                } else {
+                    model.entrant_two_ready_at = Set(Some(Utc::now()));
+                }
+                model.update(self.utils.db_ref()).await?;
+
+                self.utils
+                    .send_message(
+                        &data.interaction,
+                        &InteractionResponse {
+                            kind: InteractionResponseType::ChannelMessageWithSource,
+                            data: Some(
+                                InteractionResponseDataBuilder::new()
+                                    .content(format!("<@{}> is here.", user.id))
+                                    .allowed_mentions(AllowedMentionsBuilder::new().build())
+                                    .build(),
+                            ),
+                        },
+                    )
+                    .await?;
+
+                return Ok(());
+            }
+            ("win", Some(slot)) => {
+                let winner = entrants
+                    .get(slot)
+                    .copied()
+                    .flatten()
+                    .ok_or_else(|| anyhow!("that player isn't in this match"))?;
+
+                // Organizers decide the match right away, while a player's report waits for
+                // their opponent to confirm it.
+                let result = if self.is_organizer(tournament, member).await? {
+                    self.record_match_winner(tournament, &m, winner).await?
+                } else if is_player {
+                    let Some(result) = self
+                        .report_bracket_winner(tournament, &m, player, winner)
+                        .await?
+                    else {
+                        self.utils
+                            .send_message(
+                                &data.interaction,
+                                &InteractionResponse {
+                                    kind: InteractionResponseType::ChannelMessageWithSource,
+                                    data: Some(
+                                        InteractionResponseDataBuilder::new()
+                                            .content(
+                                                "Your result has been sent to your opponent to confirm.",
+                                            )
+                                            .flags(MessageFlags::EPHEMERAL)
+                                            .build(),
+                                    ),
+                                },
+                            )
+                            .await?;
+
+                        return Ok(());
+                    };
+
+                    result
+                } else {
+                    return Err(anyhow!("only the players in this match can report it"));
+                };
+
+                (
+                    format!(
+                        "<@{}> reported that <@{}> won. {}",
+                        user.id,
+                        self.utils.discord_user_id(winner).await?,
+                        result
+                    ),
+                    Vec::new(),
+                )
+            }
+            ("dq", Some(slot)) => {
+                if !self.is_organizer(tournament, member).await? {
+                    return Err(anyhow!("only tournament organizers can disqualify players"));
+                }
+
+                let disqualified = entrants.get(slot).copied().flatten();
+                let winner = entrants
+                    .get(1 - slot.min(1))
+                    .copied()
+                    .flatten()
+                    .ok_or_else(|| anyhow!("that player isn't in this match"))?;
+                let result = self.record_match_winner(tournament, &m, winner).await?;
+
+                let disqualified = match disqualified {
+                    Some(p) => format!("<@{}>", self.utils.discord_user_id(p).await?),
+                    None => "A player".to_string(),
+                };
+                (
+                    format!(
+                        "{} was disqualified by <@{}>. {}",
+                        disqualified, user.id, result
+                    ),
+                    Vec::new(),
+                )
+            }
+            _ => return Err(anyhow!("unknown match action \"{}\"", action)),
+        };
+
+        self.utils
+            .send_message(
+                &data.interaction,
+                &InteractionResponse {
+                    kind: InteractionResponseType::UpdateMessage,
+                    data: Some(
+                        InteractionResponseDataBuilder::new()
+                            .content(content)
+                            .components(components)
+                            .allowed_mentions(AllowedMentionsBuilder::new().build())
+                            .build(),
+                    ),
+                },
+            )
+            .await?;
+
+        if let Err(e) = self.refresh_bracket(tournament.id).await {
+            warn!(error = ?e, tournament = ?tournament.id, "failed to refresh the bracket image");
+        }
+
+        Ok(())
+    }
+
+    /// Records a result from a called match's buttons.
+    pub(super) async fn record_match_winner(
+        &self,
+        tournament: &tournament::Model,
+        m: &tournament_match::Model,
+        winner: Uuid,
+    ) -> anyhow::Result<String> {
+        let (seeds, bracket) = self.load_bracket(tournament).await?;
+        let id = usize::try_from(m.position)?;
+
+        if bracket.result(id).is_some() || !bracket.ready_matches().contains(&id) {
+            return Err(anyhow!("this match can't be reported right now"));
+        }
+
+        let winner = seeds
+            .iter()
+            .position(|s| *s == winner)
+            .ok_or_else(|| anyhow!("that player isn't in the bracket"))?;
+
+        self.record_bracket_result(tournament, &seeds, bracket, id, winner)
+            .await
+    }
+}
+
+pub(super) const DEFAULT_DQ_MINUTES: i32 = 10;
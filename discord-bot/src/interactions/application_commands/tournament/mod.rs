@@ -0,0 +1,9 @@
+mod bridge;
+mod calling;
+mod pools;
+mod registration;
+mod render;
+mod reporting;
+mod seeding;
+mod swiss;
+pub mod tournament_handler;
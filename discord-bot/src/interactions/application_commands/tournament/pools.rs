@@ -0,0 +1,318 @@
+use bot::{
+    entity::prelude::*,
+    league::{
+        schedule::round_robin,
+        standings::{standings, Scoring, SetResult, Standing, Tiebreaker},
+    },
+    tournament::pools::{advancing, assign_pools},
+};
+use chrono::Utc;
+use sea_orm::{prelude::*, IntoActiveModel, QueryOrder, Set};
+use twilight_model::{
+    application::interaction::application_command::CommandDataOption,
+    channel::message::MessageFlags,
+};
+use twilight_util::builder::embed::{EmbedBuilder, EmbedFieldBuilder};
+
+use crate::interactions::application_commands::{utils::string_option, ApplicationCommandData};
+
+use super::tournament_handler::TournamentCommandHandler;
+
+impl TournamentCommandHandler {
+    pub(super) async fn show_pools(
+        &self,
+        data: &ApplicationCommandData,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let tournament = self
+            .find_tournament(
+                data.guild_id,
+                string_option(options, "tournament")
+                    .ok_or_else(|| anyhow!("no tournament provided"))?,
+            )
+            .await?;
+
+        if tournament.format != TournamentFormat::Pools {
+            return Err(anyhow!("this tournament doesn't have pools"));
+        }
+
+        let embed = self.pools_embed(&tournament).await?;
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .embeds(&[embed.validate()?.build()])?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Splits the players into pools, and schedules a round robin inside every pool.
+    pub(super) async fn start_pools(
+        &self,
+        tournament: &tournament::Model,
+        players: &[Uuid],
+    ) -> anyhow::Result<()> {
+        let pool_count = pool_count(tournament, players.len())?;
+        let assignments = assign_pools(players.len(), pool_count);
+
+        for (player, pool) in players.iter().zip(&assignments) {
+            TournamentEntrant::update(tournament_entrant::ActiveModel {
+                tournament: Set(tournament.id),
+                player: Set(*player),
+                pool: Set(Some(i32::try_from(*pool)?)),
+                ..Default::default()
+            })
+            .exec(self.utils.db_ref())
+            .await?;
+        }
+
+        for pool in 0..pool_count {
+            let members = players
+                .iter()
+                .zip(&assignments)
+                .filter(|(_, p)| **p == pool)
+                .map(|(player, _)| *player)
+                .collect::<Vec<_>>();
+
+            for (round, pairings) in round_robin(&members, 1).into_iter().enumerate() {
+                for (one, two) in pairings {
+                    let m = tournament_pool_match::Model {
+                        id: Uuid::new_v4(),
+                        tournament: tournament.id,
+                        pool: i32::try_from(pool)?,
+                        round: i32::try_from(round + 1)?,
+                        entrant_one: one,
+                        entrant_two: two,
+                        winner: None,
+                        completed_at: None,
+                        reported_by: None,
+                        reported_winner: None,
+                    };
+
+                    TournamentPoolMatch::insert(m.into_active_model())
+                        .exec(self.utils.db_ref())
+                        .await?;
+                }
+            }
+        }
+
+        let embed = self.pools_embed(tournament).await?;
+        self.post(
+            tournament,
+            embed.description(
+                "Play everyone in your pool, and report each result with `/tournament report`.",
+            ),
+        )
+        .await
+    }
+
+    /// Reports the result of one of the caller's pool matches. The result is recorded once
+    /// the opponent or an organizer confirms it.
+    pub(super) async fn report_pool_match(
+        &self,
+        tournament: &tournament::Model,
+        m: tournament_pool_match::Model,
+        player: Uuid,
+        won: bool,
+    ) -> anyhow::Result<String> {
+        let opponent = if m.entrant_one == player {
+            m.entrant_two
+        } else {
+            m.entrant_one
+        };
+        let winner = if won { player } else { opponent };
+
+        // Reporting the same result as the opponent confirms it.
+        if m.reported_by.is_some_and(|r| r != player) && m.reported_winner == Some(winner) {
+            return self.record_pool_result(tournament, &m, winner).await;
+        }
+
+        let res = TournamentPoolMatch::update_many()
+            .set(tournament_pool_match::ActiveModel {
+                reported_by: Set(Some(player)),
+                reported_winner: Set(Some(winner)),
+                ..Default::default()
+            })
+            .filter(tournament_pool_match::Column::Id.eq(m.id))
+            .filter(tournament_pool_match::Column::Winner.is_null())
+            .exec(self.utils.db_ref())
+            .await?;
+
+        if res.rows_affected == 0 {
+            return Err(anyhow!("this match has already been reported"));
+        }
+
+        self.request_confirmation(tournament, None, ("pool", m.id), player, opponent, winner)
+            .await?;
+
+        Ok("Your result has been sent to your opponent to confirm.".to_string())
+    }
+
+    /// Records the winner of a pool match, and starts the bracket once every pool is done.
+    pub(super) async fn record_pool_result(
+        &self,
+        tournament: &tournament::Model,
+        m: &tournament_pool_match::Model,
+        winner: Uuid,
+    ) -> anyhow::Result<String> {
+        let res = TournamentPoolMatch::update_many()
+            .set(tournament_pool_match::ActiveModel {
+                winner: Set(Some(winner)),
+                completed_at: Set(Some(Utc::now())),
+                ..Default::default()
+            })
+            .filter(tournament_pool_match::Column::Id.eq(m.id))
+            .filter(tournament_pool_match::Column::Winner.is_null())
+            .exec(self.utils.db_ref())
+            .await?;
+
+        if res.rows_affected == 0 {
+            return Err(anyhow!("this match has already been reported"));
+        }
+
+        let unfinished = TournamentPoolMatch::find()
+            .filter(tournament_pool_match::Column::Tournament.eq(tournament.id))
+            .filter(tournament_pool_match::Column::Winner.is_null())
+            .count(self.utils.db_ref())
+            .await?;
+
+        // The pools are a single round, so finishing them moves the tournament on to the
+        // second, and only one of the last results to be recorded gets to start the bracket.
+        if unfinished > 0 || !self.advance(tournament, 2).await? {
+            return Ok("Your result has been recorded.".to_string());
+        }
+
+        let embed = self.pools_embed(tournament).await?;
+        self.post(
+            tournament,
+            embed.title(format!("{} final pools", tournament.name)),
+        )
+        .await?;
+
+        let tables = self
+            .pool_tables(tournament)
+            .await?
+            .into_iter()
+            .map(|table| table.into_iter().map(|s| s.entrant).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let seeds = advancing(
+            &tables,
+            usize::try_from(tournament.pool_advance.unwrap_or(2))?,
+        );
+
+        if seeds.len() < 2 {
+            self.complete(tournament).await?;
+            return Ok("Your result has been recorded, and the tournament is over.".to_string());
+        }
+
+        self.create_bracket(tournament, &seeds).await?;
+        Ok("Your result has been recorded, and the bracket has started.".to_string())
+    }
+
+    /// The standings of every pool, in pool order.
+    pub(super) async fn pool_tables(
+        &self,
+        tournament: &tournament::Model,
+    ) -> anyhow::Result<Vec<Vec<Standing<Uuid>>>> {
+        let entrants = TournamentEntrant::find()
+            .filter(tournament_entrant::Column::Tournament.eq(tournament.id))
+            .filter(tournament_entrant::Column::Pool.is_not_null())
+            .order_by_asc(tournament_entrant::Column::RegisteredAt)
+            .all(self.utils.db_ref())
+            .await?;
+
+        let results = TournamentPoolMatch::find()
+            .filter(tournament_pool_match::Column::Tournament.eq(tournament.id))
+            .filter(tournament_pool_match::Column::Winner.is_not_null())
+            .all(self.utils.db_ref())
+            .await?
+            .into_iter()
+            .map(|m| SetResult {
+                one: m.entrant_one,
+                two: m.entrant_two,
+                games: Some(if m.winner == Some(m.entrant_one) {
+                    (1, 0)
+                } else {
+                    (0, 1)
+                }),
+            })
+            .collect::<Vec<_>>();
+
+        let pool_count = tournament.pool_count.unwrap_or(0);
+        Ok((0..pool_count)
+            .map(|pool| {
+                let members = entrants
+                    .iter()
+                    .filter(|e| e.pool == Some(pool))
+                    .map(|e| e.player)
+                    .collect::<Vec<_>>();
+
+                standings(
+                    &members,
+                    &results,
+                    &Scoring::default(),
+                    &Tiebreaker::DEFAULT_ORDER,
+                )
+            })
+            .collect())
+    }
+
+    async fn pools_embed(&self, tournament: &tournament::Model) -> anyhow::Result<EmbedBuilder> {
+        let tables = self.pool_tables(tournament).await?;
+        let ids = self
+            .discord_ids(
+                tables
+                    .iter()
+                    .flat_map(|t| t.iter().map(|s| s.entrant))
+                    .collect(),
+            )
+            .await?;
+
+        let mut embed = EmbedBuilder::new().title(format!("{} pools", tournament.name));
+        if tables.iter().all(Vec::is_empty) {
+            return Ok(embed.description("The pools haven't been drawn yet."));
+        }
+
+        for (pool, table) in tables.iter().enumerate() {
+            let lines = table
+                .iter()
+                .enumerate()
+                .map(|(i, s)| {
+                    let player = ids
+                        .get(&s.entrant)
+                        .map_or_else(|| "Unknown player".to_string(), |id| format!("<@{id}>"));
+                    format!("{}. {} {}-{}", i + 1, player, s.wins, s.losses)
+                })
+                .collect::<Vec<_>>();
+
+            embed = embed.field(EmbedFieldBuilder::new(pool_name(pool), lines.join("\n")).inline());
+        }
+
+        Ok(embed)
+    }
+}
+
+/// The number of pools, as long as every pool gets at least two entrants.
+pub(super) fn pool_count(tournament: &tournament::Model, entrants: usize) -> anyhow::Result<usize> {
+    let pools = usize::try_from(tournament.pool_count.unwrap_or(2))?;
+    if pools == 0 || pools > entrants / 2 {
+        return Err(anyhow!(
+            "{} pools need at least {} entrants",
+            pools,
+            pools.max(1) * 2
+        ));
+    }
+
+    Ok(pools)
+}
+
+/// Pools are named A, B, C and so on.
+pub(super) fn pool_name(pool: usize) -> String {
+    match u8::try_from(pool) {
+        Ok(p) if p < 26 => format!("Pool {}", char::from(b'A' + p)),
+        _ => format!("Pool {}", pool + 1),
+    }
+}
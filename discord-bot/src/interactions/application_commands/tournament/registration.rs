@@ -0,0 +1,377 @@
+use bot::entity::prelude::*;
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{prelude::*, IntoActiveModel, QueryOrder, QuerySelect, Set, TransactionTrait};
+use twilight_model::{
+    application::interaction::application_command::CommandDataOption,
+    channel::message::{
+        allowed_mentions::AllowedMentionsBuilder,
+        component::{ActionRow, Button, ButtonStyle},
+        Component, MessageFlags,
+    },
+    guild::PartialMember,
+};
+use twilight_util::builder::embed::{EmbedBuilder, EmbedFieldBuilder};
+
+use crate::interactions::application_commands::{utils::string_option, ApplicationCommandData};
+
+use super::tournament_handler::{describe_tournament, TournamentCommandHandler};
+
+impl TournamentCommandHandler {
+    pub(super) async fn register(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let tournament = self
+            .find_tournament(
+                data.guild_id,
+                string_option(options, "tournament")
+                    .ok_or_else(|| anyhow!("no tournament provided"))?,
+            )
+            .await?;
+
+        if tournament.state != TournamentState::Registration {
+            return Err(anyhow!("registration for this tournament is closed"));
+        }
+
+        let user = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+        let player = self.utils.find_or_create_user(user.id).await?;
+
+        let content = if self.add_entrant(&tournament, player.user_id).await? {
+            format!(
+                "**{}** is full, so you are on the waitlist. You'll get a spot if someone leaves.",
+                tournament.name
+            )
+        } else {
+            format!("You are registered for **{}**.", tournament.name)
+        };
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .content(&content)?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        self.update_panel(&tournament).await
+    }
+
+    pub(super) async fn check_in(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let tournament = self
+            .find_tournament(
+                data.guild_id,
+                string_option(options, "tournament")
+                    .ok_or_else(|| anyhow!("no tournament provided"))?,
+            )
+            .await?;
+
+        if tournament.state != TournamentState::Registration {
+            return Err(anyhow!("this tournament has already started"));
+        }
+
+        let opens = checkin_opens(&tournament)
+            .ok_or_else(|| anyhow!("this tournament doesn't use check-in"))?;
+        if Utc::now() < opens {
+            return Err(anyhow!("check-in opens <t:{}:R>", opens.timestamp()));
+        }
+        // Check-in closes at the start time, even if an organizer starts the tournament later.
+        if tournament
+            .starts_at
+            .is_some_and(|starts_at| Utc::now() >= starts_at)
+        {
+            return Err(anyhow!("check-in for this tournament has closed"));
+        }
+
+        let user = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+        let player = self.utils.find_or_create_user(user.id).await?;
+
+        let entrant = TournamentEntrant::find_by_id((tournament.id, player.user_id))
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("you are not registered for this tournament"))?;
+
+        if entrant.checked_in_at.is_some() {
+            return Err(anyhow!("you have already checked in"));
+        }
+
+        let waitlisted = entrant.waitlisted;
+        let mut model = entrant.into_active_model();
+        model.checked_in_at = Set(Some(Utc::now()));
+        model.update(self.utils.db_ref()).await?;
+
+        let content = if waitlisted {
+            format!(
+                "You are checked in for **{}**, and will play if a spot opens up before it starts.",
+                tournament.name
+            )
+        } else {
+            format!("You are checked in for **{}**.", tournament.name)
+        };
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .content(&content)?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        self.update_panel(&tournament).await
+    }
+
+    /// Registers a player, and returns whether they were put on the waitlist.
+    pub(super) async fn add_entrant(
+        &self,
+        tournament: &tournament::Model,
+        player: Uuid,
+    ) -> anyhow::Result<bool> {
+        // Locking the tournament makes players who join at the same time wait for each
+        // other, so that they can't all take the last spot.
+        let txn = self.utils.db_ref().begin().await?;
+        let tournament = Tournament::find_by_id(tournament.id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or_else(|| anyhow!("could not find the tournament"))?;
+
+        if tournament.state != TournamentState::Registration {
+            return Err(anyhow!("registration for this tournament is closed"));
+        }
+
+        let existing = TournamentEntrant::find_by_id((tournament.id, player))
+            .one(&txn)
+            .await?;
+
+        if existing.is_some() {
+            return Err(anyhow!("you are already registered for this tournament"));
+        }
+
+        let waitlisted = match tournament.max_entrants {
+            Some(cap) => {
+                let registered = TournamentEntrant::find()
+                    .filter(tournament_entrant::Column::Tournament.eq(tournament.id))
+                    .filter(tournament_entrant::Column::Waitlisted.eq(false))
+                    .count(&txn)
+                    .await?;
+                registered >= u64::try_from(cap)?
+            }
+            None => false,
+        };
+
+        let entrant = tournament_entrant::Model {
+            tournament: tournament.id,
+            player,
+            seed: None,
+            pool: None,
+            registered_at: Utc::now(),
+            waitlisted,
+            checked_in_at: None,
+            remote_id: None,
+        };
+
+        TournamentEntrant::insert(entrant.into_active_model())
+            .exec(&txn)
+            .await?;
+        txn.commit().await?;
+
+        Ok(waitlisted)
+    }
+
+    /// Unregisters a player. If they had a spot, it goes to the first player on the
+    /// waitlist.
+    pub(super) async fn remove_entrant(
+        &self,
+        tournament: &tournament::Model,
+        player: Uuid,
+    ) -> anyhow::Result<()> {
+        // The tournament is locked like in `add_entrant`, so that nobody who joins at the
+        // same time can take the freed spot before the waitlist gets it.
+        let txn = self.utils.db_ref().begin().await?;
+        let tournament = Tournament::find_by_id(tournament.id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or_else(|| anyhow!("could not find the tournament"))?;
+
+        if tournament.state != TournamentState::Registration {
+            return Err(anyhow!("this tournament has already started"));
+        }
+
+        let entrant = TournamentEntrant::find_by_id((tournament.id, player))
+            .one(&txn)
+            .await?
+            .ok_or_else(|| anyhow!("you are not registered for this tournament"))?;
+
+        let had_spot = !entrant.waitlisted;
+        entrant.delete(&txn).await?;
+
+        let next = if had_spot {
+            TournamentEntrant::find()
+                .filter(tournament_entrant::Column::Tournament.eq(tournament.id))
+                .filter(tournament_entrant::Column::Waitlisted.eq(true))
+                .order_by_asc(tournament_entrant::Column::RegisteredAt)
+                .one(&txn)
+                .await?
+        } else {
+            None
+        };
+
+        let Some(next) = next else {
+            txn.commit().await?;
+            return Ok(());
+        };
+
+        let promoted = next.player;
+        let mut model = next.into_active_model();
+        model.waitlisted = Set(false);
+        model.update(&txn).await?;
+        txn.commit().await?;
+
+        let (Some(channel), Ok(id)) = (
+            tournament.channel.as_ref(),
+            self.utils.discord_user_id(promoted).await,
+        ) else {
+            return Ok(());
+        };
+
+        self.utils
+            .http_client
+            .create_message(channel.into_id())
+            .content(&format!(
+                "<@{}> got a spot in **{}** from the waitlist.",
+                id, tournament.name
+            ))?
+            .allowed_mentions(Some(&AllowedMentionsBuilder::new().user_ids([id]).build()))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Posts the registration panel in the tournament's channel.
+    pub(super) async fn post_panel(
+        &self,
+        tournament: tournament::Model,
+    ) -> anyhow::Result<tournament::Model> {
+        let Some(channel) = tournament.channel.as_ref() else {
+            return Ok(tournament);
+        };
+
+        let embed = self.panel_embed(&tournament).await?;
+        let msg = self
+            .utils
+            .http_client
+            .create_message(channel.into_id())
+            .embeds(&[embed.validate()?.build()])?
+            .components(&panel_buttons(&tournament))?
+            .await?
+            .model()
+            .await?;
+
+        let mut model = tournament.into_active_model();
+        model.panel_message = Set(Some(msg.id.into()));
+        Ok(model.update(self.utils.db_ref()).await?)
+    }
+
+    /// Refreshes the entrant counts on the registration panel, and removes its buttons once
+    /// registration closes.
+    pub(super) async fn update_panel(&self, tournament: &tournament::Model) -> anyhow::Result<()> {
+        let (Some(channel), Some(message)) = (
+            tournament.channel.as_ref(),
+            tournament.panel_message.as_ref(),
+        ) else {
+            return Ok(());
+        };
+
+        let embed = self.panel_embed(tournament).await?;
+        self.utils
+            .http_client
+            .update_message(channel.into_id(), message.into_id())
+            .embeds(Some(&[embed.validate()?.build()]))?
+            .components(Some(&panel_buttons(tournament)))?
+            .await?;
+
+        Ok(())
+    }
+
+    async fn panel_embed(&self, tournament: &tournament::Model) -> anyhow::Result<EmbedBuilder> {
+        let entrants = TournamentEntrant::find()
+            .filter(tournament_entrant::Column::Tournament.eq(tournament.id))
+            .all(self.utils.db_ref())
+            .await?;
+
+        let registered = entrants.iter().filter(|e| !e.waitlisted).count();
+        let waitlisted = entrants.len() - registered;
+
+        let mut embed = describe_tournament(tournament).field(
+            EmbedFieldBuilder::new(
+                "Entrants",
+                match tournament.max_entrants {
+                    Some(cap) => format!("{registered}/{cap}"),
+                    None => registered.to_string(),
+                },
+            )
+            .inline(),
+        );
+
+        if waitlisted > 0 {
+            embed =
+                embed.field(EmbedFieldBuilder::new("Waitlist", waitlisted.to_string()).inline());
+        }
+
+        if tournament.checkin_minutes.is_some() {
+            let checked_in = entrants
+                .iter()
+                .filter(|e| e.checked_in_at.is_some())
+                .count();
+            embed =
+                embed.field(EmbedFieldBuilder::new("Checked in", checked_in.to_string()).inline());
+        }
+
+        Ok(embed)
+    }
+}
+
+pub(super) fn checkin_opens(tournament: &tournament::Model) -> Option<DateTime<Utc>> {
+    Some(tournament.starts_at? - Duration::minutes(tournament.checkin_minutes?.into()))
+}
+
+/// The Join and Leave buttons, which are only shown while registration is open.
+fn panel_buttons(tournament: &tournament::Model) -> Vec<Component> {
+    if tournament.state != TournamentState::Registration {
+        return Vec::new();
+    }
+
+    vec![Component::ActionRow(ActionRow {
+        components: vec![
+            Component::Button(Button {
+                custom_id: Some(format!("tournament:join:{}", tournament.id)),
+                disabled: false,
+                emoji: None,
+                label: Some("Join".to_string()),
+                style: ButtonStyle::Success,
+                url: None,
+            }),
+            Component::Button(Button {
+                custom_id: Some(format!("tournament:leave:{}", tournament.id)),
+                disabled: false,
+                emoji: None,
+                label: Some("Leave".to_string()),
+                style: ButtonStyle::Danger,
+                url: None,
+            }),
+        ],
+    })]
+}
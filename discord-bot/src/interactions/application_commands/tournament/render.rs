@@ -0,0 +1,526 @@
+use std::collections::HashMap;
+
+use bot::{
+    entity::prelude::*,
+    tournament::{
+        export::{Export, Placement, Player, SetRecord, TournamentInfo},
+        render::{self, Table},
+        swiss,
+    },
+};
+use sea_orm::{prelude::*, IntoActiveModel, QueryOrder, Set};
+use twilight_model::{
+    application::interaction::application_command::CommandDataOption,
+    channel::message::{Embed, MessageFlags},
+    http::attachment::Attachment,
+};
+use twilight_util::builder::embed::{EmbedBuilder, ImageSource};
+
+use crate::interactions::application_commands::{utils::string_option, ApplicationCommandData};
+
+use super::{
+    bridge::source_name,
+    pools::pool_name,
+    reporting::match_name,
+    tournament_handler::{format_name, TournamentCommandHandler},
+};
+
+impl TournamentCommandHandler {
+    pub(super) async fn show_standings(
+        &self,
+        data: &ApplicationCommandData,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let tournament = self
+            .find_tournament(
+                data.guild_id,
+                string_option(options, "tournament")
+                    .ok_or_else(|| anyhow!("no tournament provided"))?,
+            )
+            .await?;
+
+        if tournament.format != TournamentFormat::Swiss {
+            return Err(anyhow!("only Swiss tournaments have standings"));
+        }
+
+        let embed = self.swiss_standings_embed(&tournament).await?;
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .embeds(&[embed.validate()?.build()])?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn show_bracket(
+        &self,
+        data: &ApplicationCommandData,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let tournament = self
+            .find_tournament(
+                data.guild_id,
+                string_option(options, "tournament")
+                    .ok_or_else(|| anyhow!("no tournament provided"))?,
+            )
+            .await?;
+
+        let image = self.render(&tournament).await?;
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .embeds(&[image_embed(&tournament)?])?
+            .attachments(&[Attachment::from_bytes(BRACKET_IMAGE.to_string(), image, 0)])?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn export_tournament(
+        &self,
+        data: &ApplicationCommandData,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let tournament = self
+            .find_tournament(
+                data.guild_id,
+                string_option(options, "tournament")
+                    .ok_or_else(|| anyhow!("no tournament provided"))?,
+            )
+            .await?;
+
+        if tournament.state == TournamentState::Registration {
+            return Err(anyhow!("\"{}\" hasn't started yet", tournament.name));
+        }
+
+        let export = self.export(&tournament).await?;
+        let standings = export.standings_markdown();
+
+        // The standings are shown in the reply too, so they can be copied straight into an
+        // announcement.
+        let content = if standings.chars().count() < 1990 {
+            format!("```md\n{standings}```")
+        } else {
+            "The standings are too long for a message, so they're in standings.md.".to_string()
+        };
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .content(&content)?
+            .attachments(&[
+                Attachment::from_bytes(
+                    "placements.csv".to_string(),
+                    export.placements_csv().into_bytes(),
+                    0,
+                ),
+                Attachment::from_bytes("sets.csv".to_string(), export.sets_csv().into_bytes(), 1),
+                Attachment::from_bytes(
+                    "results.json".to_string(),
+                    export.to_json()?.into_bytes(),
+                    2,
+                ),
+                Attachment::from_bytes("standings.md".to_string(), standings.into_bytes(), 3),
+            ])?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Collects the placements and every set of a tournament. Players who didn't make it
+    /// to the bracket are placed below it, by their Swiss standing or their place in their
+    /// pool.
+    async fn export(&self, tournament: &tournament::Model) -> anyhow::Result<Export> {
+        let entrants = TournamentEntrant::find()
+            .filter(tournament_entrant::Column::Tournament.eq(tournament.id))
+            .filter(tournament_entrant::Column::Waitlisted.eq(false))
+            .order_by_asc(tournament_entrant::Column::RegisteredAt)
+            .all(self.utils.db_ref())
+            .await?;
+
+        let ids = entrants.iter().map(|e| e.player).collect::<Vec<_>>();
+        let discord = self.discord_ids(ids.clone()).await?;
+        let players = ids
+            .iter()
+            .zip(self.player_names(&ids).await?)
+            .map(|(id, name)| {
+                let player = Player {
+                    name,
+                    discord_id: discord.get(id).map(|d| d.into_id().to_string()),
+                };
+                (*id, player)
+            })
+            .collect::<HashMap<_, _>>();
+        let player = |id: Uuid| {
+            players.get(&id).cloned().unwrap_or_else(|| Player {
+                name: "Unknown player".to_string(),
+                discord_id: None,
+            })
+        };
+        let sides = |entrants: [Option<Uuid>; 2], winner: Option<Uuid>| {
+            let winner = winner.and_then(|w| entrants.iter().position(|e| *e == Some(w)));
+            (entrants.map(|e| e.map(player)), winner)
+        };
+
+        let mut sets = Vec::new();
+
+        let swiss_matches = TournamentSwissMatch::find()
+            .filter(tournament_swiss_match::Column::Tournament.eq(tournament.id))
+            .order_by_asc(tournament_swiss_match::Column::Round)
+            .order_by_asc(tournament_swiss_match::Column::CompletedAt)
+            .all(self.utils.db_ref())
+            .await?;
+        for m in swiss_matches {
+            let (players, winner) = sides([Some(m.entrant_one), m.entrant_two], m.winner);
+            sets.push(SetRecord {
+                phase: "Swiss".to_string(),
+                round: format!("Round {}", m.round),
+                players,
+                winner,
+                score: None,
+                completed_at: m.completed_at,
+            });
+        }
+
+        let pool_matches = TournamentPoolMatch::find()
+            .filter(tournament_pool_match::Column::Tournament.eq(tournament.id))
+            .order_by_asc(tournament_pool_match::Column::Pool)
+            .order_by_asc(tournament_pool_match::Column::Round)
+            .all(self.utils.db_ref())
+            .await?;
+        for m in pool_matches {
+            let (players, winner) = sides([Some(m.entrant_one), Some(m.entrant_two)], m.winner);
+            sets.push(SetRecord {
+                phase: pool_name(usize::try_from(m.pool)?),
+                round: format!("Round {}", m.round),
+                players,
+                winner,
+                score: None,
+                completed_at: m.completed_at,
+            });
+        }
+
+        // Byes and matches that are still waiting for players aren't sets.
+        let bracket_matches = TournamentMatch::find()
+            .filter(tournament_match::Column::Tournament.eq(tournament.id))
+            .filter(tournament_match::Column::EntrantOne.is_not_null())
+            .filter(tournament_match::Column::EntrantTwo.is_not_null())
+            .order_by_asc(tournament_match::Column::CompletedAt)
+            .order_by_asc(tournament_match::Column::Position)
+            .all(self.utils.db_ref())
+            .await?;
+        for m in bracket_matches {
+            let mut round = match_name(tournament, m.side, m.round);
+            if let Some(first) = round.get_mut(..1) {
+                first.make_ascii_uppercase();
+            }
+
+            let (players, winner) = sides([m.entrant_one, m.entrant_two], m.winner);
+            sets.push(SetRecord {
+                phase: "Bracket".to_string(),
+                round,
+                players,
+                winner,
+                score: None,
+                completed_at: m.completed_at,
+            });
+        }
+
+        if let Some(source) = tournament.source {
+            let remote_sets = TournamentRemoteSet::find()
+                .filter(tournament_remote_set::Column::Tournament.eq(tournament.id))
+                .filter(tournament_remote_set::Column::EntrantOne.is_not_null())
+                .filter(tournament_remote_set::Column::EntrantTwo.is_not_null())
+                .order_by_asc(tournament_remote_set::Column::CompletedAt)
+                .all(self.utils.db_ref())
+                .await?;
+            for set in remote_sets {
+                let (players, winner) = sides([set.entrant_one, set.entrant_two], set.winner);
+                sets.push(SetRecord {
+                    phase: source_name(source).to_string(),
+                    round: set.round,
+                    players,
+                    winner,
+                    score: set
+                        .winner_games
+                        .zip(set.loser_games)
+                        .map(|(won, lost)| format!("{won}-{lost}")),
+                    completed_at: set.completed_at,
+                });
+            }
+        }
+
+        // Imported tournaments are placed by the service, which the bridge doesn't read.
+        let mut placements = Vec::new();
+        if tournament.source.is_none() {
+            let mut bracket_players = Vec::new();
+            let has_bracket = TournamentMatch::find()
+                .filter(tournament_match::Column::Tournament.eq(tournament.id))
+                .count(self.utils.db_ref())
+                .await?
+                > 0;
+            if has_bracket {
+                let (seeds, bracket) = self.load_bracket(tournament).await?;
+                placements.extend(
+                    bracket
+                        .placements()
+                        .into_iter()
+                        .map(|(entrant, place)| (seeds[entrant], place)),
+                );
+                bracket_players = seeds;
+            }
+
+            let groups = match tournament.format {
+                TournamentFormat::Swiss => {
+                    let (players, history) = self.swiss_history(tournament).await?;
+                    swiss::standings(players.len(), &history)
+                        .into_iter()
+                        .map(|s| vec![players[s.entrant]])
+                        .collect()
+                }
+                // Players who finished in the same place in different pools are tied.
+                TournamentFormat::Pools => {
+                    let tables = self.pool_tables(tournament).await?;
+                    let longest = tables.iter().map(Vec::len).max().unwrap_or(0);
+                    (0..longest)
+                        .map(|i| {
+                            tables
+                                .iter()
+                                .filter_map(|t| t.get(i))
+                                .map(|s| s.entrant)
+                                .collect()
+                        })
+                        .collect()
+                }
+                TournamentFormat::SingleElimination | TournamentFormat::DoubleElimination => {
+                    Vec::new()
+                }
+            };
+
+            let mut placed = bracket_players.len();
+            for group in groups {
+                let group = group
+                    .into_iter()
+                    .filter(|p| !bracket_players.contains(p))
+                    .collect::<Vec<Uuid>>();
+                let place = u32::try_from(placed + 1)?;
+                placed += group.len();
+                placements.extend(group.into_iter().map(|p| (p, place)));
+            }
+        }
+
+        let seeds = entrants
+            .iter()
+            .filter_map(|e| Some((e.player, u32::try_from(e.seed?).ok()?)))
+            .collect::<HashMap<_, _>>();
+        let placements = placements
+            .into_iter()
+            .map(|(p, placement)| Placement {
+                placement,
+                player: player(p),
+                seed: seeds.get(&p).copied(),
+            })
+            .collect();
+
+        let game = match tournament.game {
+            Some(game) => Game::find_by_id(game)
+                .one(self.utils.db_ref())
+                .await?
+                .map(|g| g.name),
+            None => None,
+        };
+
+        Ok(Export::new(
+            TournamentInfo {
+                name: tournament.name.clone(),
+                format: format_name(tournament),
+                game,
+                entrants: entrants.len(),
+                started_at: tournament.started_at,
+                completed: tournament.state == TournamentState::Completed,
+            },
+            placements,
+            sets,
+        ))
+    }
+
+    /// Draws the bracket once it exists, and the Swiss or pool standings before that.
+    async fn render(&self, tournament: &tournament::Model) -> anyhow::Result<Vec<u8>> {
+        if let Some(source) = tournament.source {
+            return Err(anyhow!(
+                "this bracket is hosted on {}, so it can be viewed there",
+                source_name(source)
+            ));
+        }
+
+        if tournament.state == TournamentState::Registration {
+            return Err(anyhow!("this tournament hasn't started yet"));
+        }
+
+        let has_bracket = TournamentMatch::find()
+            .filter(tournament_match::Column::Tournament.eq(tournament.id))
+            .count(self.utils.db_ref())
+            .await?
+            > 0;
+
+        let svg = if has_bracket {
+            let (seeds, bracket) = self.load_bracket(tournament).await?;
+            let names = self.player_names(&seeds).await?;
+            render::bracket_svg(&tournament.name, &bracket, &names)
+        } else {
+            match tournament.format {
+                TournamentFormat::Swiss => {
+                    let (players, history) = self.swiss_history(tournament).await?;
+                    let names = self.player_names(&players).await?;
+                    let rows = swiss::standings(players.len(), &history)
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, s)| {
+                            vec![
+                                (i + 1).to_string(),
+                                names[s.entrant].clone(),
+                                format!("{}-{}", s.wins, s.losses),
+                                s.buchholz.to_string(),
+                                format!("{:.1}%", s.opponent_win_rate * 100.0),
+                            ]
+                        })
+                        .collect();
+
+                    render::tables_svg(
+                        &format!("{} standings", tournament.name),
+                        &[Table {
+                            title: String::new(),
+                            header: ["#", "Player", "Record", "Buchholz", "OMW"]
+                                .map(String::from)
+                                .to_vec(),
+                            rows,
+                        }],
+                    )
+                }
+                TournamentFormat::Pools => {
+                    let tables = self.pool_tables(tournament).await?;
+                    let mut pools = Vec::with_capacity(tables.len());
+                    for (pool, table) in tables.into_iter().enumerate() {
+                        let players = table.iter().map(|s| s.entrant).collect::<Vec<_>>();
+                        let names = self.player_names(&players).await?;
+
+                        pools.push(Table {
+                            title: pool_name(pool),
+                            header: ["#", "Player", "Record"].map(String::from).to_vec(),
+                            rows: table
+                                .iter()
+                                .zip(names)
+                                .enumerate()
+                                .map(|(i, (s, name))| {
+                                    vec![
+                                        (i + 1).to_string(),
+                                        name,
+                                        format!("{}-{}", s.wins, s.losses),
+                                    ]
+                                })
+                                .collect(),
+                        });
+                    }
+
+                    render::tables_svg(&format!("{} pools", tournament.name), &pools)
+                }
+                TournamentFormat::SingleElimination | TournamentFormat::DoubleElimination => {
+                    return Err(anyhow!("the bracket hasn't been drawn yet"));
+                }
+            }
+        };
+
+        // Rasterizing is CPU bound, so it's kept off the async workers.
+        Ok(tokio::task::spawn_blocking(move || render::to_png(&svg)).await??)
+    }
+
+    /// Redraws the image in the tournament's channel, or posts it if it isn't there yet.
+    pub(super) async fn refresh_bracket(&self, tournament: Uuid) -> anyhow::Result<()> {
+        let tournament = Tournament::find_by_id(tournament)
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("that tournament no longer exists"))?;
+
+        let (Some(channel), None) = (tournament.channel.as_ref(), tournament.source) else {
+            return Ok(());
+        };
+
+        let embeds = [image_embed(&tournament)?];
+        let attachments = [Attachment::from_bytes(
+            BRACKET_IMAGE.to_string(),
+            self.render(&tournament).await?,
+            0,
+        )];
+
+        if let Some(message) = tournament.bracket_message.as_ref() {
+            // If the old image was deleted, a new one is posted instead.
+            let updated = self
+                .utils
+                .http_client
+                .update_message(channel.into_id(), message.into_id())
+                .embeds(Some(&embeds))?
+                .attachments(&attachments)?
+                .keep_attachment_ids(&[])
+                .await;
+
+            if updated.is_ok() {
+                return Ok(());
+            }
+        }
+
+        let msg = self
+            .utils
+            .http_client
+            .create_message(channel.into_id())
+            .embeds(&embeds)?
+            .attachments(&attachments)?
+            .await?
+            .model()
+            .await?;
+
+        let mut model = tournament.into_active_model();
+        model.bracket_message = Set(Some(msg.id.into()));
+        model.update(self.utils.db_ref()).await?;
+
+        Ok(())
+    }
+
+    /// The names of the players, for places where mentions don't work.
+    pub(super) async fn player_names(&self, players: &[Uuid]) -> anyhow::Result<Vec<String>> {
+        let ids = self.discord_ids(players.to_vec()).await?;
+
+        let mut names = Vec::with_capacity(players.len());
+        for player in players {
+            let name = match ids.get(player) {
+                Some(id) => self
+                    .utils
+                    .get_user(id.into_id())
+                    .await
+                    .map_or_else(|_| "Unknown player".to_string(), |u| u.name),
+                None => "Unknown player".to_string(),
+            };
+            names.push(name);
+        }
+
+        Ok(names)
+    }
+}
+
+const BRACKET_IMAGE: &str = "bracket.png";
+
+fn image_embed(tournament: &tournament::Model) -> anyhow::Result<Embed> {
+    Ok(EmbedBuilder::new()
+        .title(tournament.name.clone())
+        .image(ImageSource::attachment(BRACKET_IMAGE)?)
+        .validate()?
+        .build())
+}
@@ -0,0 +1,740 @@
+use bot::{
+    entity::prelude::*,
+    tournament::bracket::{Bracket, Format, Participant, Side},
+};
+use chrono::Utc;
+use sea_orm::{prelude::*, Condition, IntoActiveModel, QueryOrder, Set};
+use twilight_model::{
+    application::interaction::application_command::CommandDataOption,
+    channel::message::{
+        allowed_mentions::AllowedMentionsBuilder,
+        component::{ActionRow, Button, ButtonStyle},
+        Component, MessageFlags,
+    },
+    guild::PartialMember,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{
+    embed::{EmbedBuilder, EmbedFieldBuilder},
+    InteractionResponseDataBuilder,
+};
+
+use crate::interactions::application_commands::{
+    utils::{string_option, user_option},
+    ApplicationCommandData, MessageComponentData,
+};
+
+use super::tournament_handler::TournamentCommandHandler;
+
+impl TournamentCommandHandler {
+    pub(super) async fn report_result(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let tournament = self
+            .find_tournament(
+                data.guild_id,
+                string_option(options, "tournament")
+                    .ok_or_else(|| anyhow!("no tournament provided"))?,
+            )
+            .await?;
+
+        if tournament.state != TournamentState::InProgress {
+            return Err(anyhow!("this tournament is not in progress"));
+        }
+
+        let won = string_option(options, "result") == Some("won");
+
+        let user = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+        let player = self.utils.find_or_create_user(user.id).await?;
+
+        let swiss_match = TournamentSwissMatch::find()
+            .filter(tournament_swiss_match::Column::Tournament.eq(tournament.id))
+            .filter(tournament_swiss_match::Column::Winner.is_null())
+            .filter(
+                Condition::any()
+                    .add(tournament_swiss_match::Column::EntrantOne.eq(player.user_id))
+                    .add(tournament_swiss_match::Column::EntrantTwo.eq(player.user_id)),
+            )
+            .one(self.utils.db_ref())
+            .await?;
+
+        let opponent = match user_option(options, "opponent") {
+            Some(id) => Some(self.utils.find_or_create_user(id).await?.user_id),
+            None => None,
+        };
+
+        let pool_match = if swiss_match.is_none() {
+            let mut query = TournamentPoolMatch::find()
+                .filter(tournament_pool_match::Column::Tournament.eq(tournament.id))
+                .filter(tournament_pool_match::Column::Winner.is_null())
+                .filter(
+                    Condition::any()
+                        .add(tournament_pool_match::Column::EntrantOne.eq(player.user_id))
+                        .add(tournament_pool_match::Column::EntrantTwo.eq(player.user_id)),
+                );
+            if let Some(opponent) = opponent {
+                query = query.filter(
+                    Condition::any()
+                        .add(tournament_pool_match::Column::EntrantOne.eq(opponent))
+                        .add(tournament_pool_match::Column::EntrantTwo.eq(opponent)),
+                );
+            }
+
+            query
+                .order_by_asc(tournament_pool_match::Column::Round)
+                .one(self.utils.db_ref())
+                .await?
+        } else {
+            None
+        };
+
+        let content = if tournament.source.is_some() {
+            self.report_remote_set(&tournament, player.user_id, won, options)
+                .await?
+        } else if let Some(m) = swiss_match {
+            self.report_swiss_match(&tournament, m, player.user_id, won)
+                .await?
+        } else if let Some(m) = pool_match {
+            self.report_pool_match(&tournament, m, player.user_id, won)
+                .await?
+        } else {
+            self.report_bracket_match(&tournament, player.user_id, won)
+                .await?
+        };
+
+        if let Err(e) = self.refresh_bracket(tournament.id).await {
+            warn!(error = ?e, tournament = ?tournament.id, "failed to refresh the bracket image");
+        }
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .content(&content)?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Reports the result of the caller's match in the elimination bracket. The result is
+    /// recorded once the opponent or an organizer confirms it.
+    async fn report_bracket_match(
+        &self,
+        tournament: &tournament::Model,
+        player: Uuid,
+        won: bool,
+    ) -> anyhow::Result<String> {
+        let has_bracket = TournamentMatch::find()
+            .filter(tournament_match::Column::Tournament.eq(tournament.id))
+            .count(self.utils.db_ref())
+            .await?
+            > 0;
+
+        if !has_bracket {
+            return Err(anyhow!("you don't have a match to report"));
+        }
+
+        let (seeds, bracket) = self.load_bracket(tournament).await?;
+        let entrant = seeds
+            .iter()
+            .position(|s| *s == player)
+            .ok_or_else(|| anyhow!("you don't have a match to report"))?;
+
+        let (id, opponent) = bracket
+            .ready_matches()
+            .into_iter()
+            .find_map(|id| match bracket.slots(id)? {
+                [Participant::Entrant(a), Participant::Entrant(b)] if a == entrant => Some((id, b)),
+                [Participant::Entrant(a), Participant::Entrant(b)] if b == entrant => Some((id, a)),
+                _ => None,
+            })
+            .ok_or_else(|| anyhow!("you don't have a match to report"))?;
+
+        let m = TournamentMatch::find()
+            .filter(tournament_match::Column::Tournament.eq(tournament.id))
+            .filter(tournament_match::Column::Position.eq(i32::try_from(id)?))
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("you don't have a match to report"))?;
+        let winner = seeds[if won { entrant } else { opponent }];
+
+        Ok(self
+            .report_bracket_winner(tournament, &m, player, winner)
+            .await?
+            .unwrap_or_else(|| {
+                "Your result has been sent to your opponent to confirm.".to_string()
+            }))
+    }
+
+    /// Reports the winner of a bracket match for one of its players. Reporting the same
+    /// winner as the opponent confirms their report, and otherwise the opponent is asked to
+    /// confirm this one. Returns the outcome once the result is recorded.
+    pub(super) async fn report_bracket_winner(
+        &self,
+        tournament: &tournament::Model,
+        m: &tournament_match::Model,
+        player: Uuid,
+        winner: Uuid,
+    ) -> anyhow::Result<Option<String>> {
+        if m.reported_by.is_some_and(|r| r != player) && m.reported_winner == Some(winner) {
+            return Ok(Some(self.record_match_winner(tournament, m, winner).await?));
+        }
+
+        let opponent = if m.entrant_one == Some(player) {
+            m.entrant_two
+        } else {
+            m.entrant_one
+        }
+        .ok_or_else(|| anyhow!("your opponent isn't known yet"))?;
+
+        let res = TournamentMatch::update_many()
+            .set(tournament_match::ActiveModel {
+                reported_by: Set(Some(player)),
+                reported_winner: Set(Some(winner)),
+                ..Default::default()
+            })
+            .filter(tournament_match::Column::Id.eq(m.id))
+            .filter(tournament_match::Column::Winner.is_null())
+            .exec(self.utils.db_ref())
+            .await?;
+
+        if res.rows_affected == 0 {
+            return Err(anyhow!("this match has already been reported"));
+        }
+
+        self.request_confirmation(
+            tournament,
+            m.lobby,
+            ("bracket", m.id),
+            player,
+            opponent,
+            winner,
+        )
+        .await?;
+
+        Ok(None)
+    }
+
+    /// Asks the reporter's opponent to confirm a reported result, in the match's thread if
+    /// it has one. `result` is the kind of match and its ID, which the buttons carry.
+    pub(super) async fn request_confirmation(
+        &self,
+        tournament: &tournament::Model,
+        lobby: Option<Uuid>,
+        result: (&str, Uuid),
+        reporter: Uuid,
+        opponent: Uuid,
+        winner: Uuid,
+    ) -> anyhow::Result<()> {
+        let channel = match lobby {
+            Some(lobby) => MatchmakingLobbies::find_by_id(lobby)
+                .one(self.utils.db_ref())
+                .await?
+                .map(|l| l.channel_id),
+            None => None,
+        }
+        .or_else(|| tournament.channel.clone())
+        .ok_or_else(|| anyhow!("the tournament doesn't have a channel"))?;
+
+        let (kind, id) = result;
+        let confirmer = self.utils.discord_user_id(opponent).await?;
+
+        self.utils
+            .http_client
+            .create_message(channel.into_id())
+            .content(&format!(
+                "<@{}>, <@{}> reported that <@{}> won. Is that right?",
+                confirmer,
+                self.utils.discord_user_id(reporter).await?,
+                self.utils.discord_user_id(winner).await?,
+            ))?
+            .allowed_mentions(Some(
+                &AllowedMentionsBuilder::new().user_ids([confirmer]).build(),
+            ))
+            .components(&[Component::ActionRow(ActionRow {
+                components: vec![
+                    Component::Button(Button {
+                        custom_id: Some(format!(
+                            "tournament:confirm-{}:{}:{}",
+                            kind, tournament.id, id
+                        )),
+                        disabled: false,
+                        emoji: None,
+                        label: Some("Confirm".to_string()),
+                        style: ButtonStyle::Success,
+                        url: None,
+                    }),
+                    Component::Button(Button {
+                        custom_id: Some(format!(
+                            "tournament:dispute-{}:{}:{}",
+                            kind, tournament.id, id
+                        )),
+                        disabled: false,
+                        emoji: None,
+                        label: Some("Dispute".to_string()),
+                        style: ButtonStyle::Danger,
+                        url: None,
+                    }),
+                ],
+            })])?
+            .await?;
+
+        Ok(())
+    }
+
+    /// Handles the answer to a reported result from the reporter's opponent or an
+    /// organizer. Confirmed results are recorded, and disputed ones are cleared so they
+    /// can be reported again.
+    pub(super) async fn confirm_result(
+        &self,
+        data: &MessageComponentData,
+        tournament: &tournament::Model,
+        kind: &str,
+        id: Option<&str>,
+        confirmed: bool,
+    ) -> anyhow::Result<()> {
+        let member = data
+            .interaction
+            .member
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get member data for caller"))?;
+        let user = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+        let player = self.utils.find_or_create_user(user.id).await?.user_id;
+
+        let id: Uuid = id.ok_or_else(|| anyhow!("no match was given"))?.parse()?;
+        let content = match kind {
+            "bracket" => {
+                let m = TournamentMatch::find_by_id(id)
+                    .one(self.utils.db_ref())
+                    .await?
+                    .filter(|m| m.tournament == tournament.id)
+                    .ok_or_else(|| anyhow!("that match no longer exists"))?;
+
+                let (Some(reporter), Some(winner), None) =
+                    (m.reported_by, m.reported_winner, m.winner)
+                else {
+                    return Err(anyhow!("this result has already been handled"));
+                };
+                self.check_answer(
+                    tournament,
+                    member,
+                    player,
+                    reporter,
+                    [m.entrant_one, m.entrant_two],
+                )
+                .await?;
+
+                if confirmed {
+                    self.record_match_winner(tournament, &m, winner).await?
+                } else {
+                    TournamentMatch::update_many()
+                        .set(tournament_match::ActiveModel {
+                            reported_by: Set(None),
+                            reported_winner: Set(None),
+                            ..Default::default()
+                        })
+                        .filter(tournament_match::Column::Id.eq(m.id))
+                        .filter(tournament_match::Column::Winner.is_null())
+                        .exec(self.utils.db_ref())
+                        .await?;
+
+                    DISPUTED_RESULT.to_string()
+                }
+            }
+            "swiss" => {
+                let m = TournamentSwissMatch::find_by_id(id)
+                    .one(self.utils.db_ref())
+                    .await?
+                    .filter(|m| m.tournament == tournament.id)
+                    .ok_or_else(|| anyhow!("that match no longer exists"))?;
+
+                let (Some(reporter), Some(winner), None) =
+                    (m.reported_by, m.reported_winner, m.winner)
+                else {
+                    return Err(anyhow!("this result has already been handled"));
+                };
+                self.check_answer(
+                    tournament,
+                    member,
+                    player,
+                    reporter,
+                    [Some(m.entrant_one), m.entrant_two],
+                )
+                .await?;
+
+                if confirmed {
+                    self.record_swiss_result(tournament, m, winner).await?
+                } else {
+                    TournamentSwissMatch::update_many()
+                        .set(tournament_swiss_match::ActiveModel {
+                            reported_by: Set(None),
+                            reported_winner: Set(None),
+                            ..Default::default()
+                        })
+                        .filter(tournament_swiss_match::Column::Id.eq(m.id))
+                        .filter(tournament_swiss_match::Column::Winner.is_null())
+                        .exec(self.utils.db_ref())
+                        .await?;
+
+                    DISPUTED_RESULT.to_string()
+                }
+            }
+            "pool" => {
+                let m = TournamentPoolMatch::find_by_id(id)
+                    .one(self.utils.db_ref())
+                    .await?
+                    .filter(|m| m.tournament == tournament.id)
+                    .ok_or_else(|| anyhow!("that match no longer exists"))?;
+
+                let (Some(reporter), Some(winner), None) =
+                    (m.reported_by, m.reported_winner, m.winner)
+                else {
+                    return Err(anyhow!("this result has already been handled"));
+                };
+                self.check_answer(
+                    tournament,
+                    member,
+                    player,
+                    reporter,
+                    [Some(m.entrant_one), Some(m.entrant_two)],
+                )
+                .await?;
+
+                if confirmed {
+                    self.record_pool_result(tournament, &m, winner).await?
+                } else {
+                    TournamentPoolMatch::update_many()
+                        .set(tournament_pool_match::ActiveModel {
+                            reported_by: Set(None),
+                            reported_winner: Set(None),
+                            ..Default::default()
+                        })
+                        .filter(tournament_pool_match::Column::Id.eq(m.id))
+                        .filter(tournament_pool_match::Column::Winner.is_null())
+                        .exec(self.utils.db_ref())
+                        .await?;
+
+                    DISPUTED_RESULT.to_string()
+                }
+            }
+            _ => return Err(anyhow!("unknown kind of match \"{}\"", kind)),
+        };
+
+        self.utils
+            .send_message(
+                &data.interaction,
+                &InteractionResponse {
+                    kind: InteractionResponseType::UpdateMessage,
+                    data: Some(
+                        InteractionResponseDataBuilder::new()
+                            .content(content)
+                            .components([])
+                            .build(),
+                    ),
+                },
+            )
+            .await?;
+
+        if confirmed {
+            if let Err(e) = self.refresh_bracket(tournament.id).await {
+                warn!(error = ?e, tournament = ?tournament.id, "failed to refresh the bracket image");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Only the reporter's opponent or an organizer can confirm or dispute a result.
+    pub(super) async fn check_answer(
+        &self,
+        tournament: &tournament::Model,
+        member: &PartialMember,
+        player: Uuid,
+        reporter: Uuid,
+        entrants: [Option<Uuid>; 2],
+    ) -> anyhow::Result<()> {
+        let is_opponent = reporter != player && entrants.contains(&Some(player));
+        if !is_opponent && !self.is_organizer(tournament, member).await? {
+            return Err(anyhow!(
+                "only the reporter's opponent or an organizer can answer this"
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Records the winner of a bracket match, closes the match's thread, and calls the
+    /// matches that the result made playable.
+    pub(super) async fn record_bracket_result(
+        &self,
+        tournament: &tournament::Model,
+        seeds: &[Uuid],
+        mut bracket: Bracket,
+        id: usize,
+        winner: usize,
+    ) -> anyhow::Result<String> {
+        bracket.report(id, winner)?;
+
+        // Claiming the match first means that a result can only be recorded once, even when
+        // both players confirm at the same time.
+        let res = TournamentMatch::update_many()
+            .set(tournament_match::ActiveModel {
+                winner: Set(Some(seeds[winner])),
+                completed_at: Set(Some(Utc::now())),
+                ..Default::default()
+            })
+            .filter(tournament_match::Column::Tournament.eq(tournament.id))
+            .filter(tournament_match::Column::Position.eq(i32::try_from(id)?))
+            .filter(tournament_match::Column::Winner.is_null())
+            .exec(self.utils.db_ref())
+            .await?;
+
+        if res.rows_affected == 0 {
+            return Err(anyhow!("this match has already been reported"));
+        }
+
+        self.save_bracket(tournament, seeds, &bracket, Some(id))
+            .await?;
+
+        let lobby = TournamentMatch::find()
+            .filter(tournament_match::Column::Tournament.eq(tournament.id))
+            .filter(tournament_match::Column::Position.eq(i32::try_from(id)?))
+            .one(self.utils.db_ref())
+            .await?
+            .and_then(|m| m.lobby);
+        // The result is already saved, so a thread that can't be closed isn't an error.
+        if let Err(e) = self.close_lobby(lobby).await {
+            warn!(error = ?e, tournament = ?tournament.id, "failed to close a match thread");
+        }
+
+        if let Some(champion) = bracket.champion() {
+            let champion = self.utils.discord_user_id(seeds[champion]).await?;
+            self.post(
+                tournament,
+                EmbedBuilder::new()
+                    .title(format!("{} is over", tournament.name))
+                    .description(format!("Congratulations to <@{champion}>!")),
+            )
+            .await?;
+            self.complete(tournament).await?;
+
+            return Ok("Your result has been recorded, and the tournament is over.".to_string());
+        }
+
+        self.call_matches(tournament).await?;
+
+        Ok("Your result has been recorded.".to_string())
+    }
+
+    /// Seeds the players in order, and stores every match of a new elimination bracket.
+    /// Swiss tournaments always cut to a single elimination bracket.
+    pub(super) async fn create_bracket(
+        &self,
+        tournament: &tournament::Model,
+        players: &[Uuid],
+    ) -> anyhow::Result<()> {
+        // Only the players in the bracket keep a seed, so that players who didn't make it out
+        // of Swiss or pools aren't part of it.
+        TournamentEntrant::update_many()
+            .filter(tournament_entrant::Column::Tournament.eq(tournament.id))
+            .set(tournament_entrant::ActiveModel {
+                seed: Set(None),
+                ..Default::default()
+            })
+            .exec(self.utils.db_ref())
+            .await?;
+        self.save_seeds(tournament, players).await?;
+
+        let bracket = Bracket::new(bracket_format(tournament.format), players.len())?;
+
+        for m in bracket.matches() {
+            let model = tournament_match::Model {
+                id: Uuid::new_v4(),
+                tournament: tournament.id,
+                position: i32::try_from(m.id)?,
+                side: bracket_side(m.side),
+                round: i32::try_from(m.round)?,
+                entrant_one: None,
+                entrant_two: None,
+                winner: None,
+                completed_at: None,
+                lobby: None,
+                called_at: None,
+                entrant_one_ready_at: None,
+                entrant_two_ready_at: None,
+                dq_prompted_at: None,
+                reported_by: None,
+                reported_winner: None,
+            };
+
+            TournamentMatch::insert(model.into_active_model())
+                .exec(self.utils.db_ref())
+                .await?;
+        }
+
+        self.save_bracket(tournament, players, &bracket, None)
+            .await?;
+
+        let ids = self.discord_ids(players.to_vec()).await?;
+        let mention = |seed: usize| {
+            ids.get(&players[seed])
+                .map_or_else(|| "Unknown player".to_string(), |id| format!("<@{id}>"))
+        };
+
+        let lines = bracket
+            .ready_matches()
+            .into_iter()
+            .filter_map(|id| match bracket.slots(id)? {
+                [Participant::Entrant(a), Participant::Entrant(b)] => {
+                    Some(format!("{} vs {}", mention(a), mention(b)))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        self.post(
+            tournament,
+            EmbedBuilder::new()
+                .title(format!("{} bracket", tournament.name))
+                .field(EmbedFieldBuilder::new("First matches", lines.join("\n"))),
+        )
+        .await?;
+
+        self.call_matches(tournament).await
+    }
+
+    /// The seeded players of the bracket, and the bracket rebuilt from the reported results.
+    pub(super) async fn load_bracket(
+        &self,
+        tournament: &tournament::Model,
+    ) -> anyhow::Result<(Vec<Uuid>, Bracket)> {
+        let seeds = TournamentEntrant::find()
+            .filter(tournament_entrant::Column::Tournament.eq(tournament.id))
+            .filter(tournament_entrant::Column::Seed.is_not_null())
+            .order_by_asc(tournament_entrant::Column::Seed)
+            .all(self.utils.db_ref())
+            .await?
+            .into_iter()
+            .map(|e| e.player)
+            .collect::<Vec<_>>();
+
+        // Matches decided by a bye don't have a completion time, and aren't results.
+        let results = TournamentMatch::find()
+            .filter(tournament_match::Column::Tournament.eq(tournament.id))
+            .filter(tournament_match::Column::CompletedAt.is_not_null())
+            .all(self.utils.db_ref())
+            .await?
+            .into_iter()
+            .filter_map(|m| {
+                let winner = seeds.iter().position(|s| Some(*s) == m.winner)?;
+                Some((usize::try_from(m.position).ok()?, winner))
+            })
+            .collect::<Vec<_>>();
+
+        let bracket = Bracket::restore(bracket_format(tournament.format), seeds.len(), results)?;
+
+        Ok((seeds, bracket))
+    }
+
+    /// Copies the players and winners from the bracket engine into the stored matches.
+    /// `reported` is the match that was just reported, if any.
+    async fn save_bracket(
+        &self,
+        tournament: &tournament::Model,
+        seeds: &[Uuid],
+        bracket: &Bracket,
+        reported: Option<usize>,
+    ) -> anyhow::Result<()> {
+        let player = |p: Option<Participant>| match p {
+            Some(Participant::Entrant(e)) => seeds.get(e).copied(),
+            _ => None,
+        };
+
+        let stored = TournamentMatch::find()
+            .filter(tournament_match::Column::Tournament.eq(tournament.id))
+            .all(self.utils.db_ref())
+            .await?;
+
+        for m in stored {
+            let Ok(id) = usize::try_from(m.position) else {
+                continue;
+            };
+            let slots = bracket.slots(id);
+            let entrant_one = player(slots.map(|s| s[0]));
+            let entrant_two = player(slots.map(|s| s[1]));
+            let winner = player(bracket.winner(id));
+
+            if m.entrant_one == entrant_one
+                && m.entrant_two == entrant_two
+                && m.winner == winner
+                && reported != Some(id)
+            {
+                continue;
+            }
+
+            let mut model = m.into_active_model();
+            model.entrant_one = Set(entrant_one);
+            model.entrant_two = Set(entrant_two);
+            model.winner = Set(winner);
+            if reported == Some(id) {
+                model.completed_at = Set(Some(Utc::now()));
+            }
+            model.update(self.utils.db_ref()).await?;
+        }
+
+        Ok(())
+    }
+}
+
+pub(super) const DISPUTED_RESULT: &str =
+    "The result was disputed. Report it again, or ask a tournament organizer for help.";
+
+/// Parses a score like "2-1".
+pub(super) fn parse_score(score: &str) -> anyhow::Result<(u32, u32)> {
+    let (won, lost) = score
+        .split_once('-')
+        .ok_or_else(|| anyhow!("scores look like 2-1"))?;
+
+    Ok((won.trim().parse()?, lost.trim().parse()?))
+}
+
+/// A name for a bracket match's round, like "winners round 2".
+pub(super) fn match_name(tournament: &tournament::Model, side: BracketSide, round: i32) -> String {
+    match (side, tournament.format) {
+        (BracketSide::Winners, TournamentFormat::DoubleElimination) => {
+            format!("winners round {round}")
+        }
+        (BracketSide::Winners, _) => format!("round {round}"),
+        (BracketSide::Losers, _) => format!("losers round {round}"),
+        (BracketSide::GrandFinals, _) => "grand finals".to_string(),
+        (BracketSide::GrandFinalsReset, _) => "grand finals reset".to_string(),
+    }
+}
+
+fn bracket_format(format: TournamentFormat) -> Format {
+    match format {
+        TournamentFormat::DoubleElimination => Format::DoubleElimination,
+        TournamentFormat::SingleElimination | TournamentFormat::Swiss | TournamentFormat::Pools => {
+            Format::SingleElimination
+        }
+    }
+}
+
+fn bracket_side(side: Side) -> BracketSide {
+    match side {
+        Side::Winners => BracketSide::Winners,
+        Side::Losers => BracketSide::Losers,
+        Side::GrandFinals => BracketSide::GrandFinals,
+        Side::GrandFinalsReset => BracketSide::GrandFinalsReset,
+    }
+}
@@ -0,0 +1,394 @@
+use std::collections::HashMap;
+
+use bot::{entity::prelude::*, tournament::seeding};
+use sea_orm::{prelude::*, QueryOrder, Set};
+use twilight_model::{
+    application::interaction::application_command::CommandDataOption,
+    channel::message::{
+        component::{ActionRow, Button, ButtonStyle, SelectMenu, SelectMenuOption},
+        Component, MessageFlags,
+    },
+    guild::PartialMember,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{
+    embed::{EmbedBuilder, EmbedFooterBuilder},
+    InteractionResponseDataBuilder,
+};
+
+use crate::interactions::application_commands::{
+    utils::string_option, ApplicationCommandData, MessageComponentData,
+};
+
+use super::tournament_handler::TournamentCommandHandler;
+
+impl TournamentCommandHandler {
+    pub(super) async fn show_seeding(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let tournament = self
+            .find_tournament(
+                data.guild_id,
+                string_option(options, "tournament")
+                    .ok_or_else(|| anyhow!("no tournament provided"))?,
+            )
+            .await?;
+
+        if !self.is_organizer(&tournament, member).await? {
+            return Err(anyhow!("only tournament organizers can change seeding"));
+        }
+
+        if tournament.state != TournamentState::Registration {
+            return Err(anyhow!("this tournament has already started"));
+        }
+
+        let (embed, components) = self.seeding_panel(&tournament, None).await?;
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .embeds(&[embed.validate()?.build()])?
+            .components(&components)?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn seeding_action(
+        &self,
+        data: &MessageComponentData,
+        tournament: &tournament::Model,
+        action: &str,
+        player: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let member = data
+            .interaction
+            .member
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get member data for caller"))?;
+
+        if !self.is_organizer(tournament, member).await? {
+            return Err(anyhow!("only tournament organizers can change seeding"));
+        }
+
+        let selected = match action {
+            "seed-select" => data
+                .message
+                .values
+                .first()
+                .map(|v| v.parse::<Uuid>())
+                .transpose()?,
+            "seed-up" | "seed-down" => {
+                let player: Uuid = player
+                    .ok_or_else(|| anyhow!("pick a player to move first"))?
+                    .parse()?;
+                self.move_seed(tournament, player, action == "seed-up")
+                    .await?;
+                Some(player)
+            }
+            "seed-reset" => {
+                TournamentEntrant::update_many()
+                    .filter(tournament_entrant::Column::Tournament.eq(tournament.id))
+                    .set(tournament_entrant::ActiveModel {
+                        seed: Set(None),
+                        ..Default::default()
+                    })
+                    .exec(self.utils.db_ref())
+                    .await?;
+                None
+            }
+            "seed-lock" => {
+                let content = self.start(tournament).await?;
+
+                self.utils
+                    .send_message(
+                        &data.interaction,
+                        &InteractionResponse {
+                            kind: InteractionResponseType::UpdateMessage,
+                            data: Some(
+                                InteractionResponseDataBuilder::new()
+                                    .content(content)
+                                    .embeds([])
+                                    .components([])
+                                    .build(),
+                            ),
+                        },
+                    )
+                    .await?;
+
+                return Ok(());
+            }
+            _ => return Err(anyhow!("unknown seeding action \"{}\"", action)),
+        };
+
+        let (embed, components) = self.seeding_panel(tournament, selected).await?;
+
+        self.utils
+            .send_message(
+                &data.interaction,
+                &InteractionResponse {
+                    kind: InteractionResponseType::UpdateMessage,
+                    data: Some(
+                        InteractionResponseDataBuilder::new()
+                            .embeds([embed.validate()?.build()])
+                            .components(components)
+                            .build(),
+                    ),
+                },
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// The seeding panel, with a menu to pick a player and buttons to move them around.
+    async fn seeding_panel(
+        &self,
+        tournament: &tournament::Model,
+        selected: Option<Uuid>,
+    ) -> anyhow::Result<(EmbedBuilder, Vec<Component>)> {
+        let order = self.ensure_seeds(tournament).await?;
+        if order.is_empty() {
+            return Err(anyhow!("nobody has registered for this tournament yet"));
+        }
+
+        // Select menus are limited to 25 options, so long lists only show the seeds around
+        // the selected player.
+        let position = selected.and_then(|s| order.iter().position(|p| *p == s));
+        let start = position
+            .map_or(0, |p| p.saturating_sub(12))
+            .min(order.len().saturating_sub(25));
+        let window = start..order.len().min(start + 25);
+
+        let ids = self.discord_ids(order.clone()).await?;
+        let mut lines = Vec::with_capacity(window.len());
+        let mut options = Vec::with_capacity(window.len());
+
+        for seed in window.clone() {
+            let player = order[seed];
+            let Some(id) = ids.get(&player) else {
+                continue;
+            };
+
+            let name = self
+                .utils
+                .get_user(id.into_id())
+                .await
+                .map_or_else(|_| id.to_string(), |u| u.name);
+
+            lines.push(if position == Some(seed) {
+                format!("**{}. <@{}>**", seed + 1, id)
+            } else {
+                format!("{}. <@{}>", seed + 1, id)
+            });
+            options.push(SelectMenuOption {
+                default: position == Some(seed),
+                description: None,
+                emoji: None,
+                label: format!("{}. {}", seed + 1, name),
+                value: player.to_string(),
+            });
+        }
+
+        let embed = EmbedBuilder::new()
+            .title(format!("Seeding for {}", tournament.name))
+            .description(lines.join("\n"))
+            .footer(EmbedFooterBuilder::new(format!(
+                "Showing seeds {} to {} of {}",
+                window.start + 1,
+                window.end,
+                order.len()
+            )));
+
+        let target = |action: &str| match selected {
+            Some(player) => format!("tournament:{}:{}:{}", action, tournament.id, player),
+            None => format!("tournament:{}:{}", action, tournament.id),
+        };
+        let button = |custom_id: String, label: &str, style: ButtonStyle, disabled: bool| {
+            Component::Button(Button {
+                custom_id: Some(custom_id),
+                disabled,
+                emoji: None,
+                label: Some(label.to_string()),
+                style,
+                url: None,
+            })
+        };
+
+        let components = vec![
+            Component::ActionRow(ActionRow {
+                components: vec![Component::SelectMenu(SelectMenu {
+                    custom_id: format!("tournament:seed-select:{}", tournament.id),
+                    disabled: false,
+                    max_values: Some(1),
+                    min_values: Some(1),
+                    options,
+                    placeholder: Some("Pick a player to move".to_string()),
+                })],
+            }),
+            Component::ActionRow(ActionRow {
+                components: vec![
+                    button(
+                        target("seed-up"),
+                        "Move up",
+                        ButtonStyle::Primary,
+                        position.is_none_or(|p| p == 0),
+                    ),
+                    button(
+                        target("seed-down"),
+                        "Move down",
+                        ButtonStyle::Primary,
+                        position.is_none_or(|p| p + 1 == order.len()),
+                    ),
+                    button(
+                        format!("tournament:seed-reset:{}", tournament.id),
+                        "Reset to ratings",
+                        ButtonStyle::Secondary,
+                        false,
+                    ),
+                    button(
+                        format!("tournament:seed-lock:{}", tournament.id),
+                        "Lock bracket and start",
+                        ButtonStyle::Success,
+                        false,
+                    ),
+                ],
+            }),
+        ];
+
+        Ok((embed, components))
+    }
+
+    /// Seeds the registered players, keeping the seeds that are already set, and returns
+    /// them in seed order.
+    async fn ensure_seeds(&self, tournament: &tournament::Model) -> anyhow::Result<Vec<Uuid>> {
+        let entrants = TournamentEntrant::find()
+            .filter(tournament_entrant::Column::Tournament.eq(tournament.id))
+            .filter(tournament_entrant::Column::Waitlisted.eq(false))
+            .order_by_asc(tournament_entrant::Column::RegisteredAt)
+            .all(self.utils.db_ref())
+            .await?;
+
+        let players = entrants.iter().map(|e| e.player).collect();
+        let order = self.seed_players(tournament, &entrants, players).await?;
+        self.save_seeds(tournament, &order).await?;
+
+        Ok(order)
+    }
+
+    async fn move_seed(
+        &self,
+        tournament: &tournament::Model,
+        player: Uuid,
+        up: bool,
+    ) -> anyhow::Result<()> {
+        let mut order = self.ensure_seeds(tournament).await?;
+        let position = order
+            .iter()
+            .position(|p| *p == player)
+            .ok_or_else(|| anyhow!("that player is no longer registered"))?;
+
+        let other = if up {
+            position.checked_sub(1)
+        } else {
+            Some(position + 1).filter(|p| *p < order.len())
+        };
+
+        if let Some(other) = other {
+            order.swap(position, other);
+            self.save_seeds(tournament, &order).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Orders the players for the bracket. Seeds that an organizer set in the seeding panel
+    /// are kept, and everyone else is seeded below them by their rating for the game. When
+    /// teams are separated, first round opponents from the same team are moved apart.
+    pub(super) async fn seed_players(
+        &self,
+        tournament: &tournament::Model,
+        entrants: &[tournament_entrant::Model],
+        players: Vec<Uuid>,
+    ) -> anyhow::Result<Vec<Uuid>> {
+        let seeds = entrants
+            .iter()
+            .filter_map(|e| Some((e.player, e.seed?)))
+            .collect::<HashMap<_, _>>();
+
+        let (mut seeded, unseeded): (Vec<_>, Vec<_>) =
+            players.into_iter().partition(|p| seeds.contains_key(p));
+        seeded.sort_by_key(|p| seeds[p]);
+
+        let mut ratings = Vec::with_capacity(unseeded.len());
+        for player in unseeded {
+            let rating = self
+                .utils
+                .get_rating(tournament.guild_id.into_id(), player, tournament.game)
+                .await?;
+            ratings.push((player, rating));
+        }
+        seeded.extend(seeding::by_rating(ratings, &mut rand::thread_rng()));
+
+        // Teammates are kept apart on every pass, since players who register late or drop
+        // out change who meets in the first round.
+        if tournament.separate_teams {
+            let teams = self.league_teams(tournament, &seeded).await?;
+            seeding::separate(&mut seeded, |a, b| match (teams.get(a), teams.get(b)) {
+                (Some(a), Some(b)) => a.iter().any(|t| b.contains(t)),
+                _ => false,
+            });
+        }
+
+        Ok(seeded)
+    }
+
+    pub(super) async fn save_seeds(
+        &self,
+        tournament: &tournament::Model,
+        order: &[Uuid],
+    ) -> anyhow::Result<()> {
+        for (seed, player) in order.iter().enumerate() {
+            TournamentEntrant::update(tournament_entrant::ActiveModel {
+                tournament: Set(tournament.id),
+                player: Set(*player),
+                seed: Set(Some(i32::try_from(seed + 1)?)),
+                ..Default::default()
+            })
+            .exec(self.utils.db_ref())
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// The league teams of every player, in any of the guild's leagues.
+    async fn league_teams(
+        &self,
+        tournament: &tournament::Model,
+        players: &[Uuid],
+    ) -> anyhow::Result<HashMap<Uuid, Vec<Uuid>>> {
+        let leagues = League::find()
+            .filter(league::Column::GuildId.eq(tournament.guild_id.clone()))
+            .all(self.utils.db_ref())
+            .await?;
+
+        let members = LeagueTeamMember::find()
+            .filter(league_team_member::Column::League.is_in(leagues.iter().map(|l| l.id)))
+            .filter(league_team_member::Column::Player.is_in(players.iter().copied()))
+            .all(self.utils.db_ref())
+            .await?;
+
+        let mut teams: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for member in members {
+            teams.entry(member.player).or_default().push(member.team);
+        }
+
+        Ok(teams)
+    }
+}
@@ -0,0 +1,356 @@
+use std::collections::HashMap;
+
+use bot::{
+    entity::prelude::*,
+    tournament::swiss::{self, Pairing},
+};
+use chrono::Utc;
+use sea_orm::{prelude::*, IntoActiveModel, QueryOrder, Set};
+use twilight_model::channel::message::allowed_mentions::AllowedMentionsBuilder;
+use twilight_util::builder::embed::EmbedBuilder;
+
+use super::tournament_handler::TournamentCommandHandler;
+
+impl TournamentCommandHandler {
+    /// Reports the result of the caller's Swiss match. The result is recorded once the
+    /// opponent or an organizer confirms it.
+    pub(super) async fn report_swiss_match(
+        &self,
+        tournament: &tournament::Model,
+        m: tournament_swiss_match::Model,
+        player: Uuid,
+        won: bool,
+    ) -> anyhow::Result<String> {
+        let opponent = if m.entrant_one == player {
+            m.entrant_two
+        } else {
+            Some(m.entrant_one)
+        }
+        .ok_or_else(|| anyhow!("you have a bye this round"))?;
+        let winner = if won { player } else { opponent };
+
+        // Reporting the same result as the opponent confirms it.
+        if m.reported_by.is_some_and(|r| r != player) && m.reported_winner == Some(winner) {
+            return self.record_swiss_result(tournament, m, winner).await;
+        }
+
+        let res = TournamentSwissMatch::update_many()
+            .set(tournament_swiss_match::ActiveModel {
+                reported_by: Set(Some(player)),
+                reported_winner: Set(Some(winner)),
+                ..Default::default()
+            })
+            .filter(tournament_swiss_match::Column::Id.eq(m.id))
+            .filter(tournament_swiss_match::Column::Winner.is_null())
+            .exec(self.utils.db_ref())
+            .await?;
+
+        if res.rows_affected == 0 {
+            return Err(anyhow!("this match has already been reported"));
+        }
+
+        self.request_confirmation(
+            tournament,
+            m.lobby,
+            ("swiss", m.id),
+            player,
+            opponent,
+            winner,
+        )
+        .await?;
+
+        Ok("Your result has been sent to your opponent to confirm.".to_string())
+    }
+
+    /// Records the result of a Swiss match, and moves on to the next round, the top cut, or
+    /// the end of the tournament once every match in the round has been played.
+    pub(super) async fn record_swiss_result(
+        &self,
+        tournament: &tournament::Model,
+        m: tournament_swiss_match::Model,
+        winner: Uuid,
+    ) -> anyhow::Result<String> {
+        let round = m.round;
+        let lobby = m.lobby;
+
+        let res = TournamentSwissMatch::update_many()
+            .set(tournament_swiss_match::ActiveModel {
+                winner: Set(Some(winner)),
+                completed_at: Set(Some(Utc::now())),
+                ..Default::default()
+            })
+            .filter(tournament_swiss_match::Column::Id.eq(m.id))
+            .filter(tournament_swiss_match::Column::Winner.is_null())
+            .exec(self.utils.db_ref())
+            .await?;
+
+        if res.rows_affected == 0 {
+            return Err(anyhow!("this match has already been reported"));
+        }
+
+        if let Some(lobby) = lobby {
+            if let Some(lobby) = MatchmakingLobbies::find_by_id(lobby)
+                .one(self.utils.db_ref())
+                .await?
+            {
+                self.background.close_lobby(&lobby).await?;
+            }
+        }
+
+        let unfinished = TournamentSwissMatch::find()
+            .filter(tournament_swiss_match::Column::Tournament.eq(tournament.id))
+            .filter(tournament_swiss_match::Column::Winner.is_null())
+            .count(self.utils.db_ref())
+            .await?;
+
+        // The last two results of a round can be recorded at the same time, and only one of
+        // them gets to move the tournament on.
+        if unfinished > 0 || !self.advance(tournament, round + 1).await? {
+            return Ok("Your result has been recorded.".to_string());
+        }
+
+        if round < tournament.swiss_rounds.unwrap_or(round) {
+            self.pair_swiss_round(tournament, round + 1).await?;
+            return Ok(format!(
+                "Your result has been recorded, and round {} has been paired.",
+                round + 1
+            ));
+        }
+
+        let standings = self.swiss_standings_embed(tournament).await?;
+        self.post(tournament, standings.title("Final Swiss standings"))
+            .await?;
+
+        if let Some(cut) = tournament.top_cut {
+            let (players, history) = self.swiss_history(tournament).await?;
+            let top = swiss::standings(players.len(), &history)
+                .into_iter()
+                .take(usize::try_from(cut)?)
+                .map(|s| players[s.entrant])
+                .collect::<Vec<_>>();
+
+            self.create_bracket(tournament, &top).await?;
+            return Ok("Your result has been recorded, and the top cut has started.".to_string());
+        }
+
+        self.complete(tournament).await?;
+        Ok("Your result has been recorded, and the tournament is over.".to_string())
+    }
+
+    /// Moves the tournament on to `round`, and returns whether it wasn't there already.
+    pub(super) async fn advance(
+        &self,
+        tournament: &tournament::Model,
+        round: i32,
+    ) -> anyhow::Result<bool> {
+        let res = Tournament::update_many()
+            .set(tournament::ActiveModel {
+                advanced_to: Set(round),
+                ..Default::default()
+            })
+            .filter(tournament::Column::Id.eq(tournament.id))
+            .filter(tournament::Column::AdvancedTo.lt(round))
+            .exec(self.utils.db_ref())
+            .await?;
+
+        Ok(res.rows_affected > 0)
+    }
+
+    /// Pairs a Swiss round, and opens a lobby thread for every match in it.
+    pub(super) async fn pair_swiss_round(
+        &self,
+        tournament: &tournament::Model,
+        round: i32,
+    ) -> anyhow::Result<()> {
+        let (players, history) = self.swiss_history(tournament).await?;
+        let pairings = swiss::pair(players.len(), &history);
+
+        let now = Utc::now();
+        let mut matches = Vec::with_capacity(pairings.len());
+        for p in pairings {
+            let m = tournament_swiss_match::Model {
+                id: Uuid::new_v4(),
+                tournament: tournament.id,
+                round,
+                entrant_one: players[p.one],
+                entrant_two: p.two.map(|two| players[two]),
+                winner: p.winner.map(|winner| players[winner]),
+                lobby: None,
+                completed_at: p.is_bye().then_some(now),
+                reported_by: None,
+                reported_winner: None,
+            };
+
+            matches.push(
+                TournamentSwissMatch::insert(m.into_active_model())
+                    .exec_with_returning(self.utils.db_ref())
+                    .await?,
+            );
+        }
+
+        let channel = tournament
+            .channel
+            .as_ref()
+            .ok_or_else(|| anyhow!("the tournament doesn't have a channel"))?
+            .into_id();
+
+        for m in matches {
+            let Some(two) = m.entrant_two else {
+                let one = self.utils.discord_user_id(m.entrant_one).await?;
+                self.utils
+                    .http_client
+                    .create_message(channel)
+                    .content(&format!(
+                        "**{}** round {}: <@{}> has a bye.",
+                        tournament.name, round, one
+                    ))?
+                    .allowed_mentions(Some(&AllowedMentionsBuilder::new().user_ids([one]).build()))
+                    .await?;
+                continue;
+            };
+
+            let title = format!("**{}** round {}", tournament.name, round);
+            if let Some(lobby) = self
+                .open_match(tournament, &title, m.entrant_one, two)
+                .await?
+            {
+                let mut model = m.into_active_model();
+                model.lobby = Set(Some(lobby));
+                model.update(self.utils.db_ref()).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Announces a match in the tournament's channel, and opens a lobby thread for it. A
+    /// match can still be reported without its thread, so failing to open one is only logged.
+    pub(super) async fn open_match(
+        &self,
+        tournament: &tournament::Model,
+        title: &str,
+        one: Uuid,
+        two: Uuid,
+    ) -> anyhow::Result<Option<Uuid>> {
+        let channel = tournament
+            .channel
+            .as_ref()
+            .ok_or_else(|| anyhow!("the tournament doesn't have a channel"))?
+            .into_id();
+
+        let one = self.utils.discord_user_id(one).await?;
+        let two = self.utils.discord_user_id(two).await?;
+
+        let msg = self
+            .utils
+            .http_client
+            .create_message(channel)
+            .content(&format!("{title}: <@{one}> vs <@{two}>"))?
+            .allowed_mentions(Some(
+                &AllowedMentionsBuilder::new().user_ids([one, two]).build(),
+            ))
+            .await?
+            .model()
+            .await?;
+
+        match self
+            .background
+            .open_lobby(
+                tournament.guild_id.into_id(),
+                msg.id,
+                one,
+                two,
+                tournament.game,
+            )
+            .await
+        {
+            Ok(lobby) => Ok(Some(lobby.id)),
+            Err(e) => {
+                warn!(error = ?e, tournament = ?tournament.id, "failed to open a lobby");
+                Ok(None)
+            }
+        }
+    }
+
+    /// The tournament's entrants in registration order, and every Swiss match played so far
+    /// in terms of indices into that list.
+    pub(super) async fn swiss_history(
+        &self,
+        tournament: &tournament::Model,
+    ) -> anyhow::Result<(Vec<Uuid>, Vec<Pairing>)> {
+        // Tied entrants are paired in this order, so the first round follows the seeding.
+        let players = TournamentEntrant::find()
+            .filter(tournament_entrant::Column::Tournament.eq(tournament.id))
+            .order_by_asc(tournament_entrant::Column::Seed)
+            .order_by_asc(tournament_entrant::Column::RegisteredAt)
+            .all(self.utils.db_ref())
+            .await?
+            .into_iter()
+            .map(|e| e.player)
+            .collect::<Vec<_>>();
+
+        let index = players
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (*p, i))
+            .collect::<HashMap<_, _>>();
+
+        // Matches against players who left the tournament are skipped.
+        let history = TournamentSwissMatch::find()
+            .filter(tournament_swiss_match::Column::Tournament.eq(tournament.id))
+            .all(self.utils.db_ref())
+            .await?
+            .into_iter()
+            .filter_map(|m| {
+                Some(Pairing {
+                    one: *index.get(&m.entrant_one)?,
+                    two: match m.entrant_two {
+                        Some(two) => Some(*index.get(&two)?),
+                        None => None,
+                    },
+                    winner: match m.winner {
+                        Some(winner) => Some(*index.get(&winner)?),
+                        None => None,
+                    },
+                })
+            })
+            .collect();
+
+        Ok((players, history))
+    }
+
+    pub(super) async fn swiss_standings_embed(
+        &self,
+        tournament: &tournament::Model,
+    ) -> anyhow::Result<EmbedBuilder> {
+        let (players, history) = self.swiss_history(tournament).await?;
+        let ids = self.discord_ids(players.clone()).await?;
+
+        let lines = swiss::standings(players.len(), &history)
+            .into_iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let player = ids
+                    .get(&players[s.entrant])
+                    .map_or_else(|| "Unknown player".to_string(), |id| format!("<@{id}>"));
+                format!(
+                    "{}. {} {}-{} (Buchholz {}, OMW {:.1}%)",
+                    i + 1,
+                    player,
+                    s.wins,
+                    s.losses,
+                    s.buchholz,
+                    s.opponent_win_rate * 100.0
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Ok(EmbedBuilder::new()
+            .title(format!("{} standings", tournament.name))
+            .description(if lines.is_empty() {
+                "Nobody has registered yet.".to_string()
+            } else {
+                lines.join("\n")
+            }))
+    }
+}
@@ -0,0 +1,822 @@
+use std::{collections::HashMap, sync::Arc};
+
+use bot::{
+    bridge::{startgg::StartGg, BracketService},
+    entity::prelude::*,
+    tournament::swiss,
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sea_orm::{prelude::*, IntoActiveModel, QueryOrder, Set};
+use twilight_model::{
+    application::{command::CommandType, interaction::application_command::CommandDataOption},
+    channel::{message::MessageFlags, ChannelType},
+    guild::PartialMember,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+    id::{
+        marker::{GuildMarker, UserMarker},
+        Id,
+    },
+};
+use twilight_util::builder::{
+    command::{
+        BooleanBuilder, ChannelBuilder, CommandBuilder, IntegerBuilder, StringBuilder,
+        SubCommandBuilder, UserBuilder,
+    },
+    embed::{EmbedBuilder, EmbedFieldBuilder},
+    InteractionResponseDataBuilder,
+};
+
+use crate::interactions::application_commands::{
+    matchmaking::BackgroundLoop,
+    utils::{boolean_option, channel_option, integer_option, string_option, subcommand_path},
+    ApplicationCommandData, CommandGroupDescriptor, CommonUtilities, InteractionHandler,
+    MessageComponentData, ModalSubmitData,
+};
+
+use super::{
+    bridge::source_name, calling::DEFAULT_DQ_MINUTES, pools::pool_count,
+    registration::checkin_opens,
+};
+
+#[derive(Clone)]
+pub struct TournamentCommandHandler {
+    pub(super) utils: Arc<CommonUtilities>,
+    pub(super) background: Arc<BackgroundLoop>,
+    pub(super) startgg: Option<Arc<dyn BracketService>>,
+}
+
+#[async_trait]
+impl InteractionHandler for TournamentCommandHandler {
+    fn describe(&self) -> CommandGroupDescriptor {
+        let services = [("start.gg", "startgg"), ("Challonge", "challonge")];
+        let tournament_option = || {
+            StringBuilder::new("tournament", "The name of the tournament")
+                .required(true)
+                .max_length(80)
+        };
+
+        let builder = CommandBuilder::new(
+            "tournament",
+            "Create, manage and play in tournaments",
+            CommandType::ChatInput,
+        )
+        .dm_permission(false)
+        .option(
+            SubCommandBuilder::new("create", "Create a new tournament")
+                .option(
+                    StringBuilder::new("name", "The name of the tournament")
+                        .required(true)
+                        .max_length(80),
+                )
+                .option(
+                    StringBuilder::new("format", "How the tournament is played")
+                        .required(true)
+                        .choices([
+                            ("Single elimination", "single-elimination"),
+                            ("Double elimination", "double-elimination"),
+                            ("Swiss", "swiss"),
+                            ("Round robin pools", "pools"),
+                        ]),
+                )
+                .option(
+                    StringBuilder::new("game", "The game that's played in this tournament")
+                        .max_length(80),
+                )
+                .option(
+                    ChannelBuilder::new(
+                        "channel",
+                        "Where pairings are posted (default: this channel)",
+                    )
+                    .channel_types([ChannelType::GuildText]),
+                )
+                .option(
+                    IntegerBuilder::new(
+                        "swiss-rounds",
+                        "The number of Swiss rounds (default: enough for one undefeated player)",
+                    )
+                    .min_value(1)
+                    .max_value(15),
+                )
+                .option(
+                    IntegerBuilder::new(
+                        "top-cut",
+                        "How many players advance from Swiss to a single elimination bracket",
+                    )
+                    .min_value(2)
+                    .max_value(64),
+                )
+                .option(
+                    IntegerBuilder::new("cap", "The most players who can enter (default: no cap)")
+                        .min_value(2)
+                        .max_value(512),
+                )
+                .option(StringBuilder::new(
+                    "start",
+                    "When the tournament starts, in UTC (YYYY-MM-DD HH:MM)",
+                ))
+                .option(
+                    IntegerBuilder::new(
+                        "checkin",
+                        "Open check-in this many minutes before the start (default: no check-in)",
+                    )
+                    .min_value(5)
+                    .max_value(1440),
+                )
+                .option(
+                    IntegerBuilder::new(
+                        "dq",
+                        "Minutes players have to show up for a bracket match (default: 10)",
+                    )
+                    .min_value(1)
+                    .max_value(120),
+                )
+                .option(BooleanBuilder::new(
+                    "separate-teams",
+                    "Keep league teammates apart in the first round (default: false)",
+                ))
+                .option(
+                    IntegerBuilder::new("pools", "The number of round robin pools (default: 2)")
+                        .min_value(2)
+                        .max_value(16),
+                )
+                .option(
+                    IntegerBuilder::new(
+                        "advance",
+                        "How many players advance from each pool to the bracket (default: 2)",
+                    )
+                    .min_value(1)
+                    .max_value(8),
+                ),
+        )
+        .option(
+            SubCommandBuilder::new("register", "Register for a tournament")
+                .option(tournament_option()),
+        )
+        .option(
+            SubCommandBuilder::new("checkin", "Check in for a tournament you registered for")
+                .option(tournament_option()),
+        )
+        .option(
+            SubCommandBuilder::new(
+                "seeding",
+                "Review and reorder seeds before the bracket is locked",
+            )
+            .option(tournament_option()),
+        )
+        .option(
+            SubCommandBuilder::new("start", "Close registration and start the first round")
+                .option(tournament_option()),
+        )
+        .option(
+            SubCommandBuilder::new("report", "Report the result of your current match")
+                .option(tournament_option())
+                .option(
+                    StringBuilder::new("result", "Whether you won or lost")
+                        .required(true)
+                        .choices([("Won", "won"), ("Lost", "lost")]),
+                )
+                .option(UserBuilder::new(
+                    "opponent",
+                    "Who you played, if you have more than one match to play",
+                ))
+                .option(
+                    StringBuilder::new(
+                        "score",
+                        "Your games won and lost, like 2-1, for brackets hosted on another site",
+                    )
+                    .max_length(7),
+                ),
+        )
+        .option(
+            SubCommandBuilder::new("standings", "Show the Swiss standings of a tournament")
+                .option(tournament_option()),
+        )
+        .option(
+            SubCommandBuilder::new("pools", "Show the pool tables of a tournament")
+                .option(tournament_option()),
+        )
+        .option(
+            SubCommandBuilder::new("bracket", "Show the bracket or standings as an image")
+                .option(tournament_option()),
+        )
+        .option(
+            SubCommandBuilder::new(
+                "export",
+                "Download the placements and sets as CSV, JSON and Markdown",
+            )
+            .option(tournament_option()),
+        )
+        .option(
+            SubCommandBuilder::new(
+                "import",
+                "Mirror an event that is run on start.gg or Challonge",
+            )
+            .option(
+                StringBuilder::new("name", "The name of the tournament in this server")
+                    .required(true)
+                    .max_length(80),
+            )
+            .option(
+                StringBuilder::new(
+                    "event",
+                    "The start.gg event slug, like tournament/my-weekly/event/singles, \
+                         or the Challonge link",
+                )
+                .required(true)
+                .max_length(200),
+            )
+            .option(
+                StringBuilder::new("service", "Where the event is run (default: start.gg)")
+                    .choices(services),
+            )
+            .option(
+                ChannelBuilder::new("channel", "Where sets are called")
+                    .channel_types([ChannelType::GuildText]),
+            ),
+        )
+        .option(
+            SubCommandBuilder::new(
+                "credentials",
+                "Set the API key this server uses for a bracket service",
+            )
+            .option(
+                StringBuilder::new("service", "The bracket service")
+                    .required(true)
+                    .choices(services),
+            )
+            .option(
+                StringBuilder::new("api-key", "The API key (to remove it, leave this empty)")
+                    .max_length(200),
+            ),
+        );
+
+        let command = builder.build();
+        CommandGroupDescriptor {
+            name: "tournament",
+            description: "Commands that are related to tournaments",
+            commands: Box::new([command]),
+        }
+    }
+
+    async fn process_command(&self, data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
+        let member = data
+            .interaction
+            .member
+            .as_ref()
+            .ok_or_else(|| anyhow!("command cannot be run in a DM"))?;
+
+        let (path, options) = subcommand_path(&data.command.options);
+
+        match path.as_slice() {
+            ["create"] => self.create_tournament(&data, member, options).await,
+            ["register"] => self.register(&data, member, options).await,
+            ["checkin"] => self.check_in(&data, member, options).await,
+            ["seeding"] => self.show_seeding(&data, member, options).await,
+            ["start"] => self.start_tournament(&data, member, options).await,
+            ["report"] => self.report_result(&data, member, options).await,
+            ["standings"] => self.show_standings(&data, options).await,
+            ["pools"] => self.show_pools(&data, options).await,
+            ["bracket"] => self.show_bracket(&data, options).await,
+            ["export"] => self.export_tournament(&data, options).await,
+            ["import"] => self.import_tournament(&data, member, options).await,
+            ["credentials"] => self.set_credentials(&data, member, options).await,
+            _ => Err(anyhow!("command handler for \"{:?}\" not found.", path)),
+        }
+    }
+
+    async fn process_autocomplete(&self, _data: Box<ApplicationCommandData>) -> anyhow::Result<()> {
+        unreachable!()
+    }
+
+    async fn process_modal(&self, _data: Box<ModalSubmitData>) -> anyhow::Result<()> {
+        unreachable!()
+    }
+
+    async fn process_component(&self, data: Box<MessageComponentData>) -> anyhow::Result<()> {
+        let user = data
+            .interaction
+            .member
+            .as_ref()
+            .and_then(|m| m.user.as_ref())
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+
+        let mut fields = data.action.split(':');
+        let action = fields.next().unwrap_or_default();
+        let tournament_id: Uuid = fields
+            .next()
+            .ok_or_else(|| anyhow!("no tournament found in the panel"))?
+            .parse()?;
+
+        let tournament = Tournament::find_by_id(tournament_id)
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("that tournament no longer exists"))?;
+
+        if action == "confirm" || action == "dispute" {
+            return self
+                .confirm_remote_set(&data, &tournament, fields.next(), action == "confirm")
+                .await;
+        }
+
+        if let Some((answer, kind)) = action.split_once('-') {
+            if answer == "confirm" || answer == "dispute" {
+                return self
+                    .confirm_result(&data, &tournament, kind, fields.next(), answer == "confirm")
+                    .await;
+            }
+        }
+
+        if matches!(action, "ready" | "win" | "dq") {
+            return self
+                .match_action(&data, &tournament, action, fields.next(), fields.next())
+                .await;
+        }
+
+        if action.starts_with("seed-") {
+            return self
+                .seeding_action(&data, &tournament, action, fields.next())
+                .await;
+        }
+
+        let player = self.utils.find_or_create_user(user.id).await?;
+
+        let content = match action {
+            "join" => {
+                if self.add_entrant(&tournament, player.user_id).await? {
+                    format!(
+                        "**{}** is full, so you are on the waitlist.",
+                        tournament.name
+                    )
+                } else {
+                    format!("You are registered for **{}**.", tournament.name)
+                }
+            }
+            "leave" => {
+                self.remove_entrant(&tournament, player.user_id).await?;
+                format!("You are no longer registered for **{}**.", tournament.name)
+            }
+            _ => return Err(anyhow!("unknown tournament action \"{}\"", action)),
+        };
+
+        self.utils
+            .send_message(
+                &data.interaction,
+                &InteractionResponse {
+                    kind: InteractionResponseType::ChannelMessageWithSource,
+                    data: Some(
+                        InteractionResponseDataBuilder::new()
+                            .content(content)
+                            .flags(MessageFlags::EPHEMERAL)
+                            .build(),
+                    ),
+                },
+            )
+            .await?;
+
+        self.update_panel(&tournament).await
+    }
+}
+
+impl TournamentCommandHandler {
+    pub fn new(utils: Arc<CommonUtilities>, background: Arc<BackgroundLoop>) -> Self {
+        let settings = &crate::CONFIG.startgg;
+        let startgg = settings.token.as_ref().map(|token| {
+            Arc::new(StartGg::new(settings.api_url.clone(), token.clone()))
+                as Arc<dyn BracketService>
+        });
+
+        Self {
+            utils,
+            background,
+            startgg,
+        }
+    }
+
+    async fn create_tournament(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        if !self.utils.is_guild_admin(data.guild_id, member).await? {
+            return Err(anyhow!("only admins can create tournaments"));
+        }
+
+        let name = string_option(options, "name").ok_or_else(|| anyhow!("no name provided"))?;
+        let format = match string_option(options, "format") {
+            Some("single-elimination") => TournamentFormat::SingleElimination,
+            Some("double-elimination") => TournamentFormat::DoubleElimination,
+            Some("swiss") => TournamentFormat::Swiss,
+            Some("pools") => TournamentFormat::Pools,
+            _ => return Err(anyhow!("no valid format provided")),
+        };
+
+        let swiss_rounds = integer_option(options, "swiss-rounds")
+            .map(i32::try_from)
+            .transpose()?;
+        let top_cut = integer_option(options, "top-cut")
+            .map(i32::try_from)
+            .transpose()?;
+
+        if format != TournamentFormat::Swiss && (swiss_rounds.is_some() || top_cut.is_some()) {
+            return Err(anyhow!(
+                "Swiss rounds and top cuts can only be used in Swiss tournaments"
+            ));
+        }
+
+        let pool_count = integer_option(options, "pools")
+            .map(i32::try_from)
+            .transpose()?;
+        let pool_advance = integer_option(options, "advance")
+            .map(i32::try_from)
+            .transpose()?;
+
+        let (pool_count, pool_advance) = if format == TournamentFormat::Pools {
+            (
+                Some(pool_count.unwrap_or(2)),
+                Some(pool_advance.unwrap_or(2)),
+            )
+        } else if pool_count.is_some() || pool_advance.is_some() {
+            return Err(anyhow!(
+                "pools can only be configured in round robin pool tournaments"
+            ));
+        } else {
+            (None, None)
+        };
+
+        let game = if let Some(name) = string_option(options, "game") {
+            Some(
+                Game::find()
+                    .filter(game::Column::Name.eq(name))
+                    .one(self.utils.db_ref())
+                    .await?
+                    .ok_or_else(|| anyhow!("\"{}\" is not a known game", name))?
+                    .id,
+            )
+        } else {
+            None
+        };
+
+        let existing = Tournament::find()
+            .filter(tournament::Column::GuildId.eq(IdWrapper::from(data.guild_id)))
+            .filter(tournament::Column::Name.eq(name))
+            .one(self.utils.db_ref())
+            .await?;
+
+        if existing.is_some() {
+            return Err(anyhow!("a tournament named \"{}\" already exists", name));
+        }
+
+        let max_entrants = integer_option(options, "cap")
+            .map(i32::try_from)
+            .transpose()?;
+        let starts_at = string_option(options, "start")
+            .map(parse_time)
+            .transpose()?;
+        let checkin_minutes = integer_option(options, "checkin")
+            .map(i32::try_from)
+            .transpose()?;
+
+        if checkin_minutes.is_some() && starts_at.is_none() {
+            return Err(anyhow!("check-in needs a start time"));
+        }
+
+        let dq_minutes = integer_option(options, "dq")
+            .map(i32::try_from)
+            .transpose()?
+            .unwrap_or(DEFAULT_DQ_MINUTES);
+
+        let channel = channel_option(options, "channel")
+            .or(data.interaction.channel_id)
+            .ok_or_else(|| anyhow!("command was not run in a channel"))?;
+
+        let user = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+        let organizer = self.utils.find_or_create_user(user.id).await?;
+
+        let tournament = tournament::Model {
+            id: Uuid::new_v4(),
+            guild_id: data.guild_id.into(),
+            name: name.to_string(),
+            game,
+            format,
+            state: TournamentState::Registration,
+            channel: Some(channel.into()),
+            swiss_rounds,
+            top_cut,
+            pool_count,
+            pool_advance,
+            max_entrants,
+            starts_at,
+            checkin_minutes,
+            dq_minutes: Some(dq_minutes),
+            panel_message: None,
+            separate_teams: boolean_option(options, "separate-teams").unwrap_or(false),
+            bracket_message: None,
+            source: None,
+            source_event: None,
+            created_by: organizer.user_id,
+            created_at: Utc::now(),
+            started_at: None,
+            advanced_to: 0,
+        };
+
+        let tournament = Tournament::insert(tournament.into_active_model())
+            .exec_with_returning(self.utils.db_ref())
+            .await?;
+
+        let tournament = self.post_panel(tournament).await?;
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .embeds(&[describe_tournament(&tournament).validate()?.build()])?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn start_tournament(
+        &self,
+        data: &ApplicationCommandData,
+        member: &PartialMember,
+        options: &[CommandDataOption],
+    ) -> anyhow::Result<()> {
+        let tournament = self
+            .find_tournament(
+                data.guild_id,
+                string_option(options, "tournament")
+                    .ok_or_else(|| anyhow!("no tournament provided"))?,
+            )
+            .await?;
+
+        if !self.is_organizer(&tournament, member).await? {
+            return Err(anyhow!("only tournament organizers can start tournaments"));
+        }
+
+        let content = self.start(&tournament).await?;
+
+        self.utils
+            .http_client
+            .interaction(self.utils.application_id)
+            .create_followup(data.interaction.token.as_str())
+            .content(&content)?
+            .flags(MessageFlags::EPHEMERAL)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Drops the players who can't play, seeds everyone else, and starts the first round.
+    pub(super) async fn start(&self, tournament: &tournament::Model) -> anyhow::Result<String> {
+        if tournament.state != TournamentState::Registration {
+            return Err(anyhow!("this tournament has already started"));
+        }
+
+        if checkin_opens(tournament).is_some_and(|opens| Utc::now() < opens) {
+            return Err(anyhow!("check-in for this tournament hasn't opened yet"));
+        }
+
+        let entrants = TournamentEntrant::find()
+            .filter(tournament_entrant::Column::Tournament.eq(tournament.id))
+            .order_by_asc(tournament_entrant::Column::RegisteredAt)
+            .all(self.utils.db_ref())
+            .await?;
+
+        // Players who didn't check in are dropped, and checked in players from the waitlist
+        // take the spots that are left.
+        let cap = tournament
+            .max_entrants
+            .map(usize::try_from)
+            .transpose()?
+            .unwrap_or(usize::MAX);
+        let (confirmed, waitlisted): (Vec<_>, Vec<_>) = entrants
+            .iter()
+            .filter(|e| tournament.checkin_minutes.is_none() || e.checked_in_at.is_some())
+            .partition(|e| !e.waitlisted);
+        let players = confirmed
+            .into_iter()
+            .chain(waitlisted)
+            .take(cap)
+            .map(|e| e.player)
+            .collect::<Vec<_>>();
+
+        if players.len() < 2 {
+            return Err(anyhow!("a tournament needs at least two entrants"));
+        }
+
+        if tournament.format == TournamentFormat::Pools {
+            pool_count(tournament, players.len())?;
+        }
+
+        let players = self.seed_players(tournament, &entrants, players).await?;
+
+        TournamentEntrant::delete_many()
+            .filter(tournament_entrant::Column::Tournament.eq(tournament.id))
+            .filter(tournament_entrant::Column::Player.is_not_in(players.clone()))
+            .exec(self.utils.db_ref())
+            .await?;
+
+        TournamentEntrant::update_many()
+            .filter(tournament_entrant::Column::Tournament.eq(tournament.id))
+            .set(tournament_entrant::ActiveModel {
+                waitlisted: Set(false),
+                ..Default::default()
+            })
+            .exec(self.utils.db_ref())
+            .await?;
+
+        self.save_seeds(tournament, &players).await?;
+
+        let mut model = tournament.clone().into_active_model();
+        model.state = Set(TournamentState::InProgress);
+        model.started_at = Set(Some(Utc::now()));
+        if tournament.format == TournamentFormat::Swiss && tournament.swiss_rounds.is_none() {
+            model.swiss_rounds = Set(Some(i32::try_from(swiss::rounds_for(players.len()))?));
+        }
+        let tournament = model.update(self.utils.db_ref()).await?;
+
+        self.update_panel(&tournament).await?;
+
+        match tournament.format {
+            TournamentFormat::Swiss => self.pair_swiss_round(&tournament, 1).await?,
+            TournamentFormat::Pools => self.start_pools(&tournament, &players).await?,
+            TournamentFormat::SingleElimination | TournamentFormat::DoubleElimination => {
+                self.create_bracket(&tournament, &players).await?;
+            }
+        }
+
+        Ok(format!(
+            "**{}** has started with {} entrants, and {} were dropped.",
+            tournament.name,
+            players.len(),
+            entrants.len() - players.len()
+        ))
+    }
+
+    pub(super) async fn close_lobby(&self, lobby: Option<Uuid>) -> anyhow::Result<()> {
+        let Some(lobby) = lobby else {
+            return Ok(());
+        };
+
+        if let Some(lobby) = MatchmakingLobbies::find_by_id(lobby)
+            .one(self.utils.db_ref())
+            .await?
+        {
+            self.background.close_lobby(&lobby).await?;
+        }
+
+        Ok(())
+    }
+
+    pub(super) async fn complete(&self, tournament: &tournament::Model) -> anyhow::Result<()> {
+        Tournament::update(tournament::ActiveModel {
+            id: Set(tournament.id),
+            state: Set(TournamentState::Completed),
+            ..Default::default()
+        })
+        .exec(self.utils.db_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Posts an announcement in the tournament's channel.
+    pub(super) async fn post(
+        &self,
+        tournament: &tournament::Model,
+        embed: EmbedBuilder,
+    ) -> anyhow::Result<()> {
+        let Some(channel) = tournament.channel.as_ref() else {
+            return Ok(());
+        };
+
+        self.utils
+            .http_client
+            .create_message(channel.into_id())
+            .embeds(&[embed.validate()?.build()])?
+            .await?;
+
+        Ok(())
+    }
+
+    /// Guild admins can manage every tournament, and everyone else can only manage the
+    /// tournaments they created.
+    pub(super) async fn is_organizer(
+        &self,
+        tournament: &tournament::Model,
+        member: &PartialMember,
+    ) -> anyhow::Result<bool> {
+        if self
+            .utils
+            .is_guild_admin(tournament.guild_id.into_id(), member)
+            .await?
+        {
+            return Ok(true);
+        }
+
+        let user = member
+            .user
+            .as_ref()
+            .ok_or_else(|| anyhow!("could not get user data for caller"))?;
+        let player = self.utils.find_or_create_user(user.id).await?;
+
+        Ok(player.user_id == tournament.created_by)
+    }
+
+    pub(super) async fn find_tournament(
+        &self,
+        guild: Id<GuildMarker>,
+        name: &str,
+    ) -> anyhow::Result<tournament::Model> {
+        Tournament::find()
+            .filter(tournament::Column::GuildId.eq(IdWrapper::from(guild)))
+            .filter(tournament::Column::Name.eq(name))
+            .one(self.utils.db_ref())
+            .await?
+            .ok_or_else(|| anyhow!("there is no tournament named \"{}\"", name))
+    }
+
+    pub(super) async fn discord_ids(
+        &self,
+        users: Vec<Uuid>,
+    ) -> anyhow::Result<HashMap<Uuid, IdWrapper<UserMarker>>> {
+        Ok(Users::find()
+            .filter(users::Column::UserId.is_in(users))
+            .all(self.utils.db_ref())
+            .await?
+            .into_iter()
+            .filter_map(|u| Some((u.user_id, u.discord_user?)))
+            .collect())
+    }
+}
+
+fn parse_time(time: &str) -> anyhow::Result<DateTime<Utc>> {
+    let time = NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M")
+        .map_err(|_| anyhow!("\"{}\" is not a valid time, use YYYY-MM-DD HH:MM", time))?;
+
+    Ok(DateTime::from_utc(time, Utc))
+}
+
+pub(super) fn format_name(tournament: &tournament::Model) -> String {
+    match (tournament.source, tournament.format) {
+        (Some(source), _) => format!("Hosted on {}", source_name(source)),
+        (None, TournamentFormat::SingleElimination) => "Single elimination".to_string(),
+        (None, TournamentFormat::DoubleElimination) => "Double elimination".to_string(),
+        (None, TournamentFormat::Swiss) => {
+            let rounds = tournament.swiss_rounds.map_or_else(
+                || "Swiss".to_string(),
+                |rounds| format!("{rounds} Swiss rounds"),
+            );
+            match tournament.top_cut {
+                Some(cut) => format!("{rounds}, then a top {cut} cut"),
+                None => rounds,
+            }
+        }
+        (None, TournamentFormat::Pools) => format!(
+            "{} round robin pools, top {} of each advance",
+            tournament.pool_count.unwrap_or(2),
+            tournament.pool_advance.unwrap_or(2)
+        ),
+    }
+}
+
+pub(super) fn describe_tournament(tournament: &tournament::Model) -> EmbedBuilder {
+    let mut embed = EmbedBuilder::new()
+        .title(tournament.name.as_str())
+        .field(EmbedFieldBuilder::new("Format", format_name(tournament)).inline())
+        .field(
+            EmbedFieldBuilder::new(
+                "Channel",
+                tournament
+                    .channel
+                    .as_ref()
+                    .map_or_else(|| "Not set".to_string(), |c| format!("<#{c}>")),
+            )
+            .inline(),
+        );
+
+    if let Some(starts_at) = tournament.starts_at {
+        embed = embed.field(
+            EmbedFieldBuilder::new("Starts", format!("<t:{}:F>", starts_at.timestamp())).inline(),
+        );
+    }
+
+    if let Some(minutes) = tournament.dq_minutes {
+        embed =
+            embed.field(EmbedFieldBuilder::new("DQ timer", format!("{minutes} minutes")).inline());
+    }
+
+    if let Some(opens) = checkin_opens(tournament) {
+        embed = embed.field(
+            EmbedFieldBuilder::new("Check-in opens", format!("<t:{}:R>", opens.timestamp()))
+                .inline(),
+        );
+    }
+
+    embed
+}
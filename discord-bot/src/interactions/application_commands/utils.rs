@@ -1,12 +1,22 @@
-use bot::entity::prelude::*;
+use bot::{
+    entity::prelude::*,
+    matchmaking::rating::{self, Outcome},
+};
 use chrono::Utc;
-use sea_orm::{prelude::*, DatabaseConnection, IntoActiveModel, Set};
+use sea_orm::{
+    prelude::*, Condition, DatabaseConnection, IntoActiveModel, QuerySelect, Set, TransactionTrait,
+};
 use twilight_cache_inmemory::InMemoryCache;
 use twilight_model::{
-    application::interaction::Interaction,
+    application::interaction::{
+        application_command::{CommandDataOption, CommandOptionValue},
+        Interaction,
+    },
+    channel::message::allowed_mentions::AllowedMentionsBuilder,
+    guild::{PartialMember, Permissions},
     http::interaction::InteractionResponse,
     id::{
-        marker::{ApplicationMarker, GuildMarker, UserMarker},
+        marker::{ApplicationMarker, ChannelMarker, GuildMarker, RoleMarker, UserMarker},
         Id,
     },
     user::{CurrentUser, User},
@@ -127,6 +137,118 @@ impl CommonUtilities {
         }
     }
 
+    /// Guild admins are members with the "Manage Server" permission, or members with the
+    /// guild's admin role.
+    pub async fn is_guild_admin(
+        &self,
+        guild: Id<GuildMarker>,
+        member: &PartialMember,
+    ) -> anyhow::Result<bool> {
+        if let Some(perms) = member.permissions {
+            if perms.intersects(Permissions::ADMINISTRATOR | Permissions::MANAGE_GUILD) {
+                return Ok(true);
+            }
+        }
+
+        let settings = self.get_guild_settings(guild).await?;
+
+        Ok(settings
+            .admin_role
+            .is_some_and(|role| member.roles.contains(&role.into_id())))
+    }
+
+    /// Get the player's rating in this guild for the given game. Players that haven't played
+    /// any rated matches yet have the default rating.
+    pub async fn get_rating(
+        &self,
+        guild: Id<GuildMarker>,
+        player: Uuid,
+        game: Option<Uuid>,
+    ) -> anyhow::Result<i32> {
+        let game_filter = match game {
+            Some(game) => player_rating::Column::Game.eq(game),
+            None => player_rating::Column::Game.is_null(),
+        };
+
+        let rating = PlayerRating::find()
+            .filter(player_rating::Column::GuildId.eq(IdWrapper::from(guild)))
+            .filter(player_rating::Column::Player.eq(player))
+            .filter(game_filter)
+            .one(self.db_ref())
+            .await?;
+
+        Ok(rating.map_or(player_rating::DEFAULT_RATING, |r| r.rating))
+    }
+
+    /// Updates both players' ratings for the game after a confirmed result. Each player is
+    /// rated in their own guild, which only differs for cross-server matches.
+    pub async fn update_ratings(
+        &self,
+        first: (Id<GuildMarker>, Uuid),
+        second: (Id<GuildMarker>, Uuid),
+        game: Option<Uuid>,
+        outcome: Outcome,
+    ) -> anyhow::Result<(i32, i32)> {
+        let game_filter = || match game {
+            Some(game) => player_rating::Column::Game.eq(game),
+            None => player_rating::Column::Game.is_null(),
+        };
+
+        let txn = self.db_ref().begin().await?;
+
+        let mut current = Vec::new();
+        for (guild, player) in [first, second] {
+            let rating = PlayerRating::find()
+                .filter(player_rating::Column::GuildId.eq(IdWrapper::from(guild)))
+                .filter(player_rating::Column::Player.eq(player))
+                .filter(game_filter())
+                .lock_exclusive()
+                .one(&txn)
+                .await?;
+            current.push(rating);
+        }
+
+        let ratings = current
+            .iter()
+            .map(|r| {
+                r.as_ref()
+                    .map_or(player_rating::DEFAULT_RATING, |r| r.rating)
+            })
+            .collect::<Vec<_>>();
+        let (first_rating, second_rating) = rating::update(ratings[0], ratings[1], outcome);
+
+        for ((guild, player), (existing, rating)) in [first, second]
+            .into_iter()
+            .zip(current.into_iter().zip([first_rating, second_rating]))
+        {
+            if let Some(existing) = existing {
+                let games_played = existing.games_played + 1;
+                let mut model = existing.into_active_model();
+                model.rating = Set(rating);
+                model.games_played = Set(games_played);
+                model.last_updated = Set(Utc::now());
+                model.update(&txn).await?;
+            } else {
+                player_rating::Model {
+                    id: Uuid::new_v4(),
+                    player,
+                    guild_id: guild.into(),
+                    game,
+                    rating,
+                    games_played: 1,
+                    last_updated: Utc::now(),
+                }
+                .into_active_model()
+                .insert(&txn)
+                .await?;
+            }
+        }
+
+        txn.commit().await?;
+
+        Ok((first_rating, second_rating))
+    }
+
     pub async fn get_user(&self, user: Id<UserMarker>) -> anyhow::Result<User> {
         if let Some(user_ref) = self.cache.user(user) {
             let user = user_ref.to_owned();
@@ -137,6 +259,16 @@ impl CommonUtilities {
         Ok(user)
     }
 
+    /// Get the Discord ID of a user that's already in the database.
+    pub async fn discord_user_id(&self, user: Uuid) -> anyhow::Result<Id<UserMarker>> {
+        Users::find_by_id(user)
+            .one(self.db_ref())
+            .await?
+            .and_then(|u| u.discord_user)
+            .map(|id| id.into_id())
+            .ok_or_else(|| anyhow!("user does not have a discord id"))
+    }
+
     pub async fn find_or_create_user(&self, id: Id<UserMarker>) -> anyhow::Result<users::Model> {
         let res = Users::find()
             .filter(users::Column::DiscordUser.eq(IdWrapper::from(id)))
@@ -158,4 +290,157 @@ impl CommonUtilities {
             return Ok(user);
         }
     }
+
+    /// The player's matchmaking ban in the guild, if they're banned right now.
+    pub async fn active_ban(
+        &self,
+        guild: Id<GuildMarker>,
+        player: Uuid,
+    ) -> anyhow::Result<Option<matchmaking_ban::Model>> {
+        Ok(MatchmakingBan::find()
+            .filter(matchmaking_ban::Column::GuildId.eq(IdWrapper::from(guild)))
+            .filter(matchmaking_ban::Column::Player.eq(player))
+            .filter(
+                Condition::any()
+                    .add(matchmaking_ban::Column::ExpiresAt.is_null())
+                    .add(matchmaking_ban::Column::ExpiresAt.gt(Utc::now())),
+            )
+            .one(self.db_ref())
+            .await?)
+    }
+
+    /// Records an admin action in the guild's audit log, and posts it in the guild's audit
+    /// channel if one is set. `details` finishes the sentence "<admin> ...".
+    pub async fn audit(
+        &self,
+        guild: Id<GuildMarker>,
+        actor: Id<UserMarker>,
+        action: AuditAction,
+        target: Option<Id<UserMarker>>,
+        details: String,
+    ) -> anyhow::Result<()> {
+        let target = match target {
+            Some(target) => Some(self.find_or_create_user(target).await?.user_id),
+            None => None,
+        };
+
+        let entry = audit_log::Model {
+            id: Uuid::new_v4(),
+            guild_id: guild.into(),
+            actor: self.find_or_create_user(actor).await?.user_id,
+            action,
+            target,
+            details,
+            created_at: Utc::now(),
+        };
+
+        AuditLog::insert(entry.clone().into_active_model())
+            .exec(self.db_ref())
+            .await?;
+
+        let settings = self.get_guild_settings(guild).await?;
+        if let Some(channel) = settings.audit_channel {
+            // The log channel only mirrors the audit log, so the action still counts if
+            // posting there fails.
+            let res = self
+                .http_client
+                .create_message(channel.into_id())
+                .content(&format!("<@{actor}> {}", entry.details))?
+                .allowed_mentions(Some(&AllowedMentionsBuilder::new().build()))
+                .await;
+
+            if let Err(e) = res {
+                warn!(error = ?e, guild = ?guild, "could not post to the audit channel");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Tells a banned player why they can't play matches, and for how long.
+pub fn ban_notice(ban: &matchmaking_ban::Model) -> String {
+    let mut notice = match ban.expires_at {
+        Some(expires_at) => format!(
+            "You are banned from matchmaking in this server until <t:{}:f>.",
+            expires_at.timestamp()
+        ),
+        None => "You are banned from matchmaking in this server.".to_string(),
+    };
+    if let Some(reason) = &ban.reason {
+        notice.push_str(" Reason: ");
+        notice.push_str(reason);
+    }
+    notice
+}
+
+/// Walks through any sub-command groups and returns the names that lead to the
+/// sub-command (e.g. `["match", "list"]`), alongside the sub-command's options.
+pub fn subcommand_path(options: &[CommandDataOption]) -> (Vec<&str>, &[CommandDataOption]) {
+    let mut path = Vec::new();
+    let mut options = options;
+
+    while let Some(option) = options.first() {
+        match &option.value {
+            CommandOptionValue::SubCommandGroup(inner) | CommandOptionValue::SubCommand(inner) => {
+                path.push(option.name.as_str());
+                options = inner.as_slice();
+            }
+            _ => break,
+        }
+    }
+
+    (path, options)
+}
+
+fn find_option<'a>(options: &'a [CommandDataOption], name: &str) -> Option<&'a CommandOptionValue> {
+    options.iter().find(|o| o.name == name).map(|o| &o.value)
+}
+
+pub fn string_option<'a>(options: &'a [CommandDataOption], name: &str) -> Option<&'a str> {
+    if let CommandOptionValue::String(value) = find_option(options, name)? {
+        Some(value.as_str())
+    } else {
+        None
+    }
+}
+
+pub fn integer_option(options: &[CommandDataOption], name: &str) -> Option<i64> {
+    if let CommandOptionValue::Integer(value) = find_option(options, name)? {
+        Some(*value)
+    } else {
+        None
+    }
+}
+
+pub fn boolean_option(options: &[CommandDataOption], name: &str) -> Option<bool> {
+    if let CommandOptionValue::Boolean(value) = find_option(options, name)? {
+        Some(*value)
+    } else {
+        None
+    }
+}
+
+pub fn user_option(options: &[CommandDataOption], name: &str) -> Option<Id<UserMarker>> {
+    if let CommandOptionValue::User(value) = find_option(options, name)? {
+        Some(*value)
+    } else {
+        None
+    }
+}
+
+pub fn channel_option(options: &[CommandDataOption], name: &str) -> Option<Id<ChannelMarker>> {
+    if let CommandOptionValue::Channel(value) = find_option(options, name)? {
+        Some(*value)
+    } else {
+        None
+    }
+}
+
+pub fn role_option(options: &[CommandDataOption], name: &str) -> Option<Id<RoleMarker>> {
+    if let CommandOptionValue::Role(value) = find_option(options, name)? {
+        Some(*value)
+    } else {
+        None
+    }
 }
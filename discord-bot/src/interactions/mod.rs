@@ -24,12 +24,14 @@ use twilight_standby::Standby;
 use twilight_util::builder::embed::{EmbedBuilder, EmbedFieldBuilder, EmbedFooterBuilder};
 
 use crate::interactions::application_commands::{
-    ApplicationCommandData, CommonUtilities, MessageComponentData,
+    ApplicationCommandData, CommonUtilities, MessageComponentData, ModalSubmitData,
 };
 
 use self::application_commands::{
-    admin::admin_handler::AdminCommandHandler, matchmaking::MatchmakingCommandHandler,
-    CommandGroupDescriptor, InteractionHandler,
+    admin::admin_handler::AdminCommandHandler, league::league_handler::LeagueCommandHandler,
+    lfg::LfgCommandHandler, matchmaking::MatchmakingCommandHandler,
+    tournament::tournament_handler::TournamentCommandHandler, CommandGroupDescriptor,
+    InteractionHandler,
 };
 
 type HandlerType = Arc<Box<dyn InteractionHandler + Send + Sync + 'static>>;
@@ -46,14 +48,23 @@ impl InteractionProcessor {
     pub async fn init(utils: Arc<CommonUtilities>) -> anyhow::Result<Self> {
         event!(Level::INFO, "Registering top-level command handlers");
 
+        let matchmaking = MatchmakingCommandHandler::new(utils.clone());
+        let background = matchmaking.background();
+        let lfg = LfgCommandHandler::new(utils.clone(), background.clone());
+        let tournaments = TournamentCommandHandler::new(utils.clone(), background.clone());
+        background.set_tournaments(tournaments.clone());
+
         let top_level_handlers: Vec<Arc<Box<dyn InteractionHandler + Send + Sync + 'static>>> = vec![
-            Arc::new(Box::new(AdminCommandHandler::new(utils.clone()))),
-            Arc::new(Box::new(MatchmakingCommandHandler::new(utils.clone()))),
+            Arc::new(Box::new(AdminCommandHandler::new(
+                utils.clone(),
+                background.clone(),
+                lfg.clone(),
+            ))),
+            Arc::new(Box::new(matchmaking)),
+            Arc::new(Box::new(LeagueCommandHandler::new(utils.clone()))),
+            Arc::new(Box::new(tournaments)),
+            Arc::new(Box::new(lfg)),
             // Arc::new(Box::new(EulaCommandHandler::new(utils.clone()))),
-            // Arc::new(Box::new(LfgCommandHandler {
-            //     utils: utils.clone(),
-            //     lfg_sessions,
-            // })),
         ];
 
         let mut this = Self {
@@ -245,8 +256,27 @@ impl InteractionProcessor {
                     ));
                 }
             }
-            InteractionData::ModalSubmit(_modal) => {
+            InteractionData::ModalSubmit(modal) => {
                 debug!("Received modal");
+
+                // Modals are routed like message components, by their "handler:action" ID.
+                let (handler_name, leftover) =
+                    modal.custom_id.split_once(':').ok_or_else(|| {
+                        anyhow!("Modal custom_id does not match the format \"handler:action\"")
+                    })?;
+                let handler = self
+                    .component_handlers
+                    .get(handler_name)
+                    .ok_or_else(|| anyhow!("Invalid modal handler: {}", handler_name))?
+                    .clone();
+
+                let data = Box::new(ModalSubmitData {
+                    modal: modal.clone(),
+                    action: leftover.to_string(),
+                    interaction: interaction.0.clone(),
+                });
+                let fut = Box::pin(async move { handler.process_modal(data).await });
+                return Ok(fut);
             }
             _ => {
                 debug!(interaction = %format!("{:?}", interaction), "Unhandled interaction");
@@ -0,0 +1,41 @@
+//! Challenge ladders, where players climb by beating the players above them.
+
+/// Whether the player at `challenger` may challenge the player at `defender`.
+///
+/// Positions start at 1 for the top of the ladder, and players can only challenge players
+/// that are above them and at most `range` positions away.
+#[must_use]
+pub fn can_challenge(challenger: i32, defender: i32, range: i32) -> bool {
+    defender < challenger && challenger - defender <= range
+}
+
+/// The positions of the challenger and defender after a challenge. A winning challenger
+/// takes the defender's position, and the defender takes the challenger's old one.
+#[must_use]
+pub fn positions_after(challenger: i32, defender: i32, challenger_won: bool) -> (i32, i32) {
+    if challenger_won && defender < challenger {
+        (defender, challenger)
+    } else {
+        (challenger, defender)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_challenge_range() {
+        assert!(can_challenge(5, 4, 2));
+        assert!(can_challenge(5, 3, 2));
+        assert!(!can_challenge(5, 2, 2));
+        assert!(!can_challenge(5, 5, 2));
+        assert!(!can_challenge(3, 5, 2));
+    }
+
+    #[test]
+    fn test_positions_swap_when_challenger_wins() {
+        assert_eq!(positions_after(5, 3, true), (3, 5));
+        assert_eq!(positions_after(5, 3, false), (5, 3));
+    }
+}
@@ -0,0 +1,5 @@
+//! League logic that doesn't depend on Discord.
+
+pub mod ladder;
+pub mod schedule;
+pub mod standings;
@@ -0,0 +1,122 @@
+//! Round robin scheduling for leagues with a fixed schedule.
+
+/// Generates the rounds of a round robin using the circle method.
+///
+/// Every player meets every other player once per cycle. Passing `cycles = 2` creates a
+/// double round robin, where the second cycle swaps sides. With an odd number of players,
+/// a different player sits out each round.
+#[must_use]
+pub fn round_robin<T: Clone>(players: &[T], cycles: usize) -> Vec<Vec<(T, T)>> {
+    if players.len() < 2 {
+        return Vec::new();
+    }
+
+    let mut slots = players.iter().cloned().map(Some).collect::<Vec<_>>();
+    if slots.len() % 2 == 1 {
+        // The player paired with the empty slot has a bye.
+        slots.push(None);
+    }
+
+    let size = slots.len();
+    let mut rounds = Vec::with_capacity((size - 1) * cycles);
+
+    for cycle in 0..cycles {
+        let mut circle = slots.clone();
+
+        for round in 0..size - 1 {
+            let mut pairings = Vec::with_capacity(size / 2);
+
+            for i in 0..size / 2 {
+                if let (Some(a), Some(b)) = (&circle[i], &circle[size - 1 - i]) {
+                    // Alternate sides for the fixed player, and swap every side in odd cycles.
+                    let swap = (i == 0 && round % 2 == 1) != (cycle % 2 == 1);
+                    if swap {
+                        pairings.push((b.clone(), a.clone()));
+                    } else {
+                        pairings.push((a.clone(), b.clone()));
+                    }
+                }
+            }
+
+            rounds.push(pairings);
+
+            // Keep the first slot fixed and rotate everyone else.
+            circle[1..].rotate_right(1);
+        }
+    }
+
+    rounds
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn count_pairings(rounds: &[Vec<(u32, u32)>]) -> HashMap<(u32, u32), usize> {
+        let mut counts = HashMap::new();
+        for (a, b) in rounds.iter().flatten() {
+            *counts.entry((*a.min(b), *a.max(b))).or_default() += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn test_every_player_meets_once() {
+        let rounds = round_robin(&[1, 2, 3, 4], 1);
+
+        assert_eq!(rounds.len(), 3);
+        assert!(rounds.iter().all(|r| r.len() == 2));
+
+        let counts = count_pairings(&rounds);
+        assert_eq!(counts.len(), 6);
+        assert!(counts.values().all(|c| *c == 1));
+    }
+
+    #[test]
+    fn test_players_play_once_per_round() {
+        for round in round_robin(&[1, 2, 3, 4, 5, 6], 1) {
+            let mut players = round.iter().flat_map(|(a, b)| [*a, *b]).collect::<Vec<_>>();
+            players.sort_unstable();
+            players.dedup();
+            assert_eq!(players.len(), 6);
+        }
+    }
+
+    #[test]
+    fn test_odd_players_get_one_bye() {
+        let rounds = round_robin(&[1, 2, 3, 4, 5], 1);
+
+        assert_eq!(rounds.len(), 5);
+        assert!(rounds.iter().all(|r| r.len() == 2));
+        assert_eq!(count_pairings(&rounds).len(), 10);
+
+        for player in 1..=5 {
+            let byes = rounds
+                .iter()
+                .filter(|r| !r.iter().any(|(a, b)| *a == player || *b == player))
+                .count();
+            assert_eq!(byes, 1);
+        }
+    }
+
+    #[test]
+    fn test_double_round_robin_swaps_sides() {
+        let rounds = round_robin(&[1, 2, 3, 4], 2);
+
+        assert_eq!(rounds.len(), 6);
+        assert!(count_pairings(&rounds).values().all(|c| *c == 2));
+
+        let (first, second) = rounds.split_at(3);
+        for (a, b) in first.iter().flatten() {
+            assert!(second.iter().flatten().any(|pair| *pair == (*b, *a)));
+        }
+    }
+
+    #[test]
+    fn test_not_enough_players() {
+        assert!(round_robin(&[1], 1).is_empty());
+        assert!(round_robin::<u32>(&[], 2).is_empty());
+    }
+}
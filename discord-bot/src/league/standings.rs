@@ -0,0 +1,360 @@
+//! Standings tables for leagues, with configurable scoring and tiebreakers.
+
+use std::{cmp::Ordering, collections::HashMap, hash::Hash, str::FromStr};
+
+/// The points that are awarded for the result of a set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Scoring {
+    pub win: i32,
+    pub draw: i32,
+    pub loss: i32,
+}
+
+impl Default for Scoring {
+    fn default() -> Self {
+        Self {
+            win: 3,
+            draw: 1,
+            loss: 0,
+        }
+    }
+}
+
+/// Breaks ties between entrants with the same number of points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tiebreaker {
+    /// Points earned in the sets between the tied entrants.
+    HeadToHead,
+    /// Games won minus games lost.
+    GameDifferential,
+    /// The total points of every opponent an entrant has played.
+    StrengthOfSchedule,
+}
+
+impl Tiebreaker {
+    /// The order that's used when a league doesn't configure its own.
+    pub const DEFAULT_ORDER: [Tiebreaker; 3] = [
+        Tiebreaker::HeadToHead,
+        Tiebreaker::GameDifferential,
+        Tiebreaker::StrengthOfSchedule,
+    ];
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Tiebreaker::HeadToHead => "head-to-head",
+            Tiebreaker::GameDifferential => "game-differential",
+            Tiebreaker::StrengthOfSchedule => "strength-of-schedule",
+        }
+    }
+
+    /// Parses a comma separated list of tiebreakers, like `head-to-head,game-differential`.
+    ///
+    /// # Errors
+    /// Returns the first name that isn't a tiebreaker.
+    pub fn parse_list(list: &str) -> Result<Vec<Tiebreaker>, String> {
+        list.split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::parse)
+            .collect()
+    }
+}
+
+impl FromStr for Tiebreaker {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "head-to-head" => Ok(Tiebreaker::HeadToHead),
+            "game-differential" => Ok(Tiebreaker::GameDifferential),
+            "strength-of-schedule" => Ok(Tiebreaker::StrengthOfSchedule),
+            _ => Err(s.to_string()),
+        }
+    }
+}
+
+/// The result of a set between two entrants.
+#[derive(Clone, Debug)]
+pub struct SetResult<T> {
+    pub one: T,
+    pub two: T,
+    /// The games won by each side, or `None` if neither side showed up. Those sets count as a
+    /// loss for both entrants.
+    pub games: Option<(i32, i32)>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Standing<T> {
+    pub entrant: T,
+    pub points: i32,
+    pub wins: i32,
+    pub draws: i32,
+    pub losses: i32,
+    pub games_won: i32,
+    pub games_lost: i32,
+    pub strength_of_schedule: i32,
+}
+
+impl<T> Standing<T> {
+    #[must_use]
+    pub fn game_differential(&self) -> i32 {
+        self.games_won - self.games_lost
+    }
+}
+
+/// Tallies the results into a table, sorted from first to last place.
+///
+/// Entrants are ranked by points, and entrants with the same points are ranked by each
+/// tiebreaker in order. Entrants that are still tied keep the order they were given in.
+#[must_use]
+pub fn standings<T: Clone + Eq + Hash>(
+    entrants: &[T],
+    results: &[SetResult<T>],
+    scoring: &Scoring,
+    tiebreakers: &[Tiebreaker],
+) -> Vec<Standing<T>> {
+    let mut table = entrants
+        .iter()
+        .map(|e| Standing {
+            entrant: e.clone(),
+            points: 0,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+            games_won: 0,
+            games_lost: 0,
+            strength_of_schedule: 0,
+        })
+        .collect::<Vec<_>>();
+    let index = entrants
+        .iter()
+        .enumerate()
+        .map(|(i, e)| (e.clone(), i))
+        .collect::<HashMap<_, _>>();
+
+    for result in results {
+        let (Some(&one), Some(&two)) = (index.get(&result.one), index.get(&result.two)) else {
+            continue;
+        };
+
+        if let Some((a, b)) = result.games {
+            table[one].games_won += a;
+            table[one].games_lost += b;
+            table[two].games_won += b;
+            table[two].games_lost += a;
+
+            match a.cmp(&b) {
+                Ordering::Greater => {
+                    table[one].wins += 1;
+                    table[two].losses += 1;
+                }
+                Ordering::Less => {
+                    table[one].losses += 1;
+                    table[two].wins += 1;
+                }
+                Ordering::Equal => {
+                    table[one].draws += 1;
+                    table[two].draws += 1;
+                }
+            }
+        } else {
+            table[one].losses += 1;
+            table[two].losses += 1;
+        }
+    }
+
+    for standing in &mut table {
+        standing.points = standing.wins * scoring.win
+            + standing.draws * scoring.draw
+            + standing.losses * scoring.loss;
+    }
+
+    for result in results {
+        if let (Some(&one), Some(&two)) = (index.get(&result.one), index.get(&result.two)) {
+            table[one].strength_of_schedule += table[two].points;
+            table[two].strength_of_schedule += table[one].points;
+        }
+    }
+
+    let keys = table
+        .iter()
+        .map(|s| {
+            tiebreakers
+                .iter()
+                .map(|t| match t {
+                    Tiebreaker::HeadToHead => head_to_head(s, &table, results, scoring),
+                    Tiebreaker::GameDifferential => s.game_differential(),
+                    Tiebreaker::StrengthOfSchedule => s.strength_of_schedule,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut order = (0..table.len()).collect::<Vec<_>>();
+    order.sort_by(|a, b| {
+        table[*b]
+            .points
+            .cmp(&table[*a].points)
+            .then_with(|| keys[*b].cmp(&keys[*a]))
+    });
+
+    order.into_iter().map(|i| table[i].clone()).collect()
+}
+
+/// The points an entrant earned in the sets against entrants with the same points.
+fn head_to_head<T: Eq>(
+    standing: &Standing<T>,
+    table: &[Standing<T>],
+    results: &[SetResult<T>],
+    scoring: &Scoring,
+) -> i32 {
+    results
+        .iter()
+        .filter_map(|r| {
+            let (opponent, games) = if r.one == standing.entrant {
+                (&r.two, r.games?)
+            } else if r.two == standing.entrant {
+                (&r.one, (r.games?.1, r.games?.0))
+            } else {
+                return None;
+            };
+
+            let tied = table
+                .iter()
+                .any(|s| s.entrant == *opponent && s.points == standing.points);
+            if !tied {
+                return None;
+            }
+
+            Some(match games.0.cmp(&games.1) {
+                Ordering::Greater => scoring.win,
+                Ordering::Less => scoring.loss,
+                Ordering::Equal => scoring.draw,
+            })
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(one: char, two: char, a: i32, b: i32) -> SetResult<char> {
+        SetResult {
+            one,
+            two,
+            games: Some((a, b)),
+        }
+    }
+
+    fn order(table: &[Standing<char>]) -> String {
+        table.iter().map(|s| s.entrant).collect()
+    }
+
+    #[test]
+    fn test_points_use_scoring() {
+        let results = [
+            set('a', 'b', 2, 0),
+            set('b', 'c', 1, 1),
+            set('c', 'a', 2, 1),
+        ];
+        let scoring = Scoring {
+            win: 2,
+            draw: 1,
+            loss: -1,
+        };
+
+        let table = standings(&['a', 'b', 'c'], &results, &scoring, &[]);
+
+        let points = table
+            .iter()
+            .map(|s| (s.entrant, s.points))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(points[&'a'], 1);
+        assert_eq!(points[&'b'], 0);
+        assert_eq!(points[&'c'], 3);
+        assert_eq!(table[0].entrant, 'c');
+    }
+
+    #[test]
+    fn test_head_to_head_breaks_ties() {
+        // a and b both win twice, but a beat b.
+        let results = [
+            set('a', 'b', 2, 1),
+            set('a', 'c', 0, 2),
+            set('a', 'd', 2, 0),
+            set('b', 'c', 2, 0),
+            set('b', 'd', 2, 0),
+            set('c', 'd', 0, 2),
+        ];
+
+        let table = standings(
+            &['b', 'a', 'c', 'd'],
+            &results,
+            &Scoring::default(),
+            &[Tiebreaker::HeadToHead],
+        );
+
+        assert_eq!(&order(&table)[..2], "ab");
+    }
+
+    #[test]
+    fn test_game_differential_breaks_ties() {
+        let results = [set('a', 'c', 2, 1), set('b', 'c', 3, 0)];
+
+        let table = standings(
+            &['a', 'b', 'c'],
+            &results,
+            &Scoring::default(),
+            &Tiebreaker::DEFAULT_ORDER,
+        );
+
+        assert_eq!(order(&table), "bac");
+        assert_eq!(table[0].game_differential(), 3);
+    }
+
+    #[test]
+    fn test_strength_of_schedule_breaks_ties() {
+        // a and b each beat one opponent by the same margin, but a's opponent is stronger.
+        let results = [
+            set('a', 'c', 1, 0),
+            set('b', 'd', 1, 0),
+            set('c', 'e', 1, 0),
+        ];
+
+        let table = standings(
+            &['b', 'a', 'c', 'd', 'e'],
+            &results,
+            &Scoring::default(),
+            &Tiebreaker::DEFAULT_ORDER,
+        );
+
+        assert_eq!(&order(&table)[..2], "ab");
+    }
+
+    #[test]
+    fn test_unplayed_sets_are_losses() {
+        let results = [SetResult {
+            one: 'a',
+            two: 'b',
+            games: None,
+        }];
+
+        let table = standings(&['a', 'b'], &results, &Scoring::default(), &[]);
+
+        assert!(table.iter().all(|s| s.losses == 1 && s.points == 0));
+    }
+
+    #[test]
+    fn test_parse_tiebreakers() {
+        assert_eq!(
+            Tiebreaker::parse_list("game-differential, head-to-head"),
+            Ok(vec![Tiebreaker::GameDifferential, Tiebreaker::HeadToHead])
+        );
+        assert_eq!(
+            Tiebreaker::parse_list("coin-flip"),
+            Err("coin-flip".to_string())
+        );
+    }
+}
@@ -1,8 +1,12 @@
 #![warn(clippy::pedantic)]
 
+pub mod bridge;
 pub mod entity;
+pub mod league;
+pub mod matchmaking;
 #[cfg(feature = "migrator")]
 pub mod migration;
+pub mod tournament;
 
 #[macro_use]
 extern crate tracing;
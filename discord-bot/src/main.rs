@@ -123,9 +123,13 @@ async fn entrypoint() -> anyhow::Result<()> {
     // let scheme = ShardScheme::Bucket { bucket_id: (), concurrency: (), total: () };
 
     // Use intents to only receive guild message events.
-    let (cluster, mut events) = Cluster::builder(CONFIG.token.clone(), Intents::GUILDS)
-        .build()
-        .await?;
+    let (cluster, mut events) = Cluster::builder(
+        CONFIG.token.clone(),
+        // Messages are read to relay them between the threads of cross-server matches.
+        Intents::GUILDS | Intents::GUILD_MESSAGES | Intents::MESSAGE_CONTENT,
+    )
+    .build()
+    .await?;
     let cluster = Arc::new(cluster);
 
     // Start up the cluster.
@@ -0,0 +1,93 @@
+//! Pinging a game's subscribers when someone is looking for a match.
+
+use twilight_model::id::{marker::UserMarker, Id};
+
+/// Discord doesn't let one message ping more users than this.
+pub const MAX_MENTIONS: usize = 100;
+
+/// Splits the mentions of `users` across as few messages as it takes, each ending with
+/// `suffix`. A message can mention at most [`MAX_MENTIONS`] users and hold 2000
+/// characters.
+#[must_use]
+pub fn ping_messages(users: &[Id<UserMarker>], suffix: &str) -> Vec<(Vec<Id<UserMarker>>, String)> {
+    let mut messages = Vec::new();
+    let mut chunk = Vec::new();
+    let mut mentions = String::new();
+
+    for user in users {
+        let mention = format!("<@{user}> ");
+        if chunk.len() == MAX_MENTIONS || mentions.len() + mention.len() + suffix.len() > 2000 {
+            messages.push((std::mem::take(&mut chunk), std::mem::take(&mut mentions)));
+        }
+        chunk.push(*user);
+        mentions.push_str(&mention);
+    }
+    if !chunk.is_empty() {
+        messages.push((chunk, mentions));
+    }
+
+    for (_, message) in &mut messages {
+        let trimmed = message.trim_end().len();
+        message.truncate(trimmed);
+        message.push_str(suffix);
+    }
+
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn users(first: u64, count: u64) -> Vec<Id<UserMarker>> {
+        (first..first + count).map(Id::new).collect()
+    }
+
+    #[test]
+    fn test_ping_messages_mention_limit() {
+        // Short IDs, so that only the number of mentions splits the messages.
+        let users = users(1, 250);
+        let messages = ping_messages(&users, " is looking for a match");
+
+        let sizes = messages.iter().map(|(u, _)| u.len()).collect::<Vec<_>>();
+        assert_eq!(sizes, [100, 100, 50]);
+        assert_eq!(
+            messages
+                .iter()
+                .flat_map(|(u, _)| u.clone())
+                .collect::<Vec<_>>(),
+            users
+        );
+    }
+
+    #[test]
+    fn test_ping_messages_length_limit() {
+        let users = users(100_000_000_000_000_000, 10);
+        let suffix = format!(" {}", "x".repeat(1900));
+        let messages = ping_messages(&users, &suffix);
+
+        // Every mention takes 22 characters, so only four fit next to the suffix.
+        let sizes = messages.iter().map(|(u, _)| u.len()).collect::<Vec<_>>();
+        assert_eq!(sizes, [4, 4, 2]);
+
+        for (users, message) in &messages {
+            assert!(message.len() <= 2000);
+            assert!(message.ends_with(&suffix));
+            assert!(message.starts_with(&format!("<@{}> <@", users[0])));
+        }
+    }
+
+    #[test]
+    fn test_ping_messages_suffix() {
+        let users = users(1, 2);
+        let messages = ping_messages(&users, " is waiting in the queue");
+
+        assert_eq!(
+            messages,
+            [(
+                users.clone(),
+                format!("<@{}> <@{}> is waiting in the queue", users[0], users[1])
+            )]
+        );
+    }
+}
@@ -0,0 +1,231 @@
+//! Weekly availability windows.
+//!
+//! Players say when they're around to play, like "Mondays 18:00 to 22:00", in their own
+//! time zone. Windows repeat every week, and a window that ends at or before its start
+//! runs past midnight into the next day. Players without any windows are always available.
+
+use std::fmt::Display;
+
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+
+/// The days of the week from Monday, in the order they're numbered in the database.
+pub const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Window {
+    pub weekday: Weekday,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    pub time_zone: Tz,
+}
+
+impl Window {
+    /// Whether `at` falls inside the window.
+    #[must_use]
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        let local = at.with_timezone(&self.time_zone);
+        let (day, time) = (local.weekday(), local.time());
+
+        if self.start < self.end {
+            day == self.weekday && self.start <= time && time < self.end
+        } else {
+            (day == self.weekday && time >= self.start)
+                || (day == self.weekday.succ() && time < self.end)
+        }
+    }
+
+    /// The start and end of the window's next occurrence that hasn't ended by `after`,
+    /// which is the current one if the window is open.
+    #[must_use]
+    pub fn next(&self, after: DateTime<Utc>) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let today = after.with_timezone(&self.time_zone).date_naive();
+
+        // Starting a day early catches a window that opened yesterday and is still open.
+        (-1..=7)
+            .map(|offset| today + Duration::days(offset))
+            .filter(|day| day.weekday() == self.weekday)
+            .filter_map(|day| {
+                let start = self.local(day.and_time(self.start))?;
+                let end_day = if self.start < self.end {
+                    day
+                } else {
+                    day.succ_opt()?
+                };
+                let end = self.local(end_day.and_time(self.end))?;
+                Some((start, end))
+            })
+            .find(|(_, end)| *end > after)
+    }
+
+    /// Local times that are skipped by a daylight saving change don't exist, so they're
+    /// moved to the first time after the change.
+    fn local(&self, time: chrono::NaiveDateTime) -> Option<DateTime<Utc>> {
+        self.time_zone
+            .from_local_datetime(&time)
+            .earliest()
+            .or_else(|| {
+                self.time_zone
+                    .from_local_datetime(&(time + Duration::hours(1)))
+                    .earliest()
+            })
+            .map(|t| t.with_timezone(&Utc))
+    }
+}
+
+/// Whether a player with these windows wants to hear about games at `at`.
+#[must_use]
+pub fn is_available(windows: &[Window], at: DateTime<Utc>) -> bool {
+    windows.is_empty() || windows.iter().any(|w| w.contains(at))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AvailabilityError {
+    /// The time isn't written like "18:30".
+    InvalidTime(String),
+    /// The time zone isn't in the IANA database.
+    UnknownTimeZone(String),
+}
+
+impl std::error::Error for AvailabilityError {}
+
+impl Display for AvailabilityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AvailabilityError::InvalidTime(time) => {
+                write!(f, "\"{time}\" is not a valid time, use HH:MM like 18:30")
+            }
+            AvailabilityError::UnknownTimeZone(zone) => write!(
+                f,
+                "\"{zone}\" is not a known time zone, use a name like Europe/Berlin"
+            ),
+        }
+    }
+}
+
+/// Parses a time of day like "18:30". "24:00" is accepted as the end of the day.
+///
+/// # Errors
+/// Fails if the time isn't written as hours and minutes.
+pub fn parse_time(time: &str) -> Result<NaiveTime, AvailabilityError> {
+    let time = time.trim();
+    if time == "24:00" {
+        return Ok(NaiveTime::MIN);
+    }
+
+    NaiveTime::parse_from_str(time, "%H:%M")
+        .map_err(|_| AvailabilityError::InvalidTime(time.to_string()))
+}
+
+/// Parses an IANA time zone name like `America/New_York`.
+///
+/// # Errors
+/// Fails if the name isn't in the time zone database.
+pub fn parse_time_zone(zone: &str) -> Result<Tz, AvailabilityError> {
+    let zone = zone.trim();
+    zone.parse()
+        .map_err(|_| AvailabilityError::UnknownTimeZone(zone.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::{America::New_York, Europe::Berlin};
+
+    use super::*;
+
+    fn utc(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn window(weekday: Weekday, start: &str, end: &str, time_zone: Tz) -> Window {
+        Window {
+            weekday,
+            start: parse_time(start).unwrap(),
+            end: parse_time(end).unwrap(),
+            time_zone,
+        }
+    }
+
+    #[test]
+    fn test_contains_uses_the_local_time() {
+        // Monday 18:00 to 22:00 in Berlin is 16:00 to 20:00 UTC in the summer.
+        let w = window(Weekday::Mon, "18:00", "22:00", Berlin);
+
+        assert!(w.contains(utc("2023-08-21T16:00:00Z")));
+        assert!(w.contains(utc("2023-08-21T19:59:00Z")));
+        assert!(!w.contains(utc("2023-08-21T20:00:00Z")));
+        assert!(!w.contains(utc("2023-08-22T17:00:00Z")));
+    }
+
+    #[test]
+    fn test_window_past_midnight() {
+        // Friday 22:00 to 02:00 in New York is Saturday 02:00 to 06:00 UTC.
+        let w = window(Weekday::Fri, "22:00", "02:00", New_York);
+
+        assert!(w.contains(utc("2023-08-19T03:00:00Z")));
+        assert!(!w.contains(utc("2023-08-19T06:30:00Z")));
+        assert!(!w.contains(utc("2023-08-18T03:00:00Z")));
+
+        let until_midnight = window(Weekday::Fri, "20:00", "24:00", New_York);
+        assert!(until_midnight.contains(utc("2023-08-19T03:59:00Z")));
+        assert!(!until_midnight.contains(utc("2023-08-19T04:00:00Z")));
+    }
+
+    #[test]
+    fn test_next_occurrence() {
+        let w = window(Weekday::Mon, "18:00", "22:00", Berlin);
+
+        // During the window, the current occurrence is returned.
+        assert_eq!(
+            w.next(utc("2023-08-21T17:00:00Z")),
+            Some((utc("2023-08-21T16:00:00Z"), utc("2023-08-21T20:00:00Z")))
+        );
+        // Afterwards, it's next week's.
+        assert_eq!(
+            w.next(utc("2023-08-21T21:00:00Z")),
+            Some((utc("2023-08-28T16:00:00Z"), utc("2023-08-28T20:00:00Z")))
+        );
+        // Berlin is an hour closer to UTC once daylight saving time ends.
+        assert_eq!(
+            w.next(utc("2023-10-31T00:00:00Z")).map(|(start, _)| start),
+            Some(utc("2023-11-06T17:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_no_windows_is_always_available() {
+        let at = utc("2023-08-21T03:00:00Z");
+        assert!(is_available(&[], at));
+        assert!(!is_available(
+            &[window(Weekday::Mon, "18:00", "22:00", Berlin)],
+            at
+        ));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse_time("7:05"),
+            Ok(NaiveTime::from_hms_opt(7, 5, 0).unwrap())
+        );
+        assert_eq!(parse_time("24:00"), Ok(NaiveTime::MIN));
+        assert!(parse_time("25:00").is_err());
+        assert_eq!(parse_time_zone(" Europe/Berlin "), Ok(Berlin));
+        assert_eq!(
+            parse_time_zone("Mars/Olympus_Mons"),
+            Err(AvailabilityError::UnknownTimeZone(
+                "Mars/Olympus_Mons".to_string()
+            ))
+        );
+    }
+}
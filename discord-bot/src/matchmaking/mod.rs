@@ -0,0 +1,6 @@
+//! Matchmaking logic that doesn't depend on Discord.
+
+pub mod alerts;
+pub mod availability;
+pub mod queue;
+pub mod rating;
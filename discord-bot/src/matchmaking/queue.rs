@@ -0,0 +1,194 @@
+//! Pairing for the automatic matchmaking queue.
+//!
+//! Players are paired by rating proximity. Every player has a search window that starts
+//! narrow and widens the longer they wait, so that nobody sits in the queue forever.
+
+use chrono::Duration;
+
+/// A player that is currently waiting in the queue.
+#[derive(Debug, Clone)]
+pub struct QueueEntry<K> {
+    /// Whatever the caller uses to identify the entry (a database ID, for example).
+    pub key: K,
+    pub rating: i32,
+    /// How long the player has been waiting.
+    pub waited: Duration,
+}
+
+/// Describes how far apart two ratings may be before they're paired.
+#[derive(Debug, Clone, Copy)]
+pub struct SearchWindow {
+    /// The rating difference allowed as soon as the player joins the queue.
+    pub initial: i32,
+    /// How much the window widens for every minute spent in the queue.
+    pub growth_per_minute: i32,
+    /// The window never grows past this value.
+    pub max: i32,
+}
+
+impl Default for SearchWindow {
+    fn default() -> Self {
+        Self {
+            initial: 100,
+            growth_per_minute: 25,
+            max: 1000,
+        }
+    }
+}
+
+impl SearchWindow {
+    /// The allowed rating difference for a player that has waited for `waited`.
+    #[must_use]
+    pub fn width(&self, waited: Duration) -> i32 {
+        let minutes = i32::try_from(waited.num_minutes().max(0)).unwrap_or(i32::MAX);
+        self.initial
+            .saturating_add(self.growth_per_minute.saturating_mul(minutes))
+            .min(self.max)
+    }
+
+    /// How long a player has to wait before their window reaches its maximum.
+    #[must_use]
+    pub fn time_to_max(&self) -> Duration {
+        if self.growth_per_minute <= 0 {
+            return Duration::zero();
+        }
+
+        let steps = (self.max - self.initial).max(0);
+        Duration::minutes(i64::from(
+            (steps + self.growth_per_minute - 1) / self.growth_per_minute,
+        ))
+    }
+}
+
+/// Pair up as many entries as possible.
+///
+/// The players that have waited the longest are paired first, each with the closest rated
+/// opponent that fits inside both players' windows. Entries that couldn't be paired are left
+/// out of the result.
+#[must_use]
+pub fn find_pairs<K: Clone>(entries: &[QueueEntry<K>], window: &SearchWindow) -> Vec<(K, K)> {
+    find_pairs_where(entries, window, |_, _| true)
+}
+
+/// Like [`find_pairs`], but two entries are only paired if `allowed` returns true for their
+/// keys, like when a guild in a shared pool refuses to play another.
+#[must_use]
+pub fn find_pairs_where<K: Clone>(
+    entries: &[QueueEntry<K>],
+    window: &SearchWindow,
+    allowed: impl Fn(&K, &K) -> bool,
+) -> Vec<(K, K)> {
+    let mut order = (0..entries.len()).collect::<Vec<_>>();
+    order.sort_by(|a, b| entries[*b].waited.cmp(&entries[*a].waited));
+
+    let mut paired = vec![false; entries.len()];
+    let mut pairs = Vec::new();
+
+    for &i in &order {
+        if paired[i] {
+            continue;
+        }
+
+        let player = &entries[i];
+        let player_window = window.width(player.waited);
+
+        let opponent = order
+            .iter()
+            .copied()
+            .filter(|&j| j != i && !paired[j] && allowed(&player.key, &entries[j].key))
+            .filter(|&j| {
+                let diff = (entries[j].rating - player.rating).abs();
+                diff <= player_window.min(window.width(entries[j].waited))
+            })
+            .min_by_key(|&j| (entries[j].rating - player.rating).abs());
+
+        if let Some(j) = opponent {
+            paired[i] = true;
+            paired[j] = true;
+            pairs.push((player.key.clone(), entries[j].key.clone()));
+        }
+    }
+
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: u32, rating: i32, waited_minutes: i64) -> QueueEntry<u32> {
+        QueueEntry {
+            key,
+            rating,
+            waited: Duration::minutes(waited_minutes),
+        }
+    }
+
+    #[test]
+    fn test_window_widens_and_caps() {
+        let window = SearchWindow::default();
+
+        assert_eq!(window.width(Duration::zero()), 100);
+        assert_eq!(window.width(Duration::minutes(4)), 200);
+        assert_eq!(window.width(Duration::hours(10)), 1000);
+    }
+
+    #[test]
+    fn test_time_to_max() {
+        let window = SearchWindow::default();
+
+        assert_eq!(window.time_to_max(), Duration::minutes(36));
+        assert_eq!(window.width(window.time_to_max()), window.max);
+        assert!(window.width(window.time_to_max() - Duration::minutes(1)) < window.max);
+
+        let window = SearchWindow {
+            initial: 100,
+            growth_per_minute: 30,
+            max: 200,
+        };
+        assert_eq!(window.time_to_max(), Duration::minutes(4));
+    }
+
+    #[test]
+    fn test_pairs_closest_ratings() {
+        let entries = vec![entry(1, 1500, 0), entry(2, 1900, 0), entry(3, 1550, 0)];
+
+        let pairs = find_pairs(&entries, &SearchWindow::default());
+
+        assert_eq!(pairs, vec![(1, 3)]);
+    }
+
+    #[test]
+    fn test_long_wait_widens_window() {
+        let entries = vec![entry(1, 1500, 0), entry(2, 1800, 0)];
+        assert!(find_pairs(&entries, &SearchWindow::default()).is_empty());
+
+        // Both players need to be willing to accept the difference.
+        let entries = vec![entry(1, 1500, 20), entry(2, 1800, 0)];
+        assert!(find_pairs(&entries, &SearchWindow::default()).is_empty());
+
+        let entries = vec![entry(1, 1500, 20), entry(2, 1800, 10)];
+        assert_eq!(find_pairs(&entries, &SearchWindow::default()), vec![(1, 2)]);
+    }
+
+    #[test]
+    fn test_longest_waiting_player_goes_first() {
+        let entries = vec![entry(1, 1500, 0), entry(2, 1525, 0), entry(3, 1510, 5)];
+
+        let pairs = find_pairs(&entries, &SearchWindow::default());
+
+        assert_eq!(pairs, vec![(3, 1)]);
+    }
+
+    #[test]
+    fn test_disallowed_pairs_are_skipped() {
+        let entries = vec![entry(1, 1500, 5), entry(2, 1510, 0), entry(3, 1550, 0)];
+
+        // 1 and 2 are the closest, but they may not play each other.
+        let pairs = find_pairs_where(&entries, &SearchWindow::default(), |a, b| {
+            !matches!((a, b), (1, 2) | (2, 1))
+        });
+
+        assert_eq!(pairs, vec![(1, 3)]);
+    }
+}
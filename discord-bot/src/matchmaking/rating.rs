@@ -0,0 +1,83 @@
+//! Elo ratings for matchmaking results.
+//!
+//! Every confirmed result moves both players' ratings by the same amount in opposite
+//! directions. Beating a higher rated opponent is worth more than beating a lower rated one.
+
+/// How many points a single result can move a rating at most.
+pub const K_FACTOR: f64 = 32.0;
+
+/// The result of a match from the first player's point of view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl Outcome {
+    /// The outcome for a player that won `wins` games and lost `losses`.
+    #[must_use]
+    pub fn from_score(wins: i32, losses: i32) -> Self {
+        match wins.cmp(&losses) {
+            std::cmp::Ordering::Greater => Self::Win,
+            std::cmp::Ordering::Equal => Self::Draw,
+            std::cmp::Ordering::Less => Self::Loss,
+        }
+    }
+
+    fn score(self) -> f64 {
+        match self {
+            Self::Win => 1.0,
+            Self::Draw => 0.5,
+            Self::Loss => 0.0,
+        }
+    }
+}
+
+/// The chance that a player rated `rating` beats one rated `opponent`.
+#[must_use]
+pub fn expected_score(rating: i32, opponent: i32) -> f64 {
+    1.0 / (1.0 + 10f64.powf(f64::from(opponent - rating) / 400.0))
+}
+
+/// The new ratings of both players after a match with the given outcome for the first one.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn update(first: i32, second: i32, outcome: Outcome) -> (i32, i32) {
+    let change = (K_FACTOR * (outcome.score() - expected_score(first, second))).round() as i32;
+
+    (first + change, second - change)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equal_ratings() {
+        assert_eq!(update(1500, 1500, Outcome::Win), (1516, 1484));
+        assert_eq!(update(1500, 1500, Outcome::Loss), (1484, 1516));
+        assert_eq!(update(1500, 1500, Outcome::Draw), (1500, 1500));
+    }
+
+    #[test]
+    fn test_upsets_are_worth_more() {
+        let (favourite, _) = update(1700, 1500, Outcome::Win);
+        let (underdog, _) = update(1500, 1700, Outcome::Win);
+
+        assert_eq!(favourite, 1708);
+        assert_eq!(underdog, 1524);
+    }
+
+    #[test]
+    fn test_draw_moves_towards_opponent() {
+        assert_eq!(update(1700, 1500, Outcome::Draw), (1692, 1508));
+    }
+
+    #[test]
+    fn test_outcome_from_score() {
+        assert_eq!(Outcome::from_score(2, 1), Outcome::Win);
+        assert_eq!(Outcome::from_score(1, 1), Outcome::Draw);
+        assert_eq!(Outcome::from_score(0, 3), Outcome::Loss);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::entity::prelude::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PlayerRating)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(player_rating::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(player_rating::Column::Player)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(PlayerRating, player_rating::Column::Player)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(player_rating::Column::GuildId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(player_rating::Column::Game).uuid())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(PlayerRating, player_rating::Column::Game)
+                            .to(Game, game::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(player_rating::Column::Rating)
+                            .integer()
+                            .not_null()
+                            .default(player_rating::DEFAULT_RATING),
+                    )
+                    .col(
+                        ColumnDef::new(player_rating::Column::GamesPlayed)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(player_rating::Column::LastUpdated)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(MatchmakingQueue)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(matchmaking_queue::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(matchmaking_queue::Column::GuildId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(matchmaking_queue::Column::Player)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(MatchmakingQueue, matchmaking_queue::Column::Player)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(matchmaking_queue::Column::Game).uuid())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(MatchmakingQueue, matchmaking_queue::Column::Game)
+                            .to(Game, game::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(matchmaking_queue::Column::Rating)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(matchmaking_queue::Column::JoinedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    // A player can only wait in one queue per guild.
+                    .index(
+                        Index::create()
+                            .unique()
+                            .col(matchmaking_queue::Column::GuildId)
+                            .col(matchmaking_queue::Column::Player),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().if_exists().table(MatchmakingQueue).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().if_exists().table(PlayerRating).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+use crate::entity::{
+    prelude::*, sea_orm::sea_query::extension::postgres::Type, sea_orm_active_enums::LeagueRole,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(Iden)]
+enum LeagueRoleType {
+    LeagueRole,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(LeagueRoleType::LeagueRole)
+                    .values([LeagueRole::Player, LeagueRole::Organizer])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(League)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(league::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(league::Column::GuildId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(league::Column::Name)
+                            .string_len(80)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(league::Column::Game).uuid())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(League, league::Column::Game)
+                            .to(Game, game::Column::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(league::Column::StartDate)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(league::Column::EndDate)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(league::Column::MatchesChannel).big_integer())
+                    .col(ColumnDef::new(league::Column::ReportingChannel).big_integer())
+                    .col(ColumnDef::new(league::Column::CreatedBy).uuid().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(League, league::Column::CreatedBy)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(league::Column::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .col(league::Column::GuildId)
+                            .col(league::Column::Name),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(LeagueParticipant)
+                    .if_not_exists()
+                    .primary_key(
+                        Index::create()
+                            .col(league_participant::Column::League)
+                            .col(league_participant::Column::Player),
+                    )
+                    .col(
+                        ColumnDef::new(league_participant::Column::League)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LeagueParticipant, league_participant::Column::League)
+                            .to(League, league::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(league_participant::Column::Player)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LeagueParticipant, league_participant::Column::Player)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(league_participant::Column::Role)
+                            .enumeration(
+                                LeagueRoleType::LeagueRole,
+                                [LeagueRole::Player, LeagueRole::Organizer],
+                            )
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(league_participant::Column::JoinedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(LeagueParticipant)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().if_exists().table(League).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(LeagueRoleType::LeagueRole)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
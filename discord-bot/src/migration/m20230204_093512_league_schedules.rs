@@ -0,0 +1,155 @@
+use crate::entity::{
+    prelude::*, sea_orm::sea_query::extension::postgres::Type,
+    sea_orm_active_enums::LeagueMatchStatus,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(Iden)]
+enum LeagueMatchStatusType {
+    LeagueMatchStatus,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(LeagueMatchStatusType::LeagueMatchStatus)
+                    .values([
+                        LeagueMatchStatus::Scheduled,
+                        LeagueMatchStatus::Completed,
+                        LeagueMatchStatus::Forfeited,
+                        LeagueMatchStatus::AdminReview,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(League)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(league::Column::ForfeitUnplayed)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(LeagueMatch)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(league_match::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(league_match::Column::League)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LeagueMatch, league_match::Column::League)
+                            .to(League, league::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(league_match::Column::Round)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(league_match::Column::PlayerOne)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LeagueMatch, league_match::Column::PlayerOne)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(league_match::Column::PlayerTwo)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LeagueMatch, league_match::Column::PlayerTwo)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(league_match::Column::Deadline)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(league_match::Column::Status)
+                            .enumeration(
+                                LeagueMatchStatusType::LeagueMatchStatus,
+                                [
+                                    LeagueMatchStatus::Scheduled,
+                                    LeagueMatchStatus::Completed,
+                                    LeagueMatchStatus::Forfeited,
+                                    LeagueMatchStatus::AdminReview,
+                                ],
+                            )
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(league_match::Column::ReminderSent)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().if_exists().table(LeagueMatch).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(League)
+                    .drop_column(league::Column::ForfeitUnplayed)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(LeagueMatchStatusType::LeagueMatchStatus)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,312 @@
+use crate::entity::{prelude::*, sea_orm_active_enums::LeagueMatchStatus};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(Iden)]
+enum LeagueMatchStatusType {
+    LeagueMatchStatus,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(League)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(league::Column::TeamSize).integer().null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(LeagueTeam)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(league_team::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(league_team::Column::League)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LeagueTeam, league_team::Column::League)
+                            .to(League, league::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(league_team::Column::Name)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(league_team::Column::Captain)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LeagueTeam, league_team::Column::Captain)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(league_team::Column::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .col(league_team::Column::League)
+                            .col(league_team::Column::Name),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(LeagueTeamMember)
+                    .if_not_exists()
+                    .primary_key(
+                        Index::create()
+                            .col(league_team_member::Column::Team)
+                            .col(league_team_member::Column::Player),
+                    )
+                    .col(
+                        ColumnDef::new(league_team_member::Column::Team)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LeagueTeamMember, league_team_member::Column::Team)
+                            .to(LeagueTeam, league_team::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(league_team_member::Column::Player)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LeagueTeamMember, league_team_member::Column::Player)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(league_team_member::Column::League)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LeagueTeamMember, league_team_member::Column::League)
+                            .to(League, league::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(league_team_member::Column::JoinedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .col(league_team_member::Column::League)
+                            .col(league_team_member::Column::Player),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(LeagueTeamMatch)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(league_team_match::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(league_team_match::Column::League)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LeagueTeamMatch, league_team_match::Column::League)
+                            .to(League, league::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(league_team_match::Column::Round)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(league_team_match::Column::TeamOne)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LeagueTeamMatch, league_team_match::Column::TeamOne)
+                            .to(LeagueTeam, league_team::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(league_team_match::Column::TeamTwo)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LeagueTeamMatch, league_team_match::Column::TeamTwo)
+                            .to(LeagueTeam, league_team::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(league_team_match::Column::Deadline)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(league_team_match::Column::Status)
+                            .enumeration(
+                                LeagueMatchStatusType::LeagueMatchStatus,
+                                [
+                                    LeagueMatchStatus::Scheduled,
+                                    LeagueMatchStatus::Completed,
+                                    LeagueMatchStatus::Forfeited,
+                                    LeagueMatchStatus::AdminReview,
+                                ],
+                            )
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(league_team_match::Column::ReminderSent)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(LeagueLineup)
+                    .if_not_exists()
+                    .primary_key(
+                        Index::create()
+                            .col(league_lineup::Column::TeamMatch)
+                            .col(league_lineup::Column::Team)
+                            .col(league_lineup::Column::Slot),
+                    )
+                    .col(
+                        ColumnDef::new(league_lineup::Column::TeamMatch)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LeagueLineup, league_lineup::Column::TeamMatch)
+                            .to(LeagueTeamMatch, league_team_match::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(league_lineup::Column::Team)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LeagueLineup, league_lineup::Column::Team)
+                            .to(LeagueTeam, league_team::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(league_lineup::Column::Slot)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(league_lineup::Column::Player)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LeagueLineup, league_lineup::Column::Player)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(league_lineup::Column::Won).boolean().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().if_exists().table(LeagueLineup).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().if_exists().table(LeagueTeamMatch).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().if_exists().table(LeagueTeamMember).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().if_exists().table(LeagueTeam).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(League)
+                    .drop_column(league::Column::TeamSize)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
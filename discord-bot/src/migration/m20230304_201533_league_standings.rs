@@ -0,0 +1,95 @@
+use crate::entity::prelude::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(League)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(league::Column::PointsWin)
+                            .integer()
+                            .not_null()
+                            .default(3),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(league::Column::PointsDraw)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(league::Column::PointsLoss)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(league::Column::Tiebreakers)
+                            .string()
+                            .not_null()
+                            .default("head-to-head,game-differential,strength-of-schedule"),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(league::Column::StandingsPosted)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LeagueMatch)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(league_match::Column::PlayerOneScore)
+                            .integer()
+                            .null(),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(league_match::Column::PlayerTwoScore)
+                            .integer()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LeagueMatch)
+                    .drop_column(league_match::Column::PlayerOneScore)
+                    .drop_column(league_match::Column::PlayerTwoScore)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(League)
+                    .drop_column(league::Column::PointsWin)
+                    .drop_column(league::Column::PointsDraw)
+                    .drop_column(league::Column::PointsLoss)
+                    .drop_column(league::Column::Tiebreakers)
+                    .drop_column(league::Column::StandingsPosted)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
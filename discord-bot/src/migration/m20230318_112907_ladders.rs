@@ -0,0 +1,219 @@
+use crate::entity::{
+    prelude::*, sea_orm::sea_query::extension::postgres::Type,
+    sea_orm_active_enums::LadderChallengeStatus,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(Iden)]
+enum LadderChallengeStatusType {
+    LadderChallengeStatus,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(LadderChallengeStatusType::LadderChallengeStatus)
+                    .values([
+                        LadderChallengeStatus::Pending,
+                        LadderChallengeStatus::Accepted,
+                        LadderChallengeStatus::Completed,
+                        LadderChallengeStatus::Forfeited,
+                        LadderChallengeStatus::Cancelled,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(League)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(league::Column::LadderRange).integer().null(),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(league::Column::ChallengeHours)
+                            .integer()
+                            .not_null()
+                            .default(48),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(LadderPosition)
+                    .if_not_exists()
+                    .primary_key(
+                        Index::create()
+                            .col(ladder_position::Column::League)
+                            .col(ladder_position::Column::Player),
+                    )
+                    .col(
+                        ColumnDef::new(ladder_position::Column::League)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LadderPosition, ladder_position::Column::League)
+                            .to(League, league::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(ladder_position::Column::Player)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LadderPosition, ladder_position::Column::Player)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(ladder_position::Column::Position)
+                            .integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(LadderChallenge)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ladder_challenge::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ladder_challenge::Column::League)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LadderChallenge, ladder_challenge::Column::League)
+                            .to(League, league::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(ladder_challenge::Column::Challenger)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LadderChallenge, ladder_challenge::Column::Challenger)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(ladder_challenge::Column::Defender)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LadderChallenge, ladder_challenge::Column::Defender)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(ladder_challenge::Column::Invitation)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LadderChallenge, ladder_challenge::Column::Invitation)
+                            .to(MatchmakingInvitation, matchmaking_invitation::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(ladder_challenge::Column::Status)
+                            .enumeration(
+                                LadderChallengeStatusType::LadderChallengeStatus,
+                                [
+                                    LadderChallengeStatus::Pending,
+                                    LadderChallengeStatus::Accepted,
+                                    LadderChallengeStatus::Completed,
+                                    LadderChallengeStatus::Forfeited,
+                                    LadderChallengeStatus::Cancelled,
+                                ],
+                            )
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ladder_challenge::Column::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ladder_challenge::Column::AcceptBy)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ladder_challenge::Column::Winner)
+                            .uuid()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().if_exists().table(LadderChallenge).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().if_exists().table(LadderPosition).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(League)
+                    .drop_column(league::Column::LadderRange)
+                    .drop_column(league::Column::ChallengeHours)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(LadderChallengeStatusType::LadderChallengeStatus)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
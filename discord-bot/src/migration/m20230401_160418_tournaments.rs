@@ -0,0 +1,345 @@
+use crate::entity::{
+    prelude::*,
+    sea_orm::sea_query::extension::postgres::Type,
+    sea_orm_active_enums::{BracketSide, TournamentFormat, TournamentState},
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(Iden)]
+enum TournamentFormatType {
+    TournamentFormat,
+}
+
+#[derive(Iden)]
+enum TournamentStateType {
+    TournamentState,
+}
+
+#[derive(Iden)]
+enum BracketSideType {
+    BracketSide,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(TournamentFormatType::TournamentFormat)
+                    .values([
+                        TournamentFormat::SingleElimination,
+                        TournamentFormat::DoubleElimination,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(TournamentStateType::TournamentState)
+                    .values([
+                        TournamentState::Registration,
+                        TournamentState::InProgress,
+                        TournamentState::Completed,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(BracketSideType::BracketSide)
+                    .values([
+                        BracketSide::Winners,
+                        BracketSide::Losers,
+                        BracketSide::GrandFinals,
+                        BracketSide::GrandFinalsReset,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Tournament)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(tournament::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(tournament::Column::GuildId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(tournament::Column::Name).string().not_null())
+                    .col(ColumnDef::new(tournament::Column::Game).uuid().null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Tournament, tournament::Column::Game)
+                            .to(Game, game::Column::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament::Column::Format)
+                            .enumeration(
+                                TournamentFormatType::TournamentFormat,
+                                [
+                                    TournamentFormat::SingleElimination,
+                                    TournamentFormat::DoubleElimination,
+                                ],
+                            )
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(tournament::Column::State)
+                            .enumeration(
+                                TournamentStateType::TournamentState,
+                                [
+                                    TournamentState::Registration,
+                                    TournamentState::InProgress,
+                                    TournamentState::Completed,
+                                ],
+                            )
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(tournament::Column::CreatedBy)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Tournament, tournament::Column::CreatedBy)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament::Column::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(tournament::Column::StartedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .col(tournament::Column::GuildId)
+                            .col(tournament::Column::Name),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TournamentEntrant)
+                    .if_not_exists()
+                    .primary_key(
+                        Index::create()
+                            .col(tournament_entrant::Column::Tournament)
+                            .col(tournament_entrant::Column::Player),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_entrant::Column::Tournament)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TournamentEntrant, tournament_entrant::Column::Tournament)
+                            .to(Tournament, tournament::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_entrant::Column::Player)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TournamentEntrant, tournament_entrant::Column::Player)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_entrant::Column::Seed)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_entrant::Column::RegisteredAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TournamentMatch)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(tournament_match::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_match::Column::Tournament)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TournamentMatch, tournament_match::Column::Tournament)
+                            .to(Tournament, tournament::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_match::Column::Position)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_match::Column::Side)
+                            .enumeration(
+                                BracketSideType::BracketSide,
+                                [
+                                    BracketSide::Winners,
+                                    BracketSide::Losers,
+                                    BracketSide::GrandFinals,
+                                    BracketSide::GrandFinalsReset,
+                                ],
+                            )
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_match::Column::Round)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_match::Column::EntrantOne)
+                            .uuid()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TournamentMatch, tournament_match::Column::EntrantOne)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_match::Column::EntrantTwo)
+                            .uuid()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TournamentMatch, tournament_match::Column::EntrantTwo)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_match::Column::Winner)
+                            .uuid()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TournamentMatch, tournament_match::Column::Winner)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_match::Column::CompletedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .col(tournament_match::Column::Tournament)
+                            .col(tournament_match::Column::Position),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().if_exists().table(TournamentMatch).to_owned())
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(TournamentEntrant)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().if_exists().table(Tournament).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(BracketSideType::BracketSide)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(TournamentStateType::TournamentState)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(TournamentFormatType::TournamentFormat)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+use crate::entity::{
+    prelude::*, sea_orm::sea_query::extension::postgres::Type,
+    sea_orm_active_enums::TournamentFormat,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(Iden)]
+enum TournamentFormatType {
+    TournamentFormat,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_type(
+                Type::alter()
+                    .name(TournamentFormatType::TournamentFormat)
+                    .add_value(TournamentFormat::Swiss),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournament)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament::Column::Channel)
+                            .big_integer()
+                            .null(),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament::Column::SwissRounds)
+                            .integer()
+                            .null(),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament::Column::TopCut).integer().null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TournamentSwissMatch)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(tournament_swiss_match::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_swiss_match::Column::Tournament)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                TournamentSwissMatch,
+                                tournament_swiss_match::Column::Tournament,
+                            )
+                            .to(Tournament, tournament::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_swiss_match::Column::Round)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_swiss_match::Column::EntrantOne)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                TournamentSwissMatch,
+                                tournament_swiss_match::Column::EntrantOne,
+                            )
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_swiss_match::Column::EntrantTwo)
+                            .uuid()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                TournamentSwissMatch,
+                                tournament_swiss_match::Column::EntrantTwo,
+                            )
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_swiss_match::Column::Winner)
+                            .uuid()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TournamentSwissMatch, tournament_swiss_match::Column::Winner)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_swiss_match::Column::Lobby)
+                            .uuid()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TournamentSwissMatch, tournament_swiss_match::Column::Lobby)
+                            .to(MatchmakingLobbies, matchmaking_lobbies::Column::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_swiss_match::Column::CompletedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(TournamentSwissMatch)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournament)
+                    .drop_column(tournament::Column::Channel)
+                    .drop_column(tournament::Column::SwissRounds)
+                    .drop_column(tournament::Column::TopCut)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Postgres can't remove a value from an enum, so the swiss format stays behind.
+        Ok(())
+    }
+}
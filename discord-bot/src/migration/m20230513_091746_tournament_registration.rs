@@ -0,0 +1,85 @@
+use crate::entity::prelude::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournament)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament::Column::MaxEntrants)
+                            .integer()
+                            .null(),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament::Column::StartsAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament::Column::CheckinMinutes)
+                            .integer()
+                            .null(),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament::Column::PanelMessage)
+                            .big_integer()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TournamentEntrant)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament_entrant::Column::Waitlisted)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament_entrant::Column::CheckedInAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TournamentEntrant)
+                    .drop_column(tournament_entrant::Column::Waitlisted)
+                    .drop_column(tournament_entrant::Column::CheckedInAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournament)
+                    .drop_column(tournament::Column::MaxEntrants)
+                    .drop_column(tournament::Column::StartsAt)
+                    .drop_column(tournament::Column::CheckinMinutes)
+                    .drop_column(tournament::Column::PanelMessage)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
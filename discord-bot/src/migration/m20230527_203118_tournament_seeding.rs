@@ -0,0 +1,39 @@
+use crate::entity::prelude::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournament)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament::Column::SeparateTeams)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournament)
+                    .drop_column(tournament::Column::SeparateTeams)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use crate::entity::prelude::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournament)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament::Column::BracketMessage)
+                            .big_integer()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournament)
+                    .drop_column(tournament::Column::BracketMessage)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
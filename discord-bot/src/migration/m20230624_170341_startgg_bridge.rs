@@ -0,0 +1,246 @@
+use crate::entity::{
+    prelude::*, sea_orm::sea_query::extension::postgres::Type, sea_orm_active_enums::BracketSource,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(Iden)]
+enum BracketSourceType {
+    BracketSource,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(BracketSourceType::BracketSource)
+                    .values([BracketSource::StartGg])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournament)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament::Column::Source)
+                            .enumeration(BracketSourceType::BracketSource, [BracketSource::StartGg])
+                            .null(),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament::Column::SourceEvent)
+                            .string()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TournamentEntrant)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament_entrant::Column::RemoteId)
+                            .string()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TournamentRemoteSet)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(tournament_remote_set::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_remote_set::Column::Tournament)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                TournamentRemoteSet,
+                                tournament_remote_set::Column::Tournament,
+                            )
+                            .to(Tournament, tournament::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_remote_set::Column::RemoteId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_remote_set::Column::Round)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_remote_set::Column::EntrantOne)
+                            .uuid()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                TournamentRemoteSet,
+                                tournament_remote_set::Column::EntrantOne,
+                            )
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_remote_set::Column::EntrantTwo)
+                            .uuid()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                TournamentRemoteSet,
+                                tournament_remote_set::Column::EntrantTwo,
+                            )
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_remote_set::Column::Winner)
+                            .uuid()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TournamentRemoteSet, tournament_remote_set::Column::Winner)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_remote_set::Column::ReportedBy)
+                            .uuid()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                TournamentRemoteSet,
+                                tournament_remote_set::Column::ReportedBy,
+                            )
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_remote_set::Column::ReportedWinner)
+                            .uuid()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                TournamentRemoteSet,
+                                tournament_remote_set::Column::ReportedWinner,
+                            )
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_remote_set::Column::WinnerGames)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_remote_set::Column::LoserGames)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_remote_set::Column::Lobby)
+                            .uuid()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TournamentRemoteSet, tournament_remote_set::Column::Lobby)
+                            .to(MatchmakingLobbies, matchmaking_lobbies::Column::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(tournament_remote_set::Column::CompletedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .col(tournament_remote_set::Column::Tournament)
+                            .col(tournament_remote_set::Column::RemoteId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(TournamentRemoteSet)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TournamentEntrant)
+                    .drop_column(tournament_entrant::Column::RemoteId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournament)
+                    .drop_column(tournament::Column::Source)
+                    .drop_column(tournament::Column::SourceEvent)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(BracketSourceType::BracketSource)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use crate::entity::{
+    prelude::*, sea_orm::sea_query::extension::postgres::Type, sea_orm_active_enums::BracketSource,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(Iden)]
+enum BracketSourceType {
+    BracketSource,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_type(
+                Type::alter()
+                    .name(BracketSourceType::BracketSource)
+                    .add_value(BracketSource::Challonge),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(BracketCredentials)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(bracket_credentials::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(bracket_credentials::Column::GuildId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(bracket_credentials::Column::Source)
+                            .enumeration(
+                                BracketSourceType::BracketSource,
+                                [BracketSource::StartGg, BracketSource::Challonge],
+                            )
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(bracket_credentials::Column::ApiKey)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(bracket_credentials::Column::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .col(bracket_credentials::Column::GuildId)
+                            .col(bracket_credentials::Column::Source),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(BracketCredentials)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Postgres can't remove a value from an enum, so the challonge source stays behind.
+        Ok(())
+    }
+}
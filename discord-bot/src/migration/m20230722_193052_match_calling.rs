@@ -0,0 +1,91 @@
+use crate::entity::prelude::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournament)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament::Column::DqMinutes)
+                            .integer()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TournamentMatch)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament_match::Column::Lobby).uuid(),
+                    )
+                    .add_foreign_key(
+                        ForeignKey::create()
+                            .from(TournamentMatch, tournament_match::Column::Lobby)
+                            .to(MatchmakingLobbies, matchmaking_lobbies::Column::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .to_owned()
+                            .get_foreign_key(),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament_match::Column::CalledAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament_match::Column::EntrantOneReadyAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament_match::Column::EntrantTwoReadyAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament_match::Column::DqPromptedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TournamentMatch)
+                    .drop_column(tournament_match::Column::Lobby)
+                    .drop_column(tournament_match::Column::CalledAt)
+                    .drop_column(tournament_match::Column::EntrantOneReadyAt)
+                    .drop_column(tournament_match::Column::EntrantTwoReadyAt)
+                    .drop_column(tournament_match::Column::DqPromptedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournament)
+                    .drop_column(tournament::Column::DqMinutes)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
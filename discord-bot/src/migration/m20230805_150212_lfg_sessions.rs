@@ -0,0 +1,97 @@
+use crate::entity::prelude::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(LfgSession)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(lfg_session::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(lfg_session::Column::GuildId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(lfg_session::Column::Player)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LfgSession, lfg_session::Column::Player)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(lfg_session::Column::Game).uuid())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LfgSession, lfg_session::Column::Game)
+                            .to(Game, game::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(lfg_session::Column::Comment).string())
+                    .col(
+                        ColumnDef::new(lfg_session::Column::StartedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(lfg_session::Column::ExpiresAt).timestamp_with_time_zone())
+                    // A player can only be on one board per guild.
+                    .index(
+                        Index::create()
+                            .unique()
+                            .col(lfg_session::Column::GuildId)
+                            .col(lfg_session::Column::Player),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(MatchmakingSettings)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(matchmaking_settings::Column::LfgBoardMessage)
+                            .big_integer()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(MatchmakingSettings)
+                    .drop_column(matchmaking_settings::Column::LfgBoardMessage)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().if_exists().table(LfgSession).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
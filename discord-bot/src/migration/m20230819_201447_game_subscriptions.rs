@@ -0,0 +1,111 @@
+use crate::entity::{
+    prelude::*, sea_orm::sea_query::extension::postgres::Type,
+    sea_orm_active_enums::SubscriptionAlert,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(Iden)]
+enum SubscriptionAlertType {
+    SubscriptionAlert,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(SubscriptionAlertType::SubscriptionAlert)
+                    .values([SubscriptionAlert::Dm, SubscriptionAlert::Ping])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(GameSubscription)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(game_subscription::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(game_subscription::Column::GuildId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(game_subscription::Column::Player)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(GameSubscription, game_subscription::Column::Player)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(game_subscription::Column::Game)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(GameSubscription, game_subscription::Column::Game)
+                            .to(Game, game::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(game_subscription::Column::Alert)
+                            .enumeration(
+                                SubscriptionAlertType::SubscriptionAlert,
+                                [SubscriptionAlert::Dm, SubscriptionAlert::Ping],
+                            )
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(game_subscription::Column::LastAlertedAt)
+                            .timestamp_with_time_zone(),
+                    )
+                    // A player subscribes to a game once per guild.
+                    .index(
+                        Index::create()
+                            .unique()
+                            .col(game_subscription::Column::GuildId)
+                            .col(game_subscription::Column::Player)
+                            .col(game_subscription::Column::Game),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().if_exists().table(GameSubscription).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(SubscriptionAlertType::SubscriptionAlert)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use crate::entity::prelude::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AvailabilityWindow)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(availability_window::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(availability_window::Column::Player)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(AvailabilityWindow, availability_window::Column::Player)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(availability_window::Column::Weekday)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(availability_window::Column::StartTime)
+                            .time()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(availability_window::Column::EndTime)
+                            .time()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(availability_window::Column::TimeZone)
+                            .string()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(AvailabilityWindow)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
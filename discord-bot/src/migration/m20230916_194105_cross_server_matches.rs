@@ -0,0 +1,134 @@
+use crate::entity::prelude::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CrossServerMatch)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(cross_server_match::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(cross_server_match::Column::Game).uuid())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(CrossServerMatch, cross_server_match::Column::Game)
+                            .to(Game, game::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(cross_server_match::Column::FirstLobby)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(CrossServerMatch, cross_server_match::Column::FirstLobby)
+                            .to(MatchmakingLobbies, matchmaking_lobbies::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(cross_server_match::Column::SecondLobby)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(CrossServerMatch, cross_server_match::Column::SecondLobby)
+                            .to(MatchmakingLobbies, matchmaking_lobbies::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(cross_server_match::Column::FirstPlayer)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(CrossServerMatch, cross_server_match::Column::FirstPlayer)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(cross_server_match::Column::SecondPlayer)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(CrossServerMatch, cross_server_match::Column::SecondPlayer)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(cross_server_match::Column::ReportedBy).uuid())
+                    .col(ColumnDef::new(cross_server_match::Column::FirstPlayerWins).integer())
+                    .col(ColumnDef::new(cross_server_match::Column::SecondPlayerWins).integer())
+                    .col(
+                        ColumnDef::new(cross_server_match::Column::StartedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(cross_server_match::Column::CompletedAt)
+                            .timestamp_with_time_zone(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(MatchmakingSettings)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(matchmaking_settings::Column::CrossServerGames)
+                            .json_binary()
+                            .not_null()
+                            .default("[]"),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(matchmaking_settings::Column::BlockedGuilds)
+                            .json_binary()
+                            .not_null()
+                            .default("[]"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(MatchmakingSettings)
+                    .drop_column(matchmaking_settings::Column::CrossServerGames)
+                    .drop_column(matchmaking_settings::Column::BlockedGuilds)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().if_exists().table(CrossServerMatch).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+use crate::entity::{
+    prelude::*, sea_orm::sea_query::extension::postgres::Type, sea_orm_active_enums::AuditAction,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(Iden)]
+enum AuditActionType {
+    AuditAction,
+}
+
+const AUDIT_ACTIONS: [AuditAction; 6] = [
+    AuditAction::MatchmakingChannel,
+    AuditAction::AdminRole,
+    AuditAction::LobbyThreads,
+    AuditAction::CrossServerGames,
+    AuditAction::BlockedServers,
+    AuditAction::AuditChannel,
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(AuditActionType::AuditAction)
+                    .values(AUDIT_ACTIONS)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditLog)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(audit_log::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(audit_log::Column::GuildId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(audit_log::Column::Actor).uuid().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(AuditLog, audit_log::Column::Actor)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(audit_log::Column::Action)
+                            .enumeration(AuditActionType::AuditAction, AUDIT_ACTIONS)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(audit_log::Column::Target).uuid())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(AuditLog, audit_log::Column::Target)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(audit_log::Column::Details)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(audit_log::Column::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(MatchmakingSettings)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(matchmaking_settings::Column::AuditChannel)
+                            .big_integer()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(MatchmakingSettings)
+                    .drop_column(matchmaking_settings::Column::AuditChannel)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().if_exists().table(AuditLog).to_owned())
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .name(AuditActionType::AuditAction)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use crate::entity::{
+    prelude::*, sea_orm::sea_query::extension::postgres::Type, sea_orm_active_enums::AuditAction,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(Iden)]
+enum AuditActionType {
+    AuditAction,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for action in [AuditAction::MatchmakingBan, AuditAction::MatchmakingUnban] {
+            manager
+                .alter_type(
+                    Type::alter()
+                        .name(AuditActionType::AuditAction)
+                        .add_value(action)
+                        .clone(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(MatchmakingBan)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(matchmaking_ban::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(matchmaking_ban::Column::GuildId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(matchmaking_ban::Column::Player)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(MatchmakingBan, matchmaking_ban::Column::Player)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(matchmaking_ban::Column::BannedBy)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(MatchmakingBan, matchmaking_ban::Column::BannedBy)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(matchmaking_ban::Column::Reason).string())
+                    .col(
+                        ColumnDef::new(matchmaking_ban::Column::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(matchmaking_ban::Column::ExpiresAt)
+                            .timestamp_with_time_zone(),
+                    )
+                    // A player has at most one ban per guild.
+                    .index(
+                        Index::create()
+                            .unique()
+                            .col(matchmaking_ban::Column::GuildId)
+                            .col(matchmaking_ban::Column::Player),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Postgres can't remove values from an enum, so the audit actions stay.
+        manager
+            .drop_table(Table::drop().if_exists().table(MatchmakingBan).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
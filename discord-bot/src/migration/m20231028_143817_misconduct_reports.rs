@@ -0,0 +1,175 @@
+use crate::entity::{
+    prelude::*,
+    sea_orm::sea_query::extension::postgres::Type,
+    sea_orm_active_enums::{AuditAction, ReportCategory, ReportStatus},
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(Iden)]
+enum ReportTypes {
+    AuditAction,
+    ReportCategory,
+    ReportStatus,
+}
+
+const REPORT_CATEGORIES: [ReportCategory; 5] = [
+    ReportCategory::Cheating,
+    ReportCategory::Harassment,
+    ReportCategory::Unsportsmanlike,
+    ReportCategory::NoShow,
+    ReportCategory::Other,
+];
+
+const REPORT_STATUSES: [ReportStatus; 4] = [
+    ReportStatus::Open,
+    ReportStatus::Claimed,
+    ReportStatus::Resolved,
+    ReportStatus::Dismissed,
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_type(
+                Type::alter()
+                    .name(ReportTypes::AuditAction)
+                    .add_value(AuditAction::ReportChannel)
+                    .clone(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(ReportTypes::ReportCategory)
+                    .values(REPORT_CATEGORIES)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(ReportTypes::ReportStatus)
+                    .values(REPORT_STATUSES)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Report)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(report::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(report::Column::GuildId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(report::Column::Reporter).uuid().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Report, report::Column::Reporter)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(report::Column::Reported).uuid().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Report, report::Column::Reported)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(report::Column::Category)
+                            .enumeration(ReportTypes::ReportCategory, REPORT_CATEGORIES)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(report::Column::Description)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(report::Column::LobbyLink).string())
+                    .col(
+                        ColumnDef::new(report::Column::Attachments)
+                            .json_binary()
+                            .not_null()
+                            .default("[]"),
+                    )
+                    .col(
+                        ColumnDef::new(report::Column::Status)
+                            .enumeration(ReportTypes::ReportStatus, REPORT_STATUSES)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(report::Column::HandledBy).uuid())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Report, report::Column::HandledBy)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(report::Column::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(report::Column::ClosedAt).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(MatchmakingSettings)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(matchmaking_settings::Column::ReportChannel)
+                            .big_integer()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(MatchmakingSettings)
+                    .drop_column(matchmaking_settings::Column::ReportChannel)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().if_exists().table(Report).to_owned())
+            .await?;
+
+        for name in [ReportTypes::ReportStatus, ReportTypes::ReportCategory] {
+            manager
+                .drop_type(Type::drop().name(name).if_exists().to_owned())
+                .await?;
+        }
+
+        // Postgres can't remove values from an enum, so the audit action stays.
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use crate::entity::prelude::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LeagueMatch)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(league_match::Column::ReportedBy)
+                            .uuid()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LeagueMatch)
+                    .drop_column(league_match::Column::ReportedBy)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use crate::entity::{
+    prelude::*, sea_orm::sea_query::extension::postgres::Type,
+    sea_orm_active_enums::LadderChallengeStatus,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(Iden)]
+enum LadderChallengeStatusType {
+    LadderChallengeStatus,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_type(
+                Type::alter()
+                    .name(LadderChallengeStatusType::LadderChallengeStatus)
+                    .add_value(LadderChallengeStatus::Disputed)
+                    .clone(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LadderChallenge)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(ladder_challenge::Column::PlayBy)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(ladder_challenge::Column::ReportedBy)
+                            .uuid()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Postgres can't remove values from an enum, so the disputed status stays.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LadderChallenge)
+                    .drop_column(ladder_challenge::Column::PlayBy)
+                    .drop_column(ladder_challenge::Column::ReportedBy)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use crate::entity::prelude::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TournamentPoolMatch)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament_pool_match::Column::ReportedBy)
+                            .uuid()
+                            .null(),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(tournament_pool_match::Column::ReportedWinner)
+                            .uuid()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TournamentPoolMatch)
+                    .drop_column(tournament_pool_match::Column::ReportedBy)
+                    .drop_column(tournament_pool_match::Column::ReportedWinner)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
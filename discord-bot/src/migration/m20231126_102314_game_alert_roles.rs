@@ -0,0 +1,56 @@
+use crate::entity::{
+    prelude::*, sea_orm::sea_query::extension::postgres::Type, sea_orm_active_enums::AuditAction,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(Iden)]
+enum AuditActionType {
+    AuditAction,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(MatchmakingSettings)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(matchmaking_settings::Column::AlertRoles)
+                            .json_binary()
+                            .not_null()
+                            .default("{}"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_type(
+                Type::alter()
+                    .name(AuditActionType::AuditAction)
+                    .add_value(AuditAction::AlertRoles)
+                    .clone(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Postgres can't remove values from an enum, so the audit action stays.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(MatchmakingSettings)
+                    .drop_column(matchmaking_settings::Column::AlertRoles)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use crate::entity::{
+    sea_orm::sea_query::extension::postgres::Type, sea_orm_active_enums::AuditAction,
+};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(Iden)]
+enum AuditActionType {
+    AuditAction,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for action in [
+            AuditAction::ReportClaimed,
+            AuditAction::ReportResolved,
+            AuditAction::ReportDismissed,
+        ] {
+            manager
+                .alter_type(
+                    Type::alter()
+                        .name(AuditActionType::AuditAction)
+                        .add_value(action)
+                        .clone(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        // Postgres can't remove values from an enum, so the audit actions stay.
+        Ok(())
+    }
+}
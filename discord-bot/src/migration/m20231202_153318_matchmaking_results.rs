@@ -0,0 +1,109 @@
+use crate::entity::prelude::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(MatchmakingResult)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(matchmaking_result::Column::Id)
+                            .uuid()
+                            .primary_key()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(matchmaking_result::Column::Lobby)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(MatchmakingResult, matchmaking_result::Column::Lobby)
+                            .to(MatchmakingLobbies, matchmaking_lobbies::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(matchmaking_result::Column::GuildId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(matchmaking_result::Column::Game).uuid())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(MatchmakingResult, matchmaking_result::Column::Game)
+                            .to(Game, game::Column::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(matchmaking_result::Column::ReportedBy)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(MatchmakingResult, matchmaking_result::Column::ReportedBy)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(matchmaking_result::Column::Opponent)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(MatchmakingResult, matchmaking_result::Column::Opponent)
+                            .to(Users, users::Column::UserId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(matchmaking_result::Column::ReporterWins)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(matchmaking_result::Column::OpponentWins)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(matchmaking_result::Column::ReportedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(matchmaking_result::Column::ConfirmedAt)
+                            .timestamp_with_time_zone(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(MatchmakingResult)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
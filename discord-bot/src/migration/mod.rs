@@ -4,6 +4,37 @@ mod deprecated;
 
 mod m20221004_222202_create_tables;
 mod m20221111_215309_remove_invitation_junction;
+mod m20230107_181244_matchmaking_queue;
+mod m20230121_154730_create_leagues;
+mod m20230204_093512_league_schedules;
+mod m20230218_140251_league_teams;
+mod m20230304_201533_league_standings;
+mod m20230318_112907_ladders;
+mod m20230401_160418_tournaments;
+mod m20230415_103022_swiss;
+mod m20230429_181204_pools;
+mod m20230513_091746_tournament_registration;
+mod m20230527_203118_tournament_seeding;
+mod m20230610_144529_bracket_image;
+mod m20230624_170341_startgg_bridge;
+mod m20230708_124415_challonge_bridge;
+mod m20230722_193052_match_calling;
+mod m20230805_150212_lfg_sessions;
+mod m20230819_201447_game_subscriptions;
+mod m20230902_172530_availability_windows;
+mod m20230916_194105_cross_server_matches;
+mod m20230930_112634_audit_log;
+mod m20231014_160952_matchmaking_bans;
+mod m20231028_143817_misconduct_reports;
+mod m20231111_120455_lobby_audit_actions;
+mod m20231125_141208_league_match_confirmation;
+mod m20231125_152340_ladder_challenge_confirmation;
+mod m20231125_163015_tournament_match_confirmation;
+mod m20231125_174422_swiss_confirmation;
+mod m20231125_181907_pool_confirmation;
+mod m20231126_102314_game_alert_roles;
+mod m20231126_114530_report_audit_actions;
+mod m20231202_153318_matchmaking_results;
 
 pub struct Migrator;
 
@@ -13,6 +44,37 @@ impl MigratorTrait for Migrator {
         vec![
             Box::new(m20221004_222202_create_tables::Migration),
             Box::new(m20221111_215309_remove_invitation_junction::Migration),
+            Box::new(m20230107_181244_matchmaking_queue::Migration),
+            Box::new(m20230121_154730_create_leagues::Migration),
+            Box::new(m20230204_093512_league_schedules::Migration),
+            Box::new(m20230218_140251_league_teams::Migration),
+            Box::new(m20230304_201533_league_standings::Migration),
+            Box::new(m20230318_112907_ladders::Migration),
+            Box::new(m20230401_160418_tournaments::Migration),
+            Box::new(m20230415_103022_swiss::Migration),
+            Box::new(m20230429_181204_pools::Migration),
+            Box::new(m20230513_091746_tournament_registration::Migration),
+            Box::new(m20230527_203118_tournament_seeding::Migration),
+            Box::new(m20230610_144529_bracket_image::Migration),
+            Box::new(m20230624_170341_startgg_bridge::Migration),
+            Box::new(m20230708_124415_challonge_bridge::Migration),
+            Box::new(m20230722_193052_match_calling::Migration),
+            Box::new(m20230805_150212_lfg_sessions::Migration),
+            Box::new(m20230819_201447_game_subscriptions::Migration),
+            Box::new(m20230902_172530_availability_windows::Migration),
+            Box::new(m20230916_194105_cross_server_matches::Migration),
+            Box::new(m20230930_112634_audit_log::Migration),
+            Box::new(m20231014_160952_matchmaking_bans::Migration),
+            Box::new(m20231028_143817_misconduct_reports::Migration),
+            Box::new(m20231111_120455_lobby_audit_actions::Migration),
+            Box::new(m20231125_141208_league_match_confirmation::Migration),
+            Box::new(m20231125_152340_ladder_challenge_confirmation::Migration),
+            Box::new(m20231125_163015_tournament_match_confirmation::Migration),
+            Box::new(m20231125_174422_swiss_confirmation::Migration),
+            Box::new(m20231125_181907_pool_confirmation::Migration),
+            Box::new(m20231126_102314_game_alert_roles::Migration),
+            Box::new(m20231126_114530_report_audit_actions::Migration),
+            Box::new(m20231202_153318_matchmaking_results::Migration),
         ]
     }
 }
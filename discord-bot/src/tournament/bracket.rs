@@ -0,0 +1,656 @@
+//! Single and double elimination brackets.
+//!
+//! Entrants are referred to by their seed index, where `0` is the top seed. The bracket
+//! only stores the reported results, and everything else (who plays in a match, who
+//! advances, and who is dropped into the losers bracket) is derived from them.
+
+use std::fmt::Display;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    SingleElimination,
+    DoubleElimination,
+}
+
+/// The part of the bracket that a match belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Winners,
+    Losers,
+    GrandFinals,
+    /// The second grand finals set, which is only played if the losers bracket champion wins
+    /// the first one.
+    GrandFinalsReset,
+}
+
+/// Who plays in one side of a match.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Participant {
+    /// The previous match hasn't been decided yet.
+    Pending,
+    Entrant(usize),
+    /// Nobody will play here, so the opponent advances automatically.
+    Bye,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Source {
+    Seed(usize),
+    WinnerOf(usize),
+    LoserOf(usize),
+    /// The grand finals reset takes the players of grand finals, if it's needed.
+    ResetOf(usize),
+}
+
+#[derive(Clone, Debug)]
+pub struct Match {
+    pub id: usize,
+    pub side: Side,
+    /// The round within the match's side, starting at 1.
+    pub round: u32,
+    sources: [Source; 2],
+    result: Option<usize>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct State {
+    slots: [Participant; 2],
+    winner: Option<Participant>,
+    loser: Option<Participant>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BracketError {
+    NotEnoughEntrants,
+    UnknownMatch(usize),
+    /// The match is still waiting for its players, or it has already been decided.
+    NotReady(usize),
+    /// The winner doesn't play in the match.
+    NotInMatch {
+        id: usize,
+        entrant: usize,
+    },
+}
+
+impl std::error::Error for BracketError {}
+
+impl Display for BracketError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BracketError::NotEnoughEntrants => write!(f, "a bracket needs at least two entrants"),
+            BracketError::UnknownMatch(id) => write!(f, "match {id} is not in this bracket"),
+            BracketError::NotReady(id) => write!(f, "match {id} can't be reported right now"),
+            BracketError::NotInMatch { id, entrant } => {
+                write!(f, "entrant {entrant} doesn't play in match {id}")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Bracket {
+    format: Format,
+    entrants: usize,
+    matches: Vec<Match>,
+    states: Vec<State>,
+}
+
+impl Bracket {
+    /// Creates a bracket for `entrants` seeded entrants. Fields that aren't a power of two
+    /// are padded with byes, which go to the top seeds.
+    ///
+    /// # Errors
+    /// Fails if there are fewer than two entrants.
+    pub fn new(format: Format, entrants: usize) -> Result<Self, BracketError> {
+        if entrants < 2 {
+            return Err(BracketError::NotEnoughEntrants);
+        }
+
+        let size = entrants.next_power_of_two();
+        let mut matches = Vec::new();
+        let mut push = |side, round, sources| {
+            let id = matches.len();
+            matches.push(Match {
+                id,
+                side,
+                round,
+                sources,
+                result: None,
+            });
+            id
+        };
+
+        // Winners bracket
+        let order = seed_order(size);
+        let mut winners_rounds: Vec<Vec<usize>> = vec![order
+            .chunks(2)
+            .map(|pair| {
+                push(
+                    Side::Winners,
+                    1,
+                    [Source::Seed(pair[0]), Source::Seed(pair[1])],
+                )
+            })
+            .collect()];
+
+        while winners_rounds.last().map_or(0, Vec::len) > 1 {
+            let round = u32::try_from(winners_rounds.len() + 1).unwrap_or(u32::MAX);
+            let previous = winners_rounds.last().cloned().unwrap_or_default();
+            winners_rounds.push(
+                previous
+                    .chunks(2)
+                    .map(|pair| {
+                        push(
+                            Side::Winners,
+                            round,
+                            [Source::WinnerOf(pair[0]), Source::WinnerOf(pair[1])],
+                        )
+                    })
+                    .collect(),
+            );
+        }
+
+        let winners_final = winners_rounds.last().and_then(|r| r.first()).copied();
+
+        if format == Format::DoubleElimination {
+            let winners_final = winners_final.unwrap_or_default();
+
+            // The losers of the first round play each other, and every round after that
+            // alternates between a round where the losers of the next winners round drop in,
+            // and a round where the remaining losers bracket players play each other.
+            let mut losers: Vec<Source> = winners_rounds[0]
+                .iter()
+                .map(|m| Source::LoserOf(*m))
+                .collect();
+            let mut round = 0;
+
+            for (i, winners_round) in winners_rounds.iter().enumerate().skip(1) {
+                if losers.len() > 1 {
+                    round += 1;
+                    losers = losers
+                        .chunks(2)
+                        .map(|pair| Source::WinnerOf(push(Side::Losers, round, [pair[0], pair[1]])))
+                        .collect();
+                }
+
+                // Drop the losers in reverse order every other round to delay rematches.
+                let mut dropped = winners_round
+                    .iter()
+                    .map(|m| Source::LoserOf(*m))
+                    .collect::<Vec<_>>();
+                if i % 2 == 1 {
+                    dropped.reverse();
+                }
+
+                round += 1;
+                losers = losers
+                    .iter()
+                    .zip(dropped)
+                    .map(|(a, b)| Source::WinnerOf(push(Side::Losers, round, [*a, b])))
+                    .collect();
+            }
+
+            let losers_champion = losers
+                .first()
+                .copied()
+                .unwrap_or(Source::LoserOf(winners_final));
+
+            let grand_finals = push(
+                Side::GrandFinals,
+                1,
+                [Source::WinnerOf(winners_final), losers_champion],
+            );
+            push(
+                Side::GrandFinalsReset,
+                1,
+                [Source::ResetOf(grand_finals), Source::ResetOf(grand_finals)],
+            );
+        }
+
+        let mut bracket = Self {
+            format,
+            entrants,
+            matches,
+            states: Vec::new(),
+        };
+        bracket.refresh();
+
+        Ok(bracket)
+    }
+
+    /// Rebuilds a bracket from its stored results, given as `(match, winner)` pairs.
+    ///
+    /// # Errors
+    /// Fails if a result couldn't have been reported in this bracket.
+    pub fn restore(
+        format: Format,
+        entrants: usize,
+        results: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Result<Self, BracketError> {
+        let mut bracket = Self::new(format, entrants)?;
+
+        // Results can only be reported once both players are known, which is always the case
+        // when they're reported in match order.
+        let mut results = results.into_iter().collect::<Vec<_>>();
+        results.sort_unstable();
+
+        for (id, winner) in results {
+            bracket.report(id, winner)?;
+        }
+
+        Ok(bracket)
+    }
+
+    #[must_use]
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    #[must_use]
+    pub fn entrants(&self) -> usize {
+        self.entrants
+    }
+
+    #[must_use]
+    pub fn matches(&self) -> &[Match] {
+        &self.matches
+    }
+
+    /// The players of a match, in order.
+    #[must_use]
+    pub fn slots(&self, id: usize) -> Option<[Participant; 2]> {
+        self.states.get(id).map(|s| s.slots)
+    }
+
+    /// The winner of a match, once it's been decided.
+    #[must_use]
+    pub fn winner(&self, id: usize) -> Option<Participant> {
+        self.states.get(id).and_then(|s| s.winner)
+    }
+
+    #[must_use]
+    pub fn loser(&self, id: usize) -> Option<Participant> {
+        self.states.get(id).and_then(|s| s.loser)
+    }
+
+    /// The result that was reported for a match. Matches that were decided by a bye don't
+    /// have one.
+    #[must_use]
+    pub fn result(&self, id: usize) -> Option<usize> {
+        self.matches.get(id).and_then(|m| m.result)
+    }
+
+    /// The matches where both players are known and no result has been reported.
+    #[must_use]
+    pub fn ready_matches(&self) -> Vec<usize> {
+        self.states
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| {
+                s.winner.is_none()
+                    && matches!(s.slots, [Participant::Entrant(_), Participant::Entrant(_)])
+            })
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// Reports the winner of a match, and advances both players.
+    ///
+    /// # Errors
+    /// Fails if the match isn't ready to be played, or the winner doesn't play in it.
+    pub fn report(&mut self, id: usize, winner: usize) -> Result<(), BracketError> {
+        let state = self.states.get(id).ok_or(BracketError::UnknownMatch(id))?;
+
+        if !self.ready_matches().contains(&id) {
+            return Err(BracketError::NotReady(id));
+        }
+
+        if !state.slots.contains(&Participant::Entrant(winner)) {
+            return Err(BracketError::NotInMatch {
+                id,
+                entrant: winner,
+            });
+        }
+
+        self.matches[id].result = Some(winner);
+        self.refresh();
+
+        Ok(())
+    }
+
+    /// The winner of the bracket, once every match has been decided.
+    #[must_use]
+    pub fn champion(&self) -> Option<usize> {
+        let last = self.states.last()?;
+
+        let champion = match (self.format, last.winner?) {
+            // Without a reset, grand finals decided the bracket.
+            (Format::DoubleElimination, Participant::Bye) => {
+                self.states[self.states.len() - 2].winner?
+            }
+            (_, winner) => winner,
+        };
+
+        match champion {
+            Participant::Entrant(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// The placements of the entrants who are out of the bracket, and of the champion once
+    /// there is one, as `(entrant, placement)` from first place down. Entrants who were
+    /// knocked out in the same round share a placement, so an eight player double
+    /// elimination bracket places 1, 2, 3, 4, 5, 5, 7, 7. Entrants who are still playing
+    /// don't have a placement yet.
+    #[must_use]
+    pub fn placements(&self) -> Vec<(usize, u32)> {
+        let lives = match self.format {
+            Format::SingleElimination => 1,
+            Format::DoubleElimination => 2,
+        };
+        let stage = |m: &Match| {
+            let side = match m.side {
+                Side::Winners => 0,
+                Side::Losers => 1,
+                Side::GrandFinals => 2,
+                Side::GrandFinalsReset => 3,
+            };
+            (side, m.round)
+        };
+
+        let mut losses = vec![0; self.entrants];
+        let mut knocked_out = Vec::new();
+        let mut ordered = self.matches.iter().collect::<Vec<_>>();
+        ordered.sort_by_key(|m| stage(m));
+        for m in ordered {
+            if let Some(Participant::Entrant(e)) = self.loser(m.id) {
+                losses[e] += 1;
+                if losses[e] == lives {
+                    knocked_out.push((e, stage(m)));
+                }
+            }
+        }
+
+        let mut placements = self
+            .champion()
+            .map(|champion| (champion, 1))
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        // Everyone who's still in, or who went out later, finished above a knocked out entrant.
+        let still_in = self.entrants - knocked_out.len();
+        for &(entrant, out) in &knocked_out {
+            let above = knocked_out.iter().filter(|(_, s)| *s > out).count();
+            placements.push((
+                entrant,
+                u32::try_from(still_in + above + 1).unwrap_or(u32::MAX),
+            ));
+        }
+
+        // Ties are listed by seed.
+        placements.sort_by_key(|&(entrant, place)| (place, entrant));
+        placements
+    }
+
+    /// Derives the players and results of every match from the reported results. Matches
+    /// are created after the matches they depend on, so one pass is enough.
+    fn refresh(&mut self) {
+        let mut states: Vec<State> = Vec::with_capacity(self.matches.len());
+
+        for m in &self.matches {
+            let slot = |i: usize, source: Source| match source {
+                Source::Seed(seed) if seed < self.entrants => Participant::Entrant(seed),
+                Source::Seed(_) => Participant::Bye,
+                Source::WinnerOf(id) => states[id].winner.unwrap_or(Participant::Pending),
+                Source::LoserOf(id) => states[id].loser.unwrap_or(Participant::Pending),
+                Source::ResetOf(id) => match (states[id].winner, states[id].slots) {
+                    (None, _) => Participant::Pending,
+                    // The reset is only played if the losers bracket champion won.
+                    (Some(winner), slots) if winner == slots[1] => slots[i],
+                    _ => Participant::Bye,
+                },
+            };
+            let slots = [slot(0, m.sources[0]), slot(1, m.sources[1])];
+
+            let (winner, loser) = match (slots, m.result) {
+                ([Participant::Pending, _] | [_, Participant::Pending], _) => (None, None),
+                ([Participant::Bye, other] | [other, Participant::Bye], _) => {
+                    (Some(other), Some(Participant::Bye))
+                }
+                ([a, b], Some(result)) => {
+                    if a == Participant::Entrant(result) {
+                        (Some(a), Some(b))
+                    } else {
+                        (Some(b), Some(a))
+                    }
+                }
+                (_, None) => (None, None),
+            };
+
+            states.push(State {
+                slots,
+                winner,
+                loser,
+            });
+        }
+
+        self.states = states;
+    }
+}
+
+/// The standard seeding order for a bracket of `size` slots, which keeps the top seeds apart
+/// for as long as possible. Seeds are zero based.
+fn seed_order(size: usize) -> Vec<usize> {
+    let mut order = vec![0];
+    while order.len() < size {
+        let len = order.len() * 2;
+        order = order.iter().flat_map(|s| [*s, len - 1 - s]).collect();
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plays out the bracket, where the better seed always wins unless `upset` says otherwise.
+    fn play(bracket: &mut Bracket, upset: impl Fn(&Match) -> bool) {
+        while let Some(id) = bracket.ready_matches().first().copied() {
+            let [Participant::Entrant(a), Participant::Entrant(b)] = bracket.slots(id).unwrap()
+            else {
+                unreachable!()
+            };
+            let (better, worse) = (a.min(b), a.max(b));
+            let winner = if upset(&bracket.matches()[id]) {
+                worse
+            } else {
+                better
+            };
+            bracket.report(id, winner).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_seed_order() {
+        assert_eq!(seed_order(4), vec![0, 3, 1, 2]);
+        assert_eq!(seed_order(8), vec![0, 7, 3, 4, 1, 6, 2, 5]);
+    }
+
+    #[test]
+    fn test_single_elimination() {
+        let mut bracket = Bracket::new(Format::SingleElimination, 8).unwrap();
+
+        assert_eq!(bracket.matches().len(), 7);
+        assert_eq!(bracket.ready_matches().len(), 4);
+
+        play(&mut bracket, |_| false);
+        assert_eq!(bracket.champion(), Some(0));
+    }
+
+    #[test]
+    fn test_byes_advance_top_seeds() {
+        let bracket = Bracket::new(Format::SingleElimination, 5).unwrap();
+
+        // Seeds 0, 1 and 2 have byes, so only 3 and 4 play in the first round, and 1 and 2
+        // already meet in the second.
+        let ready = bracket
+            .ready_matches()
+            .into_iter()
+            .map(|id| bracket.slots(id).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ready,
+            vec![
+                [Participant::Entrant(3), Participant::Entrant(4)],
+                [Participant::Entrant(1), Participant::Entrant(2)],
+            ]
+        );
+        assert_eq!(bracket.winner(0), Some(Participant::Entrant(0)));
+        assert_eq!(bracket.loser(0), Some(Participant::Bye));
+    }
+
+    #[test]
+    fn test_report_validation() {
+        let mut bracket = Bracket::new(Format::SingleElimination, 4).unwrap();
+
+        assert_eq!(bracket.report(9, 0), Err(BracketError::UnknownMatch(9)));
+        // The final is still waiting for its players.
+        assert_eq!(bracket.report(2, 0), Err(BracketError::NotReady(2)));
+        assert_eq!(
+            bracket.report(0, 1),
+            Err(BracketError::NotInMatch { id: 0, entrant: 1 })
+        );
+
+        bracket.report(0, 0).unwrap();
+        assert_eq!(bracket.report(0, 0), Err(BracketError::NotReady(0)));
+    }
+
+    #[test]
+    fn test_double_elimination_without_reset() {
+        let mut bracket = Bracket::new(Format::DoubleElimination, 8).unwrap();
+
+        // 7 winners matches, 6 losers matches, grand finals and the reset.
+        assert_eq!(bracket.matches().len(), 15);
+
+        play(&mut bracket, |_| false);
+
+        let reset = bracket.matches().len() - 1;
+        assert_eq!(bracket.winner(reset), Some(Participant::Bye));
+        assert_eq!(bracket.champion(), Some(0));
+    }
+
+    #[test]
+    fn test_grand_finals_reset() {
+        let mut bracket = Bracket::new(Format::DoubleElimination, 4).unwrap();
+
+        // The top seed loses the first grand finals, but wins the reset.
+        play(&mut bracket, |m| m.side == Side::GrandFinals);
+
+        let reset = bracket.matches().len() - 1;
+        assert_eq!(bracket.matches()[reset].side, Side::GrandFinalsReset);
+        assert_eq!(bracket.result(reset), Some(0));
+        assert_eq!(bracket.champion(), Some(0));
+    }
+
+    #[test]
+    fn test_losers_bracket_champion() {
+        let mut bracket = Bracket::new(Format::DoubleElimination, 4).unwrap();
+
+        // The top seed loses in the first round, then wins the losers bracket and both sets
+        // of grand finals.
+        play(&mut bracket, |m| m.id == 0);
+
+        let grand_finals = bracket.matches().len() - 2;
+        assert_eq!(
+            bracket.slots(grand_finals),
+            Some([Participant::Entrant(1), Participant::Entrant(0)])
+        );
+        assert_eq!(bracket.result(grand_finals + 1), Some(0));
+        assert_eq!(bracket.champion(), Some(0));
+    }
+
+    #[test]
+    fn test_restore() {
+        let mut bracket = Bracket::new(Format::DoubleElimination, 6).unwrap();
+        play(&mut bracket, |m| m.side == Side::Losers);
+
+        let results = bracket
+            .matches()
+            .iter()
+            .rev()
+            .filter_map(|m| Some((m.id, bracket.result(m.id)?)))
+            .collect::<Vec<_>>();
+        let restored = Bracket::restore(Format::DoubleElimination, 6, results).unwrap();
+
+        assert_eq!(restored.champion(), bracket.champion());
+        for m in bracket.matches() {
+            assert_eq!(restored.slots(m.id), bracket.slots(m.id));
+        }
+
+        assert_eq!(
+            Bracket::restore(Format::SingleElimination, 4, [(2, 0)]).unwrap_err(),
+            BracketError::NotReady(2)
+        );
+    }
+
+    #[test]
+    fn test_double_elimination_with_byes() {
+        for entrants in 2..=17 {
+            let mut bracket = Bracket::new(Format::DoubleElimination, entrants).unwrap();
+            play(&mut bracket, |_| false);
+
+            assert_eq!(bracket.champion(), Some(0), "{entrants} entrants");
+            assert!(bracket.ready_matches().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_placements() {
+        let mut bracket = Bracket::new(Format::DoubleElimination, 8).unwrap();
+        play(&mut bracket, |_| false);
+
+        let places = bracket
+            .placements()
+            .into_iter()
+            .map(|(_, place)| place)
+            .collect::<Vec<_>>();
+        assert_eq!(places, vec![1, 2, 3, 4, 5, 5, 7, 7]);
+        assert_eq!(bracket.placements()[..2], [(0, 1), (1, 2)]);
+
+        let mut bracket = Bracket::new(Format::SingleElimination, 6).unwrap();
+        assert!(bracket.placements().is_empty());
+
+        // Only the first round has been played, so the two players who lost it are 5th.
+        let first = bracket.ready_matches();
+        for id in first {
+            let [Participant::Entrant(a), Participant::Entrant(b)] = bracket.slots(id).unwrap()
+            else {
+                unreachable!()
+            };
+            bracket.report(id, a.min(b)).unwrap();
+        }
+        assert_eq!(bracket.placements(), vec![(4, 5), (5, 5)]);
+    }
+
+    #[test]
+    fn test_everyone_loses_twice() {
+        let mut bracket = Bracket::new(Format::DoubleElimination, 6).unwrap();
+        play(&mut bracket, |m| m.round % 2 == 0);
+
+        let mut losses = [0; 6];
+        for m in bracket.matches() {
+            if let Some(Participant::Entrant(e)) = bracket.loser(m.id) {
+                losses[e] += 1;
+            }
+        }
+
+        let champion = bracket.champion().unwrap();
+        for (entrant, count) in losses.iter().enumerate() {
+            if entrant == champion {
+                assert!(*count <= 1);
+            } else {
+                assert_eq!(*count, 2, "entrant {entrant}");
+            }
+        }
+    }
+}
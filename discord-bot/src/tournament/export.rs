@@ -0,0 +1,351 @@
+//! Tournament results in formats that other tools can read.
+//!
+//! An [`Export`] is written as CSV for spreadsheets, as JSON for scripts, and as a Markdown
+//! standings post.
+//!
+//! # JSON schema
+//!
+//! The JSON export is a single object. `schema_version` is bumped whenever a field is
+//! removed or changes meaning, while new fields can be added without a bump, so readers
+//! should ignore fields they don't know about.
+//!
+//! Version 1:
+//!
+//! - `schema_version`: always `1`.
+//! - `tournament`: an object with
+//!   - `name`: string.
+//!   - `format`: string, like `"Double elimination"`.
+//!   - `game`: string or `null`.
+//!   - `entrants`: number of entrants.
+//!   - `started_at`: RFC 3339 time or `null`.
+//!   - `completed`: boolean, `false` if results are still coming in.
+//! - `placements`: array of objects, from first place down, with
+//!   - `placement`: number. Players who went out at the same time share a placement.
+//!   - `player`: a player object.
+//!   - `seed`: the player's bracket seed starting at 1, or `null`.
+//! - `sets`: array of objects, in the order they were played where that's known, with
+//!   - `phase`: string, like `"Swiss"`, `"Pool B"` or `"Bracket"`.
+//!   - `round`: string, like `"Round 2"` or `"Losers round 1"`.
+//!   - `players`: array of two player objects, where the second is `null` for a bye.
+//!   - `winner`: `0` or `1`, the index of the winner in `players`, or `null` if the set
+//!     hasn't been decided.
+//!   - `score`: string like `"2-1"` from the winner's side, or `null` if no score was given.
+//!   - `completed_at`: RFC 3339 time or `null`.
+//!
+//! A player object has a `name` string, and a `discord_id` string (not a number, since
+//! Discord IDs don't fit in a JavaScript number) or `null`.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Export {
+    pub schema_version: u32,
+    pub tournament: TournamentInfo,
+    pub placements: Vec<Placement>,
+    pub sets: Vec<SetRecord>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TournamentInfo {
+    pub name: String,
+    pub format: String,
+    pub game: Option<String>,
+    pub entrants: usize,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Player {
+    pub name: String,
+    pub discord_id: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Placement {
+    pub placement: u32,
+    pub player: Player,
+    pub seed: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SetRecord {
+    pub phase: String,
+    pub round: String,
+    pub players: [Option<Player>; 2],
+    pub winner: Option<usize>,
+    pub score: Option<String>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl Export {
+    #[must_use]
+    pub fn new(
+        tournament: TournamentInfo,
+        placements: Vec<Placement>,
+        sets: Vec<SetRecord>,
+    ) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            tournament,
+            placements,
+            sets,
+        }
+    }
+
+    /// # Errors
+    /// Fails if the export can't be serialized, which shouldn't happen.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    #[must_use]
+    pub fn placements_csv(&self) -> String {
+        let mut csv = String::from("placement,name,discord_id,seed\n");
+        for p in &self.placements {
+            csv_row(
+                &mut csv,
+                &[
+                    &p.placement.to_string(),
+                    &p.player.name,
+                    p.player.discord_id.as_deref().unwrap_or_default(),
+                    &p.seed.map(|s| s.to_string()).unwrap_or_default(),
+                ],
+            );
+        }
+        csv
+    }
+
+    #[must_use]
+    pub fn sets_csv(&self) -> String {
+        let mut csv = String::from(
+            "phase,round,player_one,player_one_discord_id,player_two,player_two_discord_id,\
+             winner,score,completed_at\n",
+        );
+        for set in &self.sets {
+            let name = |i: usize| set.players[i].as_ref().map(|p| p.name.as_str());
+            let id = |i: usize| {
+                set.players[i]
+                    .as_ref()
+                    .and_then(|p| p.discord_id.as_deref())
+            };
+
+            csv_row(
+                &mut csv,
+                &[
+                    &set.phase,
+                    &set.round,
+                    name(0).unwrap_or_default(),
+                    id(0).unwrap_or_default(),
+                    name(1).unwrap_or("bye"),
+                    id(1).unwrap_or_default(),
+                    set.winner.and_then(name).unwrap_or_default(),
+                    set.score.as_deref().unwrap_or_default(),
+                    &set.completed_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+                ],
+            );
+        }
+        csv
+    }
+
+    /// A standings post like the ones shared after an event, with ordinal placements and
+    /// tied players on one line.
+    #[must_use]
+    pub fn standings_markdown(&self) -> String {
+        let mut post = format!("**{}**", self.tournament.name);
+        if let Some(game) = &self.tournament.game {
+            post.push_str(" (");
+            post.push_str(game);
+            post.push(')');
+        }
+        post.push('\n');
+        post.push_str(&self.tournament.entrants.to_string());
+        post.push_str(" entrants, ");
+        post.push_str(&self.tournament.format.to_lowercase());
+        post.push_str("\n\n");
+
+        let mut rest = self.placements.as_slice();
+        while let Some(first) = rest.first() {
+            let tied = rest
+                .iter()
+                .take_while(|p| p.placement == first.placement)
+                .count();
+            let names = rest[..tied]
+                .iter()
+                .map(|p| escape_markdown(&p.player.name))
+                .collect::<Vec<_>>();
+
+            post.push_str(&ordinal(first.placement));
+            post.push_str(": ");
+            post.push_str(&names.join(", "));
+            post.push('\n');
+            rest = &rest[tied..];
+        }
+
+        if !self.tournament.completed {
+            post.push_str("\n*Results are still coming in.*\n");
+        }
+
+        post
+    }
+}
+
+fn csv_row(csv: &mut String, fields: &[&str]) {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            csv.push(',');
+        }
+
+        if field.contains([',', '"', '\n', '\r']) {
+            csv.push('"');
+            csv.push_str(&field.replace('"', "\"\""));
+            csv.push('"');
+        } else {
+            csv.push_str(field);
+        }
+    }
+    csv.push('\n');
+}
+
+/// Player names often have underscores and asterisks in them.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '*' | '_' | '~' | '`' | '|' | '\\' | '>') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn ordinal(n: u32) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{n}{suffix}")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn player(name: &str, id: Option<&str>) -> Player {
+        Player {
+            name: name.to_string(),
+            discord_id: id.map(str::to_string),
+        }
+    }
+
+    fn example() -> Export {
+        let placements = [("Alice", 1), ("Bob_", 2), ("Carol", 3), ("Dan, Jr.", 3)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (name, placement))| Placement {
+                placement,
+                player: player(name, (i == 0).then_some("80351110224678912")),
+                seed: Some(u32::try_from(i + 1).unwrap()),
+            })
+            .collect();
+
+        let sets = vec![
+            SetRecord {
+                phase: "Bracket".to_string(),
+                round: "Final".to_string(),
+                players: [
+                    Some(player("Alice", Some("80351110224678912"))),
+                    Some(player("Bob_", None)),
+                ],
+                winner: Some(0),
+                score: Some("3-1".to_string()),
+                completed_at: None,
+            },
+            SetRecord {
+                phase: "Swiss".to_string(),
+                round: "Round 1".to_string(),
+                players: [Some(player("Dan, Jr.", None)), None],
+                winner: Some(0),
+                score: None,
+                completed_at: None,
+            },
+        ];
+
+        Export::new(
+            TournamentInfo {
+                name: "Weekly #12".to_string(),
+                format: "Single elimination".to_string(),
+                game: Some("Melee".to_string()),
+                entrants: 4,
+                started_at: None,
+                completed: true,
+            },
+            placements,
+            sets,
+        )
+    }
+
+    #[test]
+    fn test_json() {
+        let json: Value = serde_json::from_str(&example().to_json().unwrap()).unwrap();
+
+        assert_eq!(json["schema_version"], 1);
+        assert_eq!(
+            json["placements"][0],
+            json!({
+                "placement": 1,
+                "player": { "name": "Alice", "discord_id": "80351110224678912" },
+                "seed": 1,
+            })
+        );
+        assert_eq!(json["sets"][1]["players"][1], Value::Null);
+        assert_eq!(json["sets"][0]["winner"], 0);
+    }
+
+    #[test]
+    fn test_csv() {
+        let export = example();
+
+        assert_eq!(
+            export.placements_csv(),
+            "placement,name,discord_id,seed\n\
+             1,Alice,80351110224678912,1\n\
+             2,Bob_,,2\n\
+             3,Carol,,3\n\
+             3,\"Dan, Jr.\",,4\n"
+        );
+        assert_eq!(
+            export.sets_csv().lines().nth(2),
+            Some("Swiss,Round 1,\"Dan, Jr.\",,bye,,\"Dan, Jr.\",,")
+        );
+    }
+
+    #[test]
+    fn test_standings_markdown() {
+        assert_eq!(
+            example().standings_markdown(),
+            "**Weekly #12** (Melee)\n\
+             4 entrants, single elimination\n\
+             \n\
+             1st: Alice\n\
+             2nd: Bob\\_\n\
+             3rd: Carol, Dan, Jr.\n"
+        );
+    }
+
+    #[test]
+    fn test_ordinal() {
+        assert_eq!(ordinal(1), "1st");
+        assert_eq!(ordinal(12), "12th");
+        assert_eq!(ordinal(22), "22nd");
+        assert_eq!(ordinal(113), "113th");
+    }
+}
@@ -0,0 +1,8 @@
+//! Tournament logic that doesn't depend on Discord.
+
+pub mod bracket;
+pub mod export;
+pub mod pools;
+pub mod render;
+pub mod seeding;
+pub mod swiss;
@@ -0,0 +1,63 @@
+//! Round robin pools that feed an elimination bracket.
+//!
+//! Entrants are referred to by their seed index, like in the [`super::bracket`] module. The
+//! matches inside a pool are scheduled with [`crate::league::schedule::round_robin`].
+
+/// Splits the seeded entrants into pools, and returns the pool of every entrant.
+///
+/// Seeds snake through the pools (1, 2, 3, 3, 2, 1, ...), so every pool gets a similar
+/// spread of strong and weak players.
+#[must_use]
+pub fn assign_pools(entrants: usize, pools: usize) -> Vec<usize> {
+    let pools = pools.max(1);
+
+    (0..entrants)
+        .map(|seed| {
+            let pass = seed / pools;
+            let offset = seed % pools;
+            if pass % 2 == 1 {
+                pools - 1 - offset
+            } else {
+                offset
+            }
+        })
+        .collect()
+}
+
+/// The players who advance out of their pools, in seed order for the bracket.
+///
+/// `tables` holds every pool's final standings from first to last place. Every pool winner
+/// is seeded above every runner-up and so on, and players with the same placement are
+/// seeded in pool order. This keeps players from the same pool apart in the first round.
+#[must_use]
+pub fn advancing<T: Clone>(tables: &[Vec<T>], advance: usize) -> Vec<T> {
+    (0..advance)
+        .flat_map(|place| tables.iter().filter_map(move |table| table.get(place)))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pools_snake() {
+        assert_eq!(assign_pools(8, 3), [0, 1, 2, 2, 1, 0, 0, 1]);
+        assert_eq!(assign_pools(4, 1), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_advancing_by_placement() {
+        let tables = [vec!["a1", "a2", "a3"], vec!["b1", "b2", "b3"]];
+
+        assert_eq!(advancing(&tables, 2), ["a1", "b1", "a2", "b2"]);
+    }
+
+    #[test]
+    fn test_advancing_from_small_pools() {
+        let tables = [vec!["a1", "a2", "a3"], vec!["b1"]];
+
+        assert_eq!(advancing(&tables, 2), ["a1", "b1", "a2"]);
+    }
+}
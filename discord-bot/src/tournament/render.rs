@@ -0,0 +1,449 @@
+//! Draws brackets and standings tables as SVG, and rasterizes them to PNG.
+//!
+//! Rendering happens entirely in-process. The fonts are bundled with the bot, so the
+//! images look the same no matter which fonts the host has installed.
+
+use std::{
+    fmt::{Display, Write},
+    sync::LazyLock,
+};
+
+use resvg::{
+    tiny_skia::{Pixmap, Transform},
+    usvg::{self, fontdb, PostProcessingSteps, TreeParsing, TreePostProc},
+};
+
+use super::bracket::{Bracket, Participant, Side};
+
+const FONT_FAMILY: &str = "DejaVu Sans";
+const FONT_SIZE: u32 = 13;
+/// A rough average glyph width for the font size, used to size table columns.
+const CHAR_WIDTH: u32 = 8;
+const MAX_NAME_LENGTH: usize = 20;
+
+const MARGIN: u32 = 20;
+const TITLE_HEIGHT: u32 = 40;
+const HEADING_HEIGHT: u32 = 24;
+const BOX_WIDTH: u32 = 190;
+const ROW_HEIGHT: u32 = 22;
+const BOX_HEIGHT: u32 = ROW_HEIGHT * 2;
+const COLUMN_GAP: u32 = 36;
+const BOX_GAP: u32 = 16;
+const CELL_PADDING: u32 = 12;
+
+const BACKGROUND: &str = "#313338";
+const BOX: &str = "#1e1f22";
+const TEXT: &str = "#f2f3f5";
+const MUTED: &str = "#949ba4";
+const ACCENT: &str = "#57f287";
+
+static FONTS: LazyLock<fontdb::Database> = LazyLock::new(|| {
+    let mut fonts = fontdb::Database::new();
+    fonts.load_font_data(include_bytes!("../../assets/fonts/DejaVuSans.ttf").to_vec());
+    fonts.load_font_data(include_bytes!("../../assets/fonts/DejaVuSans-Bold.ttf").to_vec());
+    fonts.set_sans_serif_family(FONT_FAMILY);
+    fonts
+});
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RenderError {
+    /// The SVG couldn't be parsed.
+    Svg(String),
+    /// The image has no size, or is too large to allocate.
+    Size,
+    Encode(String),
+}
+
+impl std::error::Error for RenderError {}
+
+impl Display for RenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderError::Svg(e) => write!(f, "the image couldn't be drawn: {e}"),
+            RenderError::Size => write!(f, "the image is too large to draw"),
+            RenderError::Encode(e) => write!(f, "the image couldn't be encoded: {e}"),
+        }
+    }
+}
+
+/// A standings table, like a Swiss round or a pool.
+#[derive(Clone, Debug, Default)]
+pub struct Table {
+    pub title: String,
+    pub header: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Draws every match of a bracket, with one column per round. The losers bracket is drawn
+/// below the winners bracket, and grand finals to the right of it. `names` are the
+/// entrants in seed order.
+#[must_use]
+pub fn bracket_svg(title: &str, bracket: &Bracket, names: &[String]) -> String {
+    let round_count = |side: Side| {
+        bracket
+            .matches()
+            .iter()
+            .filter(|m| m.side == side)
+            .map(|m| m.round)
+            .max()
+            .unwrap_or(0)
+    };
+    let round_matches = |side: Side, round: u32| {
+        bracket
+            .matches()
+            .iter()
+            .filter(|m| m.side == side && m.round == round)
+            .map(|m| m.id)
+            .collect::<Vec<_>>()
+    };
+    let section_height = |side: Side| {
+        (1..=round_count(side))
+            .map(|round| round_matches(side, round).len())
+            .max()
+            .map_or(0, |n| {
+                u32::try_from(n).unwrap_or(u32::MAX) * (BOX_HEIGHT + BOX_GAP)
+            })
+    };
+
+    let winners_rounds = round_count(Side::Winners);
+    let losers_rounds = round_count(Side::Losers);
+    let winners_height = section_height(Side::Winners);
+    let losers_height = section_height(Side::Losers);
+
+    // The reset is only drawn once it's known to be needed.
+    let finals = bracket
+        .matches()
+        .iter()
+        .filter(|m| match m.side {
+            Side::GrandFinals => true,
+            Side::GrandFinalsReset => bracket
+                .slots(m.id)
+                .is_some_and(|s| s.iter().any(|p| matches!(p, Participant::Entrant(_)))),
+            Side::Winners | Side::Losers => false,
+        })
+        .collect::<Vec<_>>();
+
+    let columns = (winners_rounds + u32::try_from(finals.len()).unwrap_or(0)).max(losers_rounds);
+    let width = MARGIN * 2 + columns.max(1) * (BOX_WIDTH + COLUMN_GAP) - COLUMN_GAP;
+    let winners_top = MARGIN + TITLE_HEIGHT + HEADING_HEIGHT;
+    let losers_top = winners_top + winners_height + HEADING_HEIGHT;
+    let height = if losers_rounds > 0 {
+        losers_top + losers_height + MARGIN
+    } else {
+        winners_top + winners_height + MARGIN
+    };
+
+    let mut svg = open_svg(width, height, title);
+    let double = losers_rounds > 0;
+
+    for (side, top, height, rounds) in [
+        (Side::Winners, winners_top, winners_height, winners_rounds),
+        (Side::Losers, losers_top, losers_height, losers_rounds),
+    ] {
+        for round in 1..=rounds {
+            let x = MARGIN + (round - 1) * (BOX_WIDTH + COLUMN_GAP);
+            let heading = match (side, double) {
+                (Side::Losers, _) => format!("Losers round {round}"),
+                (_, true) => format!("Winners round {round}"),
+                (_, false) if round == rounds => "Final".to_string(),
+                (_, false) => format!("Round {round}"),
+            };
+            text(&mut svg, x, top - 8, MUTED, false, &heading);
+
+            let ids = round_matches(side, round);
+            let slot = height / u32::try_from(ids.len()).unwrap_or(1).max(1);
+            for (i, id) in ids.into_iter().enumerate() {
+                let y = top + slot * u32::try_from(i).unwrap_or(0) + (slot - BOX_HEIGHT) / 2;
+                match_box(&mut svg, bracket, names, id, x, y);
+            }
+        }
+    }
+
+    let finals_y = winners_top + winners_height.saturating_sub(BOX_HEIGHT) / 2;
+    for (i, m) in finals.into_iter().enumerate() {
+        let x =
+            MARGIN + (winners_rounds + u32::try_from(i).unwrap_or(0)) * (BOX_WIDTH + COLUMN_GAP);
+        let heading = if m.side == Side::GrandFinals {
+            "Grand finals"
+        } else {
+            "Grand finals reset"
+        };
+        text(&mut svg, x, finals_y - 8, MUTED, false, heading);
+        match_box(&mut svg, bracket, names, m.id, x, finals_y);
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Draws tables below each other, with each column as wide as its longest cell.
+#[must_use]
+pub fn tables_svg(title: &str, tables: &[Table]) -> String {
+    let table_widths = tables
+        .iter()
+        .map(|table| {
+            let columns = table
+                .rows
+                .iter()
+                .chain(std::iter::once(&table.header))
+                .map(Vec::len)
+                .max()
+                .unwrap_or(0);
+
+            (0..columns)
+                .map(|c| {
+                    let longest = table
+                        .rows
+                        .iter()
+                        .chain(std::iter::once(&table.header))
+                        .filter_map(|row| row.get(c))
+                        .map(|cell| truncate(cell).chars().count())
+                        .max()
+                        .unwrap_or(0);
+                    u32::try_from(longest).unwrap_or(u32::MAX) * CHAR_WIDTH + CELL_PADDING * 2
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let width = table_widths
+        .iter()
+        .map(|w| w.iter().sum::<u32>())
+        .max()
+        .unwrap_or(0)
+        .max(BOX_WIDTH)
+        + MARGIN * 2;
+    let height = MARGIN * 2
+        + TITLE_HEIGHT
+        + tables
+            .iter()
+            .map(|t| {
+                HEADING_HEIGHT
+                    + (u32::try_from(t.rows.len()).unwrap_or(0) + 1) * ROW_HEIGHT
+                    + BOX_GAP
+            })
+            .sum::<u32>();
+
+    let mut svg = open_svg(width, height, title);
+    let mut y = MARGIN + TITLE_HEIGHT;
+
+    for (table, widths) in tables.iter().zip(&table_widths) {
+        if !table.title.is_empty() {
+            text(
+                &mut svg,
+                MARGIN,
+                y + HEADING_HEIGHT - 8,
+                TEXT,
+                true,
+                &table.title,
+            );
+        }
+        y += HEADING_HEIGHT;
+
+        let table_width = widths.iter().sum::<u32>();
+        let _ = write!(
+            svg,
+            r#"<rect x="{MARGIN}" y="{y}" width="{table_width}" height="{}" rx="4" fill="{BOX}"/>"#,
+            (u32::try_from(table.rows.len()).unwrap_or(0) + 1) * ROW_HEIGHT
+        );
+
+        for (r, row) in std::iter::once(&table.header)
+            .chain(&table.rows)
+            .enumerate()
+        {
+            let mut x = MARGIN;
+            for (cell, w) in row.iter().zip(widths) {
+                let color = if r == 0 { MUTED } else { TEXT };
+                text(
+                    &mut svg,
+                    x + CELL_PADDING,
+                    y + ROW_HEIGHT - 7,
+                    color,
+                    false,
+                    cell,
+                );
+                x += w;
+            }
+            y += ROW_HEIGHT;
+        }
+
+        y += BOX_GAP;
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Rasterizes an SVG drawn by this module into a PNG.
+///
+/// # Errors
+/// Fails if the SVG is invalid, or the image is too large.
+pub fn to_png(svg: &str) -> Result<Vec<u8>, RenderError> {
+    let options = usvg::Options {
+        font_family: FONT_FAMILY.to_string(),
+        ..Default::default()
+    };
+
+    let mut tree =
+        usvg::Tree::from_str(svg, &options).map_err(|e| RenderError::Svg(e.to_string()))?;
+    tree.postprocess(PostProcessingSteps::default(), &FONTS);
+
+    let size = tree.size.to_int_size();
+    let mut pixmap = Pixmap::new(size.width(), size.height()).ok_or(RenderError::Size)?;
+    resvg::render(&tree, Transform::default(), &mut pixmap.as_mut());
+
+    pixmap
+        .encode_png()
+        .map_err(|e| RenderError::Encode(e.to_string()))
+}
+
+fn open_svg(width: u32, height: u32, title: &str) -> String {
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="{FONT_FAMILY}" font-size="{FONT_SIZE}"><rect width="100%" height="100%" fill="{BACKGROUND}"/>"#
+    );
+    let _ = write!(
+        svg,
+        r#"<text x="{MARGIN}" y="{}" fill="{TEXT}" font-size="20" font-weight="bold">{}</text>"#,
+        MARGIN + 20,
+        escape(title)
+    );
+    svg
+}
+
+fn match_box(svg: &mut String, bracket: &Bracket, names: &[String], id: usize, x: u32, y: u32) {
+    let _ = write!(
+        svg,
+        r#"<rect x="{x}" y="{y}" width="{BOX_WIDTH}" height="{BOX_HEIGHT}" rx="4" fill="{BOX}"/>"#
+    );
+
+    let winner = bracket.winner(id);
+    let slots = bracket.slots(id).unwrap_or([Participant::Pending; 2]);
+
+    for (row, participant) in (0u32..).zip(slots) {
+        let row_y = y + row * ROW_HEIGHT;
+        let won = winner == Some(participant) && matches!(participant, Participant::Entrant(_));
+
+        if won {
+            let _ = write!(
+                svg,
+                r#"<rect x="{x}" y="{row_y}" width="4" height="{ROW_HEIGHT}" fill="{ACCENT}"/>"#
+            );
+        }
+
+        let (label, color) = match participant {
+            Participant::Entrant(e) => (
+                format!(
+                    "{}  {}",
+                    e + 1,
+                    names.get(e).map_or("Unknown player", String::as_str)
+                ),
+                if winner.is_some() && !won {
+                    MUTED
+                } else {
+                    TEXT
+                },
+            ),
+            Participant::Pending => ("TBD".to_string(), MUTED),
+            Participant::Bye => ("Bye".to_string(), MUTED),
+        };
+        text(svg, x + 10, row_y + ROW_HEIGHT - 7, color, won, &label);
+    }
+
+    let _ = write!(
+        svg,
+        r#"<line x1="{x}" y1="{}" x2="{}" y2="{}" stroke="{BACKGROUND}"/>"#,
+        y + ROW_HEIGHT,
+        x + BOX_WIDTH,
+        y + ROW_HEIGHT
+    );
+}
+
+fn text(svg: &mut String, x: u32, y: u32, color: &str, bold: bool, content: &str) {
+    let weight = if bold { r#" font-weight="bold""# } else { "" };
+    let _ = write!(
+        svg,
+        r#"<text x="{x}" y="{y}" fill="{color}"{weight}>{}</text>"#,
+        escape(&truncate(content))
+    );
+}
+
+/// Shortens long names so they don't run out of their box.
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= MAX_NAME_LENGTH {
+        return text.to_string();
+    }
+
+    let mut short = text.chars().take(MAX_NAME_LENGTH - 1).collect::<String>();
+    short.push('…');
+    short
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tournament::bracket::Format;
+
+    fn names(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("Player {}", i + 1)).collect()
+    }
+
+    #[test]
+    fn test_bracket_shows_results() {
+        let mut bracket = Bracket::new(Format::SingleElimination, 4).unwrap();
+        let first = bracket.ready_matches()[0];
+        let [Participant::Entrant(winner), _] = bracket.slots(first).unwrap() else {
+            panic!("the first match should be ready");
+        };
+        bracket.report(first, winner).unwrap();
+
+        let svg = bracket_svg("Weekly", &bracket, &names(4));
+
+        assert!(svg.contains("Player 4"));
+        assert!(svg.contains("Final"));
+        assert!(svg.contains(ACCENT));
+        assert!(svg.contains("TBD"));
+    }
+
+    #[test]
+    fn test_double_elimination_sections() {
+        let bracket = Bracket::new(Format::DoubleElimination, 4).unwrap();
+        let svg = bracket_svg("Weekly", &bracket, &names(4));
+
+        assert!(svg.contains("Winners round 1"));
+        assert!(svg.contains("Losers round 1"));
+        assert!(svg.contains("Grand finals"));
+        // The reset isn't needed yet.
+        assert!(!svg.contains("Grand finals reset"));
+    }
+
+    #[test]
+    fn test_text_is_escaped_and_truncated() {
+        let table = Table {
+            title: "Pool A".to_string(),
+            header: vec!["#".to_string(), "Player".to_string()],
+            rows: vec![vec![
+                "1".to_string(),
+                "<script> & a very long name indeed".to_string(),
+            ]],
+        };
+        let svg = tables_svg("Pools", &[table]);
+
+        assert!(svg.contains("&lt;script&gt; &amp; a very l…"));
+        assert!(!svg.contains("<script>"));
+    }
+
+    #[test]
+    fn test_to_png() {
+        let bracket = Bracket::new(Format::SingleElimination, 3).unwrap();
+        let png = to_png(&bracket_svg("Weekly", &bracket, &names(3))).unwrap();
+
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    }
+}
@@ -0,0 +1,93 @@
+//! Seeding entrants before a bracket is generated.
+
+use std::cmp::Reverse;
+
+use rand::{seq::SliceRandom, Rng};
+
+/// Orders entrants from the highest rating to the lowest. Entrants with the same rating are
+/// put in a random order.
+pub fn by_rating<T, R: Rng + ?Sized>(mut entrants: Vec<(T, i32)>, rng: &mut R) -> Vec<T> {
+    entrants.shuffle(rng);
+    // The sort is stable, so the shuffled order is kept within each rating.
+    entrants.sort_by_key(|(_, rating)| Reverse(*rating));
+
+    entrants.into_iter().map(|(entrant, _)| entrant).collect()
+}
+
+/// Keeps entrants that `conflict` apart in the first round of the bracket.
+///
+/// When two first round opponents conflict, the lower seed is swapped with the closest
+/// seed that doesn't conflict with either of its new opponents, so that seeds move as little
+/// as possible. Conflicts that can't be resolved this way are left alone.
+pub fn separate<T>(seeds: &mut [T], conflict: impl Fn(&T, &T) -> bool) {
+    let entrants = seeds.len();
+    let size = entrants.next_power_of_two();
+
+    for top in 0..size / 2 {
+        // In the first round, the top seeds play the bottom seeds.
+        let bottom = size - 1 - top;
+        if bottom >= entrants || !conflict(&seeds[top], &seeds[bottom]) {
+            continue;
+        }
+
+        let swap = (1..entrants)
+            .flat_map(|distance| [bottom.checked_sub(distance), bottom.checked_add(distance)])
+            .flatten()
+            .filter(|k| (size / 2..entrants).contains(k))
+            .find(|&k| {
+                !conflict(&seeds[top], &seeds[k]) && !conflict(&seeds[size - 1 - k], &seeds[bottom])
+            });
+
+        if let Some(k) = swap {
+            seeds.swap(bottom, k);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn test_by_rating() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let seeds = by_rating(
+            vec![("c", 1400), ("a", 1700), ("b1", 1500), ("b2", 1500)],
+            &mut rng,
+        );
+
+        assert_eq!(seeds[0], "a");
+        assert!(seeds[1..3].contains(&"b1") && seeds[1..3].contains(&"b2"));
+        assert_eq!(seeds[3], "c");
+    }
+
+    #[test]
+    fn test_ties_are_random() {
+        let entrants = (0..8).map(|e| (e, 1500)).collect::<Vec<_>>();
+
+        let orders = (0..4)
+            .map(|s| by_rating(entrants.clone(), &mut StdRng::seed_from_u64(s)))
+            .collect::<Vec<_>>();
+        assert!(orders.iter().any(|o| *o != orders[0]));
+    }
+
+    #[test]
+    fn test_separate_first_round() {
+        // Seeds 1 and 4 are on the same team, and would meet in the first round.
+        let mut seeds = [("a", 1), ("b", 2), ("c", 2), ("d", 1)];
+        separate(&mut seeds, |x, y| x.1 == y.1);
+
+        assert_eq!(seeds, [("a", 1), ("b", 2), ("d", 1), ("c", 2)]);
+    }
+
+    #[test]
+    fn test_separate_with_byes() {
+        // With six entrants, seeds 1 and 2 have byes, and seeds 3-6 and 4-5 conflict.
+        let mut seeds = [1, 2, 3, 4, 5, 6];
+        separate(&mut seeds, |x, y| x + y == 9);
+
+        assert_eq!(seeds, [1, 2, 3, 4, 6, 5]);
+    }
+}
@@ -0,0 +1,309 @@
+//! Swiss-system rounds.
+//!
+//! Entrants are referred to by their index, like in the [`super::bracket`] module. Every
+//! round pairs entrants with the same record where possible, and nobody plays the same
+//! opponent twice unless there's no other way to pair the round, or finding one takes too
+//! long.
+
+use std::cmp::Ordering;
+
+/// How many opponents the pairing search tries before it gives up on avoiding rematches.
+/// The search is exponential when a round can't be paired without a rematch.
+const MAX_PAIRING_ATTEMPTS: usize = 10_000;
+
+/// A match in a Swiss round. A match without a second entrant is a bye, which counts as a
+/// win for the entrant that gets it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pairing {
+    pub one: usize,
+    pub two: Option<usize>,
+    pub winner: Option<usize>,
+}
+
+impl Pairing {
+    #[must_use]
+    pub fn is_bye(&self) -> bool {
+        self.two.is_none()
+    }
+
+    fn is_between(&self, a: usize, b: usize) -> bool {
+        (self.one == a && self.two == Some(b)) || (self.one == b && self.two == Some(a))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Standing {
+    pub entrant: usize,
+    /// Wins, including byes.
+    pub wins: u32,
+    pub losses: u32,
+    pub byes: u32,
+    /// The sum of the opponents' wins.
+    pub buchholz: u32,
+    /// The average match win percentage of the opponents, where each opponent's percentage
+    /// is floored at a third so that players aren't punished too hard for beating players
+    /// who dropped to the bottom.
+    pub opponent_win_rate: f64,
+}
+
+/// The number of rounds needed to find a single undefeated player.
+#[must_use]
+pub fn rounds_for(entrants: usize) -> u32 {
+    entrants.max(2).next_power_of_two().trailing_zeros()
+}
+
+/// Ranks the entrants by wins, then Buchholz, then opponent match win percentage. Entrants
+/// that are still tied stay in their original order. Only decided matches are counted.
+#[must_use]
+pub fn standings(entrants: usize, matches: &[Pairing]) -> Vec<Standing> {
+    let mut wins = vec![0u32; entrants];
+    let mut losses = vec![0u32; entrants];
+    let mut byes = vec![0u32; entrants];
+    let mut opponents = vec![Vec::new(); entrants];
+
+    for m in matches {
+        match (m.two, m.winner) {
+            (None, _) => {
+                wins[m.one] += 1;
+                byes[m.one] += 1;
+            }
+            (Some(two), Some(winner)) => {
+                let loser = if winner == m.one { two } else { m.one };
+                wins[winner] += 1;
+                losses[loser] += 1;
+                opponents[m.one].push(two);
+                opponents[two].push(m.one);
+            }
+            (Some(_), None) => {}
+        }
+    }
+
+    let win_rate = |entrant: usize| {
+        let played = wins[entrant] + losses[entrant];
+        if played == 0 {
+            return 1.0 / 3.0;
+        }
+        (f64::from(wins[entrant]) / f64::from(played)).max(1.0 / 3.0)
+    };
+
+    let mut table = (0..entrants)
+        .map(|entrant| {
+            let faced = &opponents[entrant];
+            #[allow(clippy::cast_precision_loss)]
+            let opponent_win_rate = if faced.is_empty() {
+                0.0
+            } else {
+                faced.iter().map(|o| win_rate(*o)).sum::<f64>() / faced.len() as f64
+            };
+
+            Standing {
+                entrant,
+                wins: wins[entrant],
+                losses: losses[entrant],
+                byes: byes[entrant],
+                buchholz: faced.iter().map(|o| wins[*o]).sum(),
+                opponent_win_rate,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    table.sort_by(|a, b| {
+        b.wins
+            .cmp(&a.wins)
+            .then(b.buchholz.cmp(&a.buchholz))
+            .then(
+                b.opponent_win_rate
+                    .partial_cmp(&a.opponent_win_rate)
+                    .unwrap_or(Ordering::Equal),
+            )
+            .then(a.entrant.cmp(&b.entrant))
+    });
+
+    table
+}
+
+/// Pairs the next round. Entrants are paired with their neighbours in the standings, so
+/// that players with the same record meet, and rematches are avoided when possible. If the
+/// search for a round without rematches takes too long, neighbours are paired anyway. If
+/// there's an odd number of entrants, the lowest ranked entrant who hasn't had a bye yet
+/// gets one.
+#[must_use]
+pub fn pair(entrants: usize, matches: &[Pairing]) -> Vec<Pairing> {
+    let mut order = standings(entrants, matches)
+        .into_iter()
+        .map(|s| s.entrant)
+        .collect::<Vec<_>>();
+
+    let mut round = Vec::with_capacity(entrants / 2 + 1);
+
+    if order.len() % 2 == 1 {
+        let had_bye = |e: usize| matches.iter().any(|m| m.one == e && m.is_bye());
+        let bye = order
+            .iter()
+            .rev()
+            .copied()
+            .find(|e| !had_bye(*e))
+            .unwrap_or(order[order.len() - 1]);
+
+        order.retain(|e| *e != bye);
+        round.push(Pairing {
+            one: bye,
+            two: None,
+            winner: Some(bye),
+        });
+    }
+
+    let played = |a: usize, b: usize| matches.iter().any(|m| m.is_between(a, b));
+    let mut attempts = 0;
+    let pairs = pair_off(&order, &played, &mut attempts)
+        .unwrap_or_else(|| order.chunks(2).map(|c| (c[0], c[1])).collect());
+
+    let mut pairings = pairs
+        .into_iter()
+        .map(|(one, two)| Pairing {
+            one,
+            two: Some(two),
+            winner: None,
+        })
+        .collect::<Vec<_>>();
+    pairings.append(&mut round);
+
+    pairings
+}
+
+/// Pairs the first remaining entrant with the highest ranked opponent they haven't played
+/// yet, backtracking if the rest of the entrants can't be paired after that. Gives up once
+/// `attempts` reaches [`MAX_PAIRING_ATTEMPTS`].
+fn pair_off(
+    remaining: &[usize],
+    played: &impl Fn(usize, usize) -> bool,
+    attempts: &mut usize,
+) -> Option<Vec<(usize, usize)>> {
+    let Some((&first, rest)) = remaining.split_first() else {
+        return Some(Vec::new());
+    };
+
+    for (i, &opponent) in rest.iter().enumerate() {
+        if played(first, opponent) {
+            continue;
+        }
+
+        *attempts += 1;
+        if *attempts > MAX_PAIRING_ATTEMPTS {
+            return None;
+        }
+
+        let mut others = rest.to_vec();
+        others.remove(i);
+
+        if let Some(mut pairs) = pair_off(&others, played, attempts) {
+            pairs.insert(0, (first, opponent));
+            return Some(pairs);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn win(one: usize, two: usize) -> Pairing {
+        Pairing {
+            one,
+            two: Some(two),
+            winner: Some(one),
+        }
+    }
+
+    fn pairs(round: &[Pairing]) -> Vec<(usize, Option<usize>)> {
+        round.iter().map(|p| (p.one, p.two)).collect()
+    }
+
+    #[test]
+    fn test_rounds_for() {
+        assert_eq!(rounds_for(2), 1);
+        assert_eq!(rounds_for(8), 3);
+        assert_eq!(rounds_for(9), 4);
+        assert_eq!(rounds_for(32), 5);
+    }
+
+    #[test]
+    fn test_pairs_equal_records() {
+        let history = [win(0, 1), win(2, 3)];
+
+        assert_eq!(pairs(&pair(4, &history)), [(0, Some(2)), (1, Some(3))]);
+    }
+
+    #[test]
+    fn test_avoids_rematches() {
+        // Entrant 0 already played both 1 and 2, so it has to play 3.
+        let history = [win(0, 1), win(2, 3), win(0, 2), win(1, 3)];
+
+        let round = pair(4, &history);
+        for p in &round {
+            assert!(!history.iter().any(|h| h.is_between(p.one, p.two.unwrap())));
+        }
+        assert_eq!(round.len(), 2);
+    }
+
+    #[test]
+    fn test_gives_up_on_impossible_rounds() {
+        // The last entrant has played everyone, so the round can't avoid a rematch, and
+        // an exhaustive search would try every way of pairing the others first.
+        let history = (0..29).map(|e| win(e, 29)).collect::<Vec<_>>();
+
+        let round = pair(30, &history);
+        assert_eq!(round.len(), 15);
+        let mut paired = round
+            .iter()
+            .flat_map(|p| [p.one, p.two.unwrap()])
+            .collect::<Vec<_>>();
+        paired.sort_unstable();
+        assert_eq!(paired, (0..30).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_bye_goes_to_lowest_ranked_without_one() {
+        let first = pair(3, &[]);
+        assert_eq!(pairs(&first), [(0, Some(1)), (2, None)]);
+
+        // Entrant 0 is last now, and entrant 2 already had a bye.
+        let history = [win(1, 0), first[1]];
+        let second = pair(3, &history);
+        assert_eq!(second.iter().find(|p| p.is_bye()).unwrap().one, 0);
+        assert_eq!(second.len(), 2);
+    }
+
+    #[test]
+    fn test_tiebreakers() {
+        // 0 beat 1 and 2, 1 beat 3, 2 beat 3 and 1.
+        let history = [win(0, 1), win(2, 3), win(0, 2), win(1, 3), win(2, 1)];
+        let table = standings(4, &history);
+
+        // 0 and 2 are tied on wins and Buchholz, but 2 faced tougher opponents.
+        assert_eq!(
+            table.iter().map(|s| s.entrant).collect::<Vec<_>>(),
+            [2, 0, 1, 3]
+        );
+        assert_eq!(table[0].buchholz, 3);
+        assert_eq!(table[1].buchholz, 3);
+        // Entrant 2 faced 3 (0-2, floored to a third), 0 (2-0) and 1 (1-2).
+        assert!((table[0].opponent_win_rate - 5.0 / 9.0).abs() < 1e-9);
+        // Entrant 0 faced 1 (1-2, floored to a third) and 2 (2-1).
+        assert!((table[1].opponent_win_rate - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_unreported_matches_are_ignored() {
+        let history = [Pairing {
+            one: 0,
+            two: Some(1),
+            winner: None,
+        }];
+        let table = standings(2, &history);
+
+        assert!(table.iter().all(|s| s.wins == 0 && s.buchholz == 0));
+    }
+}